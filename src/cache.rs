@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::error::GraphError;
+use crate::graph::Graph;
+
+struct Entry<V, E> {
+    distances: HashMap<V, E>,
+    last_used: u64,
+}
+
+// keeps the results of recent dijkstra_paths calls around, forgetting
+// the least recently used source once it's holding `capacity` of them.
+// the graph can only be changed through the cache, and since any new
+// vertex or edge (or changed weight) could shorten any path, every
+// change empties it.
+pub struct CachedShortestPaths<V: Hash + Eq, E> {
+    graph: Graph<V, E>,
+    capacity: usize,
+    entries: HashMap<V, Entry<V, E>>,
+    clock: u64,
+}
+
+impl<V, E> CachedShortestPaths<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    pub fn new(graph: Graph<V, E>, capacity: usize) -> Self {
+        Self {
+            graph,
+            capacity,
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    pub fn graph(&self) -> &Graph<V, E> {
+        &self.graph
+    }
+
+    pub fn into_graph(self) -> Graph<V, E> {
+        self.graph
+    }
+
+    pub fn add_vertex(&mut self, value: V) {
+        self.entries.clear();
+        self.graph.add_vertex(value);
+    }
+
+    pub fn connect_vertices(&mut self, v1: &V, v2: &V, edge_value: E) -> Result<(), GraphError> {
+        self.graph.connect_vertices(v1, v2, edge_value)?;
+        self.entries.clear();
+
+        Ok(())
+    }
+
+    pub fn connect_directed(&mut self, v1: &V, v2: &V, edge_value: E) -> Result<(), GraphError> {
+        self.graph.connect_directed(v1, v2, edge_value)?;
+        self.clear();
+
+        Ok(())
+    }
+
+    // the cache is emptied whether the new value is bigger or smaller,
+    // as long as there was an edge to change.
+    pub fn set_edge_value(&mut self, v1: &V, v2: &V, value: E) -> Option<E> {
+        let old = self.graph.set_edge_value(v1, v2, value)?;
+        self.clear();
+
+        Some(old)
+    }
+
+    pub fn connect_or_update(
+        &mut self,
+        v1: &V,
+        v2: &V,
+        edge_value: E,
+    ) -> Result<Option<E>, GraphError> {
+        let old = self.graph.connect_or_update(v1, v2, edge_value)?;
+        self.clear();
+
+        Ok(old)
+    }
+
+    // removing things can only make paths longer, but it still means
+    // emptying the cache, as long as something was actually removed.
+    pub fn remove_vertex(&mut self, vertex: &V) -> Option<V> {
+        let removed = self.graph.remove_vertex(vertex)?;
+        self.entries.clear();
+
+        Some(removed)
+    }
+
+    pub fn remove_edge(&mut self, v1: &V, v2: &V) -> Option<E> {
+        let removed = self.graph.remove_edge(v1, v2)?;
+        self.entries.clear();
+
+        Some(removed)
+    }
+
+    pub fn disconnect_all(&mut self, vertex: &V) -> Vec<E> {
+        let removed = self.graph.disconnect_all(vertex);
+
+        if !removed.is_empty() {
+            self.entries.clear();
+        }
+
+        removed
+    }
+
+    pub fn dijkstra_paths(&mut self, source: &V) -> &HashMap<V, E> {
+        self.clock += 1;
+
+        if !self.entries.contains_key(source) {
+            if self.entries.len() >= self.capacity {
+                let oldest = self
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(vertex, _)| vertex.clone());
+
+                if let Some(oldest) = oldest {
+                    self.entries.remove(&oldest);
+                }
+            }
+
+            let distances = self
+                .graph
+                .dijkstra_paths(source)
+                .into_iter()
+                .map(|(vertex, dist)| (vertex.clone(), dist))
+                .collect();
+
+            self.entries.insert(
+                source.clone(),
+                Entry {
+                    distances,
+                    last_used: 0,
+                },
+            );
+        }
+
+        let entry = self.entries.get_mut(source).unwrap();
+        entry.last_used = self.clock;
+        &entry.distances
+    }
+
+    // the shortest distance between two vertices, where repeated
+    // queries from the same source share a single search.
+    pub fn distance(&mut self, source: &V, target: &V) -> Option<E> {
+        self.dijkstra_paths(source).get(target).cloned()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
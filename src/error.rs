@@ -0,0 +1,80 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::limits::Limit;
+
+// what went wrong in any of the graph operations that can fail, other
+// than the few with something more to give back (like CycleError's
+// cycle, or LimitExceeded's partial answer). the variants with a
+// message are the broad kinds of failure, with the message saying
+// exactly what it was. syntax errors while reading a graph say which
+// line (counting from one) they were found on.
+#[derive(Debug)]
+pub enum GraphError {
+    VertexNotFound,
+    EdgeNotFound,
+    // a parallel edge, in a graph that isn't a multigraph.
+    DuplicateEdge,
+    NegativeWeight,
+    Unreachable,
+    Overflow,
+    // an argument that doesn't make sense, like a stretch below one.
+    InvalidArgument(&'static str),
+    // a path that isn't what it was meant to be.
+    InvalidPath(&'static str),
+    // a graph the algorithm can't work with, like one with loops.
+    Unsupported(&'static str),
+    // a graph with no answer at all, like an odd degree vertex for an
+    // eulerian orientation.
+    Infeasible(&'static str),
+    // a search that ran out of its budget before finding an answer.
+    Limit(Limit),
+    Io(io::Error),
+    Compression(&'static str),
+    Syntax { line: usize, message: &'static str },
+}
+
+impl GraphError {
+    pub(crate) fn syntax(line: usize, message: &'static str) -> Self {
+        GraphError::Syntax { line, message }
+    }
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GraphError::VertexNotFound => write!(f, "Graph does not contain the vertex."),
+            GraphError::EdgeNotFound => write!(f, "Graph does not contain the edge."),
+            GraphError::DuplicateEdge => {
+                write!(f, "Graph already has an edge between the vertices.")
+            }
+            GraphError::NegativeWeight => write!(f, "Graph has a negative edge weight."),
+            GraphError::Unreachable => write!(f, "Target cannot be reached."),
+            GraphError::Overflow => write!(f, "Distance overflowed the edge weight type."),
+            GraphError::InvalidArgument(message)
+            | GraphError::InvalidPath(message)
+            | GraphError::Unsupported(message)
+            | GraphError::Infeasible(message)
+            | GraphError::Compression(message) => write!(f, "{}", message),
+            GraphError::Limit(limit) => write!(f, "Search exceeded its {} limit.", limit),
+            GraphError::Io(error) => write!(f, "Could not read graph: {}", error),
+            GraphError::Syntax { line, message } => write!(f, "Line {}: {}", line, message),
+        }
+    }
+}
+
+impl Error for GraphError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GraphError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GraphError {
+    fn from(error: io::Error) -> Self {
+        GraphError::Io(error)
+    }
+}
@@ -0,0 +1,114 @@
+use crate::graph::data::GraphData;
+use crate::graph::hops::Unweighted;
+
+// well known small graphs, for tests and examples. each is built from
+// scratch on every call, so they can be changed freely afterwards.
+
+// the graph from main, with six vertices and a few alternative routes.
+// demo_distances gives the shortest distances from "A".
+pub fn demo() -> GraphData<&'static str, u32> {
+    let mut data = GraphData::new();
+    let names = ["A", "B", "C", "D", "E", "F"];
+
+    for name in names.iter() {
+        data.add_vertex(*name);
+    }
+
+    let edges = [
+        (0, 1, 6),
+        (0, 2, 5),
+        (1, 2, 3),
+        (1, 3, 4),
+        (2, 3, 3),
+        (2, 4, 7),
+        (2, 5, 10),
+        (3, 4, 5),
+        (4, 5, 4),
+    ];
+
+    for &(v1, v2, weight) in edges.iter() {
+        data.connect(v1, v2, weight);
+    }
+
+    data
+}
+
+pub fn demo_distances() -> Vec<(&'static str, u32)> {
+    vec![("B", 6), ("C", 5), ("D", 8), ("E", 12), ("F", 15)]
+}
+
+// euler's seven bridges of königsberg, joining the two banks of the
+// pregel, the kneiphof island and the lomse island. every land mass
+// has an odd number of bridges, which is why there's no walk that
+// crosses each of them exactly once. note the parallel edges.
+pub fn konigsberg() -> GraphData<&'static str, Unweighted> {
+    let mut data = GraphData::new();
+    let north = data.add_vertex("North bank");
+    let south = data.add_vertex("South bank");
+    let kneiphof = data.add_vertex("Kneiphof");
+    let lomse = data.add_vertex("Lomse");
+
+    let bridges = [
+        (kneiphof, north),
+        (kneiphof, north),
+        (kneiphof, south),
+        (kneiphof, south),
+        (kneiphof, lomse),
+        (north, lomse),
+        (south, lomse),
+    ];
+
+    for &(v1, v2) in bridges.iter() {
+        data.connect(v1, v2, Unweighted);
+    }
+
+    data
+}
+
+// zachary's karate club: 34 members, numbered from 1 as in the paper,
+// and the 78 friendships between them. the club later split into the
+// groups led by the instructor (1) and the administrator (34).
+pub fn karate_club() -> GraphData<usize, Unweighted> {
+    const FRIENDS: [(usize, &[usize]); 26] = [
+        (1, &[2, 3, 4, 5, 6, 7, 8, 9, 11, 12, 13, 14, 18, 20, 22, 32]),
+        (2, &[3, 4, 8, 14, 18, 20, 22, 31]),
+        (3, &[4, 8, 9, 10, 14, 28, 29, 33]),
+        (4, &[8, 13, 14]),
+        (5, &[7, 11]),
+        (6, &[7, 11, 17]),
+        (7, &[17]),
+        (9, &[31, 33, 34]),
+        (10, &[34]),
+        (14, &[34]),
+        (15, &[33, 34]),
+        (16, &[33, 34]),
+        (19, &[33, 34]),
+        (20, &[34]),
+        (21, &[33, 34]),
+        (23, &[33, 34]),
+        (24, &[26, 28, 30, 33, 34]),
+        (25, &[26, 28, 32]),
+        (26, &[32]),
+        (27, &[30, 34]),
+        (28, &[34]),
+        (29, &[32, 34]),
+        (30, &[33, 34]),
+        (31, &[33, 34]),
+        (32, &[33, 34]),
+        (33, &[34]),
+    ];
+
+    let mut data = GraphData::new();
+
+    for member in 1..=34 {
+        data.add_vertex(member);
+    }
+
+    for &(member, friends) in FRIENDS.iter() {
+        for &friend in friends.iter() {
+            data.connect(member - 1, friend - 1, Unweighted);
+        }
+    }
+
+    data
+}
@@ -0,0 +1,233 @@
+use std::collections::VecDeque;
+use std::ops::{Add, Sub};
+use std::time::Instant;
+
+use crate::metrics::{Counters, Recorder};
+
+// a directed flow network over numbered nodes, used as the engine for
+// the cut and flow based algorithms. capacities can be any numeric
+// type whose default value is zero, which covers integers and floats.
+#[derive(Debug, Clone)]
+pub struct FlowNetwork<C> {
+    adjacency: Vec<Vec<usize>>,
+    arcs: Vec<Arc<C>>,
+    // the tally for the current run of max_flow.
+    counters: Counters,
+}
+
+#[derive(Debug, Clone)]
+struct Arc<C> {
+    to: usize,
+    capacity: C,
+    residual: C,
+}
+
+impl<C> FlowNetwork<C>
+where
+    C: Add<Output = C> + Sub<Output = C> + PartialOrd + Copy + Default,
+{
+    pub fn new(nodes: usize) -> Self {
+        Self {
+            adjacency: vec![Vec::new(); nodes],
+            arcs: Vec::new(),
+            counters: Counters::default(),
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    pub fn add_node(&mut self) -> usize {
+        self.adjacency.push(Vec::new());
+        self.adjacency.len() - 1
+    }
+
+    // every arc is stored next to its reverse residual arc, so that
+    // the partner of arc i is always arc i ^ 1. only residuals are
+    // tracked, which keeps unsigned capacities from going negative.
+    pub fn add_arc(&mut self, from: usize, to: usize, capacity: C) -> usize {
+        let index = self.arcs.len();
+
+        self.arcs.push(Arc {
+            to,
+            capacity,
+            residual: capacity,
+        });
+        self.arcs.push(Arc {
+            to: from,
+            capacity: C::default(),
+            residual: C::default(),
+        });
+
+        self.adjacency[from].push(index);
+        self.adjacency[to].push(index + 1);
+
+        index
+    }
+
+    pub fn flow(&self, arc: usize) -> C {
+        self.arcs[arc].capacity - self.arcs[arc].residual
+    }
+
+    pub fn arc_endpoints(&self, arc: usize) -> (usize, usize) {
+        (self.arcs[arc ^ 1].to, self.arcs[arc].to)
+    }
+
+    // dinic's algorithm: repeatedly build a level graph with a bfs and
+    // saturate it with blocking flows found by depth-first searches.
+    pub fn max_flow(&mut self, source: usize, sink: usize) -> C {
+        self.max_flow_recorded(source, sink, &mut ())
+    }
+
+    pub fn max_flow_recorded<R: Recorder>(
+        &mut self,
+        source: usize,
+        sink: usize,
+        recorder: &mut R,
+    ) -> C {
+        let started = Instant::now();
+        self.counters = Counters::default();
+
+        let total = self.dinic(source, sink);
+
+        self.counters.elapsed = started.elapsed();
+        recorder.record("max_flow", &self.counters);
+
+        total
+    }
+
+    fn dinic(&mut self, source: usize, sink: usize) -> C {
+        let mut total = C::default();
+
+        if source == sink {
+            return total;
+        }
+
+        loop {
+            let levels = self.levels(source);
+            if levels[sink].is_none() {
+                break;
+            }
+
+            // remembers how far through each adjacency list we've got,
+            // so that dead ends aren't explored more than once.
+            let mut next_arc = vec![0; self.adjacency.len()];
+
+            while let Some(pushed) = self.augment(source, sink, &levels, &mut next_arc) {
+                total = total + pushed;
+            }
+        }
+
+        total
+    }
+
+    fn levels(&mut self, source: usize) -> Vec<Option<usize>> {
+        let mut levels = vec![None; self.adjacency.len()];
+        let mut queue = VecDeque::new();
+
+        levels[source] = Some(0);
+        queue.push_back(source);
+        self.counters.queue_operations += 1;
+
+        while let Some(node) = queue.pop_front() {
+            self.counters.settled += 1;
+            self.counters.queue_operations += 1;
+
+            for &arc in self.adjacency[node].iter() {
+                let to = self.arcs[arc].to;
+                self.counters.relaxed += 1;
+
+                if levels[to].is_none() && self.arcs[arc].residual > C::default() {
+                    levels[to] = Some(levels[node].unwrap() + 1);
+                    queue.push_back(to);
+                    self.counters.queue_operations += 1;
+                }
+            }
+        }
+
+        levels
+    }
+
+    // finds one path to the sink through the level graph and pushes as
+    // much as it can along it. the path is kept as a stack of arcs
+    // rather than by recursing, since it can be as long as the network
+    // has nodes.
+    fn augment(
+        &mut self,
+        source: usize,
+        sink: usize,
+        levels: &[Option<usize>],
+        next_arc: &mut [usize],
+    ) -> Option<C> {
+        let mut path: Vec<usize> = Vec::new();
+        let mut node = source;
+
+        while node != sink {
+            let mut advanced = false;
+
+            while next_arc[node] < self.adjacency[node].len() {
+                let arc = self.adjacency[node][next_arc[node]];
+                let to = self.arcs[arc].to;
+                self.counters.relaxed += 1;
+
+                let downhill = match (levels[node], levels[to]) {
+                    (Some(here), Some(there)) => there == here + 1,
+                    _ => false,
+                };
+
+                if downhill && self.arcs[arc].residual > C::default() {
+                    path.push(arc);
+                    node = to;
+                    advanced = true;
+                    break;
+                }
+
+                next_arc[node] += 1;
+            }
+
+            // a dead end, so step back and skip the arc that led here.
+            if !advanced {
+                let arc = path.pop()?;
+                node = self.arcs[arc ^ 1].to;
+                next_arc[node] += 1;
+            }
+        }
+
+        let mut pushed = self.arcs[path[0]].residual;
+
+        for &arc in path.iter() {
+            if self.arcs[arc].residual < pushed {
+                pushed = self.arcs[arc].residual;
+            }
+        }
+
+        for &arc in path.iter() {
+            self.arcs[arc].residual = self.arcs[arc].residual - pushed;
+            self.arcs[arc ^ 1].residual = self.arcs[arc ^ 1].residual + pushed;
+        }
+
+        Some(pushed)
+    }
+
+    // after a max flow, the nodes still reachable from the source in
+    // the residual network form the source side of a minimum cut.
+    pub fn min_cut(&self, source: usize) -> Vec<bool> {
+        let mut reachable = vec![false; self.adjacency.len()];
+        let mut stack = vec![source];
+        reachable[source] = true;
+
+        while let Some(node) = stack.pop() {
+            for &arc in self.adjacency[node].iter() {
+                let to = self.arcs[arc].to;
+
+                if !reachable[to] && self.arcs[arc].residual > C::default() {
+                    reachable[to] = true;
+                    stack.push(to);
+                }
+            }
+        }
+
+        reachable
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt;
+
+use crate::graph::data::GraphData;
+use crate::graph::geojson::Located;
+use crate::random::Rng;
+
+// a junction in a generated road network, at a position in metres.
+// the id is its index in the generated data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Junction {
+    pub id: usize,
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Located for Junction {
+    fn coordinates(&self) -> (f64, f64) {
+        (self.x as f64, self.y as f64)
+    }
+}
+
+impl fmt::Display for Junction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RoadNetworkConfig {
+    pub columns: usize,
+    pub rows: usize,
+    // the distance between neighbouring junctions on the grid.
+    pub spacing: f64,
+    // how far junctions may be pushed off the grid, and how much
+    // longer than a straight line a road may be, as fractions.
+    pub distortion: f64,
+    // the chance that a local road is left out, as long as that
+    // doesn't cut anything off.
+    pub deletion: f64,
+    // every this many rows and columns is a highway, which is never
+    // deleted and is this many times faster than a local road.
+    pub highway_every: usize,
+    pub highway_speedup: f64,
+}
+
+impl Default for RoadNetworkConfig {
+    fn default() -> Self {
+        Self {
+            columns: 32,
+            rows: 32,
+            spacing: 100.0,
+            distortion: 0.3,
+            deletion: 0.25,
+            highway_every: 8,
+            highway_speedup: 3.0,
+        }
+    }
+}
+
+// a road-like graph for benchmarking routing: a distorted grid with
+// some of its local roads missing and a coarser grid of highways on
+// top. it's planar, always connected, and the edge values are travel
+// times (in units of seconds at one metre per second on local roads).
+pub fn road_network<R: Rng + ?Sized>(
+    config: &RoadNetworkConfig,
+    rng: &mut R,
+) -> GraphData<Junction, f64> {
+    let mut data = GraphData::new();
+    let jitter = config.spacing * config.distortion / 2.0;
+
+    for row in 0..config.rows {
+        for column in 0..config.columns {
+            let mut offset = || (rng.next_f64() * 2.0 - 1.0) * jitter;
+            let (dx, dy) = (offset(), offset());
+
+            data.add_vertex(Junction {
+                id: row * config.columns + column,
+                x: (column as f64 * config.spacing + dx).round() as i64,
+                y: (row as f64 * config.spacing + dy).round() as i64,
+            });
+        }
+    }
+
+    let index = |column: usize, row: usize| row * config.columns + column;
+    let highway =
+        |line: usize| config.highway_every > 0 && line.is_multiple_of(config.highway_every);
+
+    // each road is (from, to, whether it's a highway). a horizontal
+    // road is on a highway if its row is, and likewise for columns.
+    let mut roads: Vec<(usize, usize, bool)> = Vec::new();
+
+    for row in 0..config.rows {
+        for column in 0..config.columns {
+            if column + 1 < config.columns {
+                roads.push((index(column, row), index(column + 1, row), highway(row)));
+            }
+
+            if row + 1 < config.rows {
+                roads.push((index(column, row), index(column, row + 1), highway(column)));
+            }
+        }
+    }
+
+    // highways go in first, then the local roads in a random order,
+    // keeping any that join two parts that aren't connected yet. the
+    // rest are only kept by chance, which means deleting roads can
+    // never split the network.
+    let (mut highways, mut local): (Vec<_>, Vec<_>) = roads.into_iter().partition(|road| road.2);
+    // (going through &mut R, since shuffle needs a sized rng.)
+    Rng::shuffle(&mut &mut *rng, &mut local);
+    highways.append(&mut local);
+
+    let mut parent: Vec<usize> = (0..data.vertices().len()).collect();
+
+    for (from, to, is_highway) in highways {
+        let (a, b) = (find(&mut parent, from), find(&mut parent, to));
+        let needed = a != b;
+
+        if needed {
+            parent[a] = b;
+        }
+
+        if !(is_highway || needed || rng.next_f64() >= config.deletion) {
+            continue;
+        }
+
+        let (p, q) = (data.vertices()[from], data.vertices()[to]);
+        let length = ((p.x - q.x) as f64).hypot((q.y - p.y) as f64);
+        let winding = 1.0 + rng.next_f64() * config.distortion;
+        let speed = if is_highway {
+            config.highway_speedup
+        } else {
+            1.0
+        };
+
+        data.connect(from, to, length * winding / speed);
+    }
+
+    data
+}
+
+fn find(parent: &mut [usize], vertex: usize) -> usize {
+    let mut root = vertex;
+
+    while parent[root] != root {
+        root = parent[root];
+    }
+
+    // point everything on the way straight at the root.
+    let mut current = vertex;
+    while parent[current] != root {
+        let next = parent[current];
+        parent[current] = root;
+        current = next;
+    }
+
+    root
+}
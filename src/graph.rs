@@ -1,9 +1,93 @@
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
+use std::mem;
 use std::ops::Add;
+use std::time::Instant;
 
 use std::fmt::Debug;
 
+use crate::error::GraphError;
+use crate::limits::{LimitExceeded, SearchLimits};
+use crate::metrics::{Counters, Recorder};
+use frontier::Queued;
+use queue::{BinaryQueue, PriorityQueue};
+
+pub mod adj;
+pub mod arc_flags;
+pub mod assignment;
+pub mod assortativity;
+pub mod automorphism;
+mod bitset;
+pub mod chordal;
+pub mod coarsen;
+pub mod coloring;
+pub mod components;
+pub mod constrained;
+pub mod context;
+pub mod cycle;
+pub mod cycle_basis;
+pub mod data;
+pub mod densest;
+pub mod dial;
+pub mod diameter;
+mod disjoint;
+pub mod disjoint_paths;
+pub mod dot;
+pub mod edge_list;
+pub mod ego;
+pub mod flow_field;
+mod frontier;
+pub mod geojson;
+pub mod hops;
+pub mod hpa;
+pub mod hub;
+pub mod interval;
+pub mod itinerary;
+pub mod labeling;
+pub mod landmark;
+pub mod layout;
+pub mod line;
+pub mod low_stretch;
+pub mod matching;
+pub mod matrix;
+pub mod minor;
+pub mod morphology;
+pub mod mtx;
+pub mod multi_agent;
+pub mod multiway;
+pub mod navmesh;
+pub mod nearest;
+pub mod orient;
+pub mod overflow;
+pub mod pareto;
+pub mod path_cover;
+pub mod power;
+pub mod product;
+pub mod queue;
+pub mod reachability;
+pub mod reliability;
+pub mod rewire;
+pub mod rewrite;
+pub mod rich_club;
+pub mod simulation;
+pub mod snap;
+pub mod spanner;
+pub mod spanning;
+pub mod sparsify;
+pub mod stats;
+pub mod stochastic;
+pub mod strong_cuts;
+pub mod topological;
+pub mod trails;
+pub mod transform;
+pub mod traversal;
+pub mod tree;
+pub mod validate;
+pub mod verify;
+pub mod visitor;
+pub mod zero;
+
+#[macro_export]
 macro_rules! graph {
     (
         $(
@@ -12,7 +96,7 @@ macro_rules! graph {
             ]
         ),*
     ) => {{
-        let mut graph = Graph::empty();
+        let mut graph = $crate::Graph::empty();
 
         // add all of the vertices
         $( graph.add_vertex($start_vertex); )*
@@ -28,33 +112,65 @@ macro_rules! graph {
     }}
 }
 
-#[derive(Debug)]
-struct Edge<'a, V: Hash + Eq, E> {
-    v1: &'a V,
-    v2: &'a V,
+#[derive(Debug, Clone)]
+struct Edge<V: Hash + Eq, E> {
+    v1: V,
+    v2: V,
     pub value: E,
+    // a directed edge only goes from v1 to v2.
+    directed: bool,
 }
 
-#[derive(Debug)]
-pub struct Graph<'a, V: Hash + Eq, E> {
+// when a search can finish before it's settled everything it can
+// reach: once it's past some distance, or once it's got to a target.
+enum Stop<'s, V, W> {
+    Never,
+    Beyond(W),
+    At(&'s V),
+}
+
+#[derive(Debug, Clone)]
+pub struct Graph<V: Hash + Eq, E> {
     vertices: HashSet<V>,
-    edges: Vec<Edge<'a, V, E>>,
+    edges: Vec<Edge<V, E>>,
+    // the positions of the edges touching each vertex, in the order
+    // they were added, so finding neighbours doesn't mean going over
+    // every edge in the graph.
+    incident: HashMap<V, Vec<usize>>,
+    // whether another edge can be added alongside one that already goes
+    // the same way between the same two vertices.
+    multigraph: bool,
 }
 
-impl<'a, V, E> Edge<'a, V, E>
+impl<V, E> Edge<V, E>
 where
     V: Hash + Eq,
 {
-    fn new(v1: &'a V, v2: &'a V, value: E) -> Self {
+    fn new(v1: V, v2: V, value: E) -> Self {
         Self {
-            v1: v1,
-            v2: v2,
-            value: value,
+            v1,
+            v2,
+            value,
+            directed: false,
         }
     }
+
+    fn directed(v1: V, v2: V, value: E) -> Self {
+        Self {
+            v1,
+            v2,
+            value,
+            directed: true,
+        }
+    }
+
+    // whether the edge can be followed from a to b.
+    fn leads(&self, a: &V, b: &V) -> bool {
+        (self.v1 == *a && self.v2 == *b) || (!self.directed && self.v1 == *b && self.v2 == *a)
+    }
 }
 
-impl<'a, V, E> Graph<'a, V, E>
+impl<V, E> Graph<V, E>
 where
     V: Hash + Eq,
 {
@@ -62,40 +178,249 @@ where
         Self {
             vertices: HashSet::new(),
             edges: Vec::new(),
+            incident: HashMap::new(),
+            multigraph: true,
         }
     }
 
+    fn incident_edges(&self, vertex: &V) -> impl Iterator<Item = &Edge<V, E>> {
+        let positions = match self.incident.get(vertex) {
+            Some(positions) => &positions[..],
+            None => &[],
+        };
+
+        positions.iter().map(move |&i| &self.edges[i])
+    }
+
     pub fn add_vertex(&mut self, value: V) {
         self.vertices.insert(value);
     }
 
     pub fn contains(&self, value: &V) -> bool {
-        self.vertices.contains(&value)
+        self.vertices.contains(value)
+    }
+
+    // every vertex, in no particular order.
+    pub fn vertices(&self) -> impl Iterator<Item = &V> {
+        self.vertices.iter()
+    }
+
+    // every edge as (v1, v2, value), in the order they were added. each
+    // undirected edge only comes up once, with its ends the way round
+    // they were given, and a directed one goes from v1 to v2.
+    pub fn edges(&self) -> impl Iterator<Item = (&V, &V, &E)> {
+        self.edges
+            .iter()
+            .map(|edge| (&edge.v1, &edge.v2, &edge.value))
+    }
+
+    // graphs start out as multigraphs, where connecting the same two
+    // vertices twice keeps both edges. otherwise, that's an error.
+    pub fn is_multigraph(&self) -> bool {
+        self.multigraph
+    }
+
+    // this only affects edges added from now on, so any parallel edges
+    // that are already there stay.
+    pub fn set_multigraph(&mut self, multigraph: bool) {
+        self.multigraph = multigraph;
+    }
+
+    // whether there's already an edge that could be followed the same
+    // way as a new one from v1 to v2 (both ways, if it's undirected).
+    fn has_parallel(&self, v1: &V, v2: &V, directed: bool) -> bool {
+        self.incident_edges(v1)
+            .any(|edge| edge.leads(v1, v2) || (!directed && edge.leads(v2, v1)))
+    }
+}
+
+// edges keep their own copies of the vertices at either end, so the
+// graph doesn't borrow anything and can be built up from anywhere.
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+{
+    // for building a graph out of edges that are already known to be
+    // between its vertices.
+    fn from_parts(vertices: HashSet<V>, edges: Vec<Edge<V, E>>) -> Self {
+        let mut graph = Self {
+            vertices,
+            edges: Vec::with_capacity(edges.len()),
+            incident: HashMap::new(),
+            multigraph: true,
+        };
+
+        for edge in edges {
+            graph.push_edge(edge);
+        }
+
+        graph
     }
 
-    pub fn connect_vertices(
+    fn push_edge(&mut self, edge: Edge<V, E>) {
+        let position = self.edges.len();
+        self.incident
+            .entry(edge.v1.clone())
+            .or_default()
+            .push(position);
+
+        // a loop only touches its vertex once.
+        if edge.v1 != edge.v2 {
+            self.incident
+                .entry(edge.v2.clone())
+                .or_default()
+                .push(position);
+        }
+
+        self.edges.push(edge);
+    }
+
+    pub fn connect_vertices(&mut self, v1: &V, v2: &V, edge_value: E) -> Result<(), GraphError> {
+        if !(self.contains(v1) && self.contains(v2)) {
+            return Err(GraphError::VertexNotFound);
+        }
+
+        if !self.multigraph && self.has_parallel(v1, v2, false) {
+            return Err(GraphError::DuplicateEdge);
+        }
+
+        self.push_edge(Edge::new(v1.clone(), v2.clone(), edge_value));
+
+        Ok(())
+    }
+
+    // a one-way edge, which can only be followed from v1 to v2. both
+    // kinds of edge can be mixed in the same graph, like one-way
+    // streets among two-way ones.
+    pub fn connect_directed(&mut self, v1: &V, v2: &V, edge_value: E) -> Result<(), GraphError> {
+        if !(self.contains(v1) && self.contains(v2)) {
+            return Err(GraphError::VertexNotFound);
+        }
+
+        if !self.multigraph && self.has_parallel(v1, v2, true) {
+            return Err(GraphError::DuplicateEdge);
+        }
+
+        self.push_edge(Edge::directed(v1.clone(), v2.clone(), edge_value));
+
+        Ok(())
+    }
+
+    // takes the vertex out of the graph along with every edge touching
+    // it, giving it back if it was there.
+    pub fn remove_vertex(&mut self, vertex: &V) -> Option<V> {
+        let removed = self.vertices.take(vertex)?;
+        self.disconnect_all(&removed);
+
+        Some(removed)
+    }
+
+    // takes out an edge that can be followed from v1 to v2, giving back
+    // its value. with parallel edges, it's the one value_between gives.
+    pub fn remove_edge(&mut self, v1: &V, v2: &V) -> Option<E> {
+        let position = self.position_between(v1, v2)?;
+        self.remove_edges(&[position]).pop()
+    }
+
+    // changes the value of an edge that can be followed from v1 to v2
+    // in place, giving back the old one. with parallel edges, it's the
+    // one value_between gives. if there's no such edge, nothing changes.
+    pub fn set_edge_value(&mut self, v1: &V, v2: &V, value: E) -> Option<E> {
+        let position = self.position_between(v1, v2)?;
+        Some(mem::replace(&mut self.edges[position].value, value))
+    }
+
+    // sets the value of the edge from v1 to v2 if there is one, like
+    // set_edge_value, or adds a new undirected edge if there isn't.
+    pub fn connect_or_update(
         &mut self,
-        v1: &'a V,
-        v2: &'a V,
+        v1: &V,
+        v2: &V,
         edge_value: E,
-    ) -> Result<(), &'static str> {
+    ) -> Result<Option<E>, GraphError> {
         if !(self.contains(v1) && self.contains(v2)) {
-            return Err("Graph does not contain both vertices.");
+            return Err(GraphError::VertexNotFound);
         }
 
-        self.edges.push(Edge::new(v1, v2, edge_value));
+        match self.position_between(v1, v2) {
+            Some(position) => Ok(Some(mem::replace(
+                &mut self.edges[position].value,
+                edge_value,
+            ))),
+            None => self.connect_vertices(v1, v2, edge_value).map(|_| None),
+        }
+    }
 
-        Ok(())
+    // where the edge value_between would give is in the list of edges.
+    fn position_between(&self, v1: &V, v2: &V) -> Option<usize> {
+        self.incident
+            .get(v1)?
+            .iter()
+            .cloned()
+            .find(|&i| self.edges[i].leads(v1, v2))
+    }
+
+    // takes out every edge touching the vertex but leaves the vertex
+    // itself, giving back the values in the order they were added.
+    pub fn disconnect_all(&mut self, vertex: &V) -> Vec<E> {
+        match self.incident.get(vertex) {
+            Some(positions) => {
+                let positions = positions.clone();
+                self.remove_edges(&positions)
+            }
+            None => Vec::new(),
+        }
+    }
+
+    // the positions have to be in order. everything after a removed edge
+    // moves down, so the incident index is worked out again from scratch.
+    fn remove_edges(&mut self, positions: &[usize]) -> Vec<E> {
+        let mut doomed = positions.iter().peekable();
+        let mut removed = Vec::new();
+        self.incident.clear();
+
+        for (i, edge) in mem::take(&mut self.edges).into_iter().enumerate() {
+            if doomed.peek() == Some(&&i) {
+                doomed.next();
+                removed.push(edge.value);
+            } else {
+                self.push_edge(edge);
+            }
+        }
+
+        removed
     }
+}
 
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // the vertices that can be reached from this one in a single step,
+    // so a directed edge only counts from its start.
     pub fn neighbors(&self, vertex: &V) -> Vec<(&V, &E)> {
+        self.arcs(vertex, false)
+    }
+
+    // the vertices that can reach this one in a single step.
+    pub fn predecessors(&self, vertex: &V) -> Vec<(&V, &E)> {
+        self.arcs(vertex, true)
+    }
+
+    fn arcs(&self, vertex: &V, reverse: bool) -> Vec<(&V, &E)> {
         let mut neighbors = Vec::new();
 
-        for edge in self.edges.iter() {
-            let neighbor = if vertex == edge.v1 {
-                Some(edge.v2)
-            } else if vertex == edge.v2 {
-                Some(edge.v1)
+        for edge in self.incident_edges(vertex) {
+            let (from, to) = if reverse {
+                (&edge.v2, &edge.v1)
+            } else {
+                (&edge.v1, &edge.v2)
+            };
+
+            let neighbor = if vertex == from {
+                Some(to)
+            } else if vertex == to && !edge.directed {
+                Some(from)
             } else {
                 None
             };
@@ -108,89 +433,333 @@ where
         neighbors
     }
 
+    // the value of an edge that can be followed from v1 to v2. with
+    // parallel edges, it's the first one that was added.
     pub fn value_between(&self, v1: &V, v2: &V) -> Option<&E> {
-        for edge in self.edges.iter() {
-            let forward_link = edge.v1 == v1 && edge.v2 == v2;
-            let backward_link = edge.v1 == v2 && edge.v2 == v1;
-
-            if forward_link || backward_link {
+        for edge in self.incident_edges(v1) {
+            if edge.leads(v1, v2) {
                 return Some(&edge.value);
             }
         }
 
         None
     }
+
+    // the values of every edge that can be followed from v1 to v2, in
+    // the order they were added.
+    pub fn edges_between(&self, v1: &V, v2: &V) -> Vec<&E> {
+        self.incident_edges(v1)
+            .filter(|edge| edge.leads(v1, v2))
+            .map(|edge| &edge.value)
+            .collect()
+    }
 }
 
-impl<'a, V, E> Graph<'a, V, E>
+impl<V, E> Graph<V, E>
 where
     V: Hash + Eq,
-    E: Add<Output = E> + Ord + Clone,
+    E: Add<Output = E> + PartialOrd + Clone,
 {
+    // like the other searches, this trusts that no weight is negative.
+    // dijkstra_paths_checked makes sure first.
     pub fn dijkstra_paths(&self, source: &V) -> HashMap<&V, E> {
+        self.dijkstra_by(source, |edge| edge.clone(), None)
+    }
+
+    // the shortest distances from every vertex to the target, rather
+    // than from the source to everything. it's the same search run
+    // from the target, only following every directed edge backwards.
+    pub fn dijkstra_paths_to(&self, target: &V) -> HashMap<&V, E> {
+        match self.dijkstra_within(
+            target,
+            |edge| edge.clone(),
+            Stop::Never,
+            true,
+            &SearchLimits::new(),
+            &mut Counters::default(),
+            &mut HashMap::new(),
+        ) {
+            Ok(distances) => distances,
+            Err(exceeded) => exceeded.partial,
+        }
+    }
+
+    // the same search, but giving up once it goes over any of the
+    // limits. the partial result only has the vertices whose distances
+    // were settled by then, so every distance in it is still exact.
+    pub fn dijkstra_paths_within(
+        &self,
+        source: &V,
+        limits: &SearchLimits,
+    ) -> Result<HashMap<&V, E>, LimitExceeded<HashMap<&V, E>>> {
+        self.dijkstra_within(
+            source,
+            |edge| edge.clone(),
+            Stop::Never,
+            false,
+            limits,
+            &mut Counters::default(),
+            &mut HashMap::new(),
+        )
+    }
+
+    // the same search again, handing its counters to the recorder once
+    // it's finished.
+    pub fn dijkstra_paths_recorded<R: Recorder>(
+        &self,
+        source: &V,
+        recorder: &mut R,
+    ) -> HashMap<&V, E> {
+        let mut counters = Counters::default();
+        let started = Instant::now();
+
+        let distances = match self.dijkstra_within(
+            source,
+            |edge| edge.clone(),
+            Stop::Never,
+            false,
+            &SearchLimits::new(),
+            &mut counters,
+            &mut HashMap::new(),
+        ) {
+            Ok(distances) => distances,
+            Err(exceeded) => exceeded.partial,
+        };
+
+        counters.elapsed = started.elapsed();
+        recorder.record("dijkstra_paths", &counters);
+
+        distances
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone + Default,
+{
+    // dijkstra's algorithm quietly gives wrong answers if any weight is
+    // negative, so this checks first. a negative two-way edge could be
+    // crossed back and forth forever to make any path as short as you
+    // like, and there's no right answer to fall back on (bellman-ford
+    // would just report a negative cycle).
+    pub fn dijkstra_paths_checked(&self, source: &V) -> Result<HashMap<&V, E>, GraphError> {
+        if self.has_negative_weight() {
+            return Err(GraphError::NegativeWeight);
+        }
+
+        Ok(self.dijkstra_by(source, |edge| edge.clone(), None))
+    }
+
+    // a weight is negative when it's below E's default, which is zero
+    // for all of the number types. (adding a weight to itself to see if
+    // it gets smaller would avoid needing a zero, but overflows.)
+    pub fn has_negative_weight(&self) -> bool {
+        let zero = E::default();
+        self.edges.iter().any(|edge| edge.value < zero)
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // the actual search behind dijkstra_paths, generalised so that
+    // the weight of each edge can be derived from its value. the
+    // weights only need a partial order, which lets floats through.
+    // given a limit, the search stops once it gets any further away.
+    fn dijkstra_by<W, F>(&self, source: &V, weight: F, limit: Option<W>) -> HashMap<&V, W>
+    where
+        W: Add<Output = W> + PartialOrd + Clone,
+        F: Fn(&E) -> W,
+    {
+        match self.dijkstra_within(
+            source,
+            weight,
+            match limit {
+                Some(limit) => Stop::Beyond(limit),
+                None => Stop::Never,
+            },
+            false,
+            &SearchLimits::new(),
+            &mut Counters::default(),
+            &mut HashMap::new(),
+        ) {
+            Ok(distances) => distances,
+            Err(exceeded) => exceeded.partial,
+        }
+    }
+
+    // alongside the distances, this fills in the vertex each one was
+    // reached from on its shortest path, so the paths can be traced.
+    // in reverse, every directed edge is followed backwards instead.
+    #[allow(clippy::too_many_arguments)]
+    fn dijkstra_within<'g, W, F>(
+        &'g self,
+        source: &V,
+        weight: F,
+        stop: Stop<V, W>,
+        reverse: bool,
+        limits: &SearchLimits,
+        counters: &mut Counters,
+        previous: &mut HashMap<&'g V, &'g V>,
+    ) -> Result<HashMap<&'g V, W>, LimitExceeded<HashMap<&'g V, W>>>
+    where
+        W: Add<Output = W> + PartialOrd + Clone,
+        F: Fn(&E) -> W,
+    {
+        self.dijkstra_queued(
+            source,
+            weight,
+            stop,
+            reverse,
+            limits,
+            counters,
+            previous,
+            BinaryQueue::new(),
+        )
+    }
+
+    // and the same again, with any priority queue.
+    #[allow(clippy::too_many_arguments)]
+    fn dijkstra_queued<'g, W, F, Q>(
+        &'g self,
+        source: &V,
+        weight: F,
+        stop: Stop<V, W>,
+        reverse: bool,
+        limits: &SearchLimits,
+        counters: &mut Counters,
+        previous: &mut HashMap<&'g V, &'g V>,
+        mut queue: Q,
+    ) -> Result<HashMap<&'g V, W>, LimitExceeded<HashMap<&'g V, W>>>
+    where
+        W: Add<Output = W> + PartialOrd + Clone,
+        F: Fn(&E) -> W,
+        Q: PriorityQueue<W, &'g V>,
+    {
         // this implementation of dijkstra's algorithm is a little
         // different from a typical version. since a generic type E
         // is used for the edge weights, we do not know which values
         // are analogous to zero and infinity. many implementations
         // of this algorithm would use those values as provisional
         // distances from the source, but we cannot do that here.
-        let mut distances: HashMap<&V, E> = HashMap::new();
+        let mut distances: HashMap<&V, W> = HashMap::new();
         let mut unvisited_vertices: HashSet<&V> = HashSet::new();
+        let source_vertex = self.vertices.get(source);
+        let mut budget = limits.start();
+        let mut exceeded = None;
 
-        // the first iteration of the algorithm happens here.
         for vertex in self.vertices.iter() {
-            // skip over the source here because we're dealing with
-            // its neighbors in this loop instead of the main loop.
-            if vertex == source {
-                continue;
+            // the source is dealt with here rather than in the main
+            // loop, so it never goes in with the unvisited vertices.
+            if vertex != source {
+                unvisited_vertices.insert(vertex);
             }
+        }
 
-            unvisited_vertices.insert(vertex);
+        // the first iteration of the algorithm happens here, taking
+        // note of the distance to each of the source's neighbours. with
+        // parallel edges, it's the lightest one that counts.
+        if let Some(from) = source_vertex {
+            for (vertex, value) in self.arcs(from, reverse) {
+                if vertex == from {
+                    continue;
+                }
 
-            // if the current vertex is a neighbor to the source,
-            // take note of the distance of the edge between them.
-            if let Some(source_dist) = self.value_between(source, vertex) {
-                distances.insert(vertex, source_dist.clone());
+                let source_dist = weight(value);
+                counters.relaxed += 1;
+
+                let prev_dist = distances.get(vertex);
+
+                if prev_dist.is_none() || source_dist < *prev_dist.unwrap() {
+                    counters.queue_operations += 1;
+                    distances.insert(vertex, source_dist.clone());
+                    previous.insert(vertex, from);
+                    queue.push(source_dist, vertex);
+                }
             }
         }
 
-        while !unvisited_vertices.is_empty() {
-            // search through the unvisited vertices to find which
-            // one has the lowest provisional distance.
-            let &nearest_vertex = unvisited_vertices
-                .iter()
-                .filter(|&v| distances.contains_key(v))
-                .min_by_key(|&v| distances.get(v))
-                .unwrap();
+        // the queue always gives the unvisited vertex with the lowest
+        // provisional distance next. improving a distance just queues
+        // the vertex again rather than updating it in place, so any
+        // entry that's been beaten since it went in gets skipped. once
+        // the queue runs dry, the rest of the graph can't be reached.
+        while let Some((nearest_dist, nearest_vertex)) = queue.pop() {
+            counters.queue_operations += 1;
+
+            if !unvisited_vertices.contains(nearest_vertex)
+                || distances[nearest_vertex] < nearest_dist
+            {
+                continue;
+            }
 
             unvisited_vertices.remove(nearest_vertex);
+            counters.settled += 1;
 
-            // this seem convoluted, but it prevents an error from
-            // the coexistence of mutable and immutable references.
-            let dist_entry = distances.get(nearest_vertex);
-            let nearest_dist = dist_entry.unwrap().clone();
+            let memory = distances.len() * mem::size_of::<(&V, W)>()
+                + unvisited_vertices.len() * mem::size_of::<&V>()
+                + queue.len() * mem::size_of::<Queued<W, &V>>();
+
+            if let Err(hit) = budget.expand(memory) {
+                exceeded = Some(hit);
+                break;
+            }
+
+            // everything left is at least this far away, so once it's
+            // past the limit there's nothing more worth settling. and
+            // once the target's settled, its distance won't change.
+            match &stop {
+                Stop::Beyond(limit) if nearest_dist > *limit => break,
+                Stop::At(target) if nearest_vertex == *target => break,
+                _ => (),
+            }
+
+            for &(vertex, edge_len) in self.arcs(nearest_vertex, reverse).iter() {
+                // visited vertices (the source included) are already as
+                // close as they're going to get. this also keeps the
+                // source from picking up a round trip as its distance,
+                // which we couldn't fix by setting it to zero since we
+                // don't know the analogous value for the generic type W.
+                if !unvisited_vertices.contains(vertex) {
+                    continue;
+                }
 
-            for &(vertex, edge_len) in self.neighbors(nearest_vertex).iter() {
                 // for each neighboring vertex, we check if passing
                 // through the current vertex allows for a smaller
                 // distance than the shortest path checked so far.
-                let alt_dist = nearest_dist.clone() + edge_len.clone();
+                let alt_dist = nearest_dist.clone() + weight(edge_len);
                 let prev_dist = distances.get(vertex);
+                counters.relaxed += 1;
 
                 if prev_dist.is_none() || alt_dist < *prev_dist.unwrap() {
-                    distances.insert(vertex, alt_dist);
+                    counters.queue_operations += 1;
+                    distances.insert(vertex, alt_dist.clone());
+                    previous.insert(vertex, nearest_vertex);
+                    queue.push(alt_dist, vertex);
                 }
             }
         }
 
-        // this implementation would produce an erroneous distance
-        // for the source (and we can't just set it to zero since we
-        // don't know the analogous value for the generic type E) so
-        // let's just get rid of it. it's a trivial result anyway.
-        if distances.contains_key(source) {
-            distances.remove(source);
+        // provisional distances beyond the limit may have been left
+        // behind by the last few relaxations, so tidy those up too.
+        if let Stop::Beyond(limit) = stop {
+            distances.retain(|_, dist| *dist <= limit);
         }
 
-        distances
+        match exceeded {
+            None => Ok(distances),
+            Some(limit) => {
+                // anything not yet visited only has a provisional
+                // distance, which might still have come down.
+                distances.retain(|vertex, _| !unvisited_vertices.contains(vertex));
+
+                Err(LimitExceeded {
+                    limit,
+                    partial: distances,
+                })
+            }
+        }
     }
 }
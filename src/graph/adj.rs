@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use super::Graph;
+use crate::error::GraphError;
+
+// a plain text adjacency list, with one vertex per line followed by
+// pairs of neighbours and edge weights, like "A B 6 C 5". as with the
+// graph! macro, each undirected edge only needs listing under one of
+// its ends, and a neighbour that never gets a line of its own is still
+// added. if the edges are directed, they go from the vertex whose line
+// they're on. blank lines and lines starting with # are skipped.
+impl<E: FromStr> Graph<String, E> {
+    pub fn from_adj<R: BufRead>(reader: R, directed: bool) -> Result<Self, GraphError> {
+        let mut graph = Graph::empty();
+
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+
+            if tokens[1..].len() % 2 == 1 {
+                return Err(GraphError::syntax(
+                    number + 1,
+                    "Neighbour is missing its weight.",
+                ));
+            }
+
+            let start = tokens[0].to_string();
+
+            if !graph.vertices.contains(&start) {
+                graph.add_vertex(start.clone());
+            }
+
+            for pair in tokens[1..].chunks(2) {
+                let weight = pair[1].parse().map_err(|_| {
+                    GraphError::syntax(number + 1, "Edge weight could not be parsed.")
+                })?;
+
+                let end = pair[0].to_string();
+
+                if !graph.vertices.contains(&end) {
+                    graph.add_vertex(end.clone());
+                }
+
+                // both ends are in the graph, so connecting them can't fail.
+                if directed {
+                    graph.connect_directed(&start, &end, weight).unwrap();
+                } else {
+                    graph.connect_vertices(&start, &end, weight).unwrap();
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+
+    pub fn parse_adj(text: &str, directed: bool) -> Result<Self, GraphError> {
+        Self::from_adj(text.as_bytes(), directed)
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Display,
+    E: Display,
+{
+    // each edge is written under whichever end it was connected from,
+    // and every vertex gets a line, so isolated ones survive the trip.
+    // the file doesn't say which edges are directed, so a graph with
+    // both kinds can't be written out.
+    pub fn write_adj<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let directed = self.edges.iter().filter(|edge| edge.directed).count();
+
+        if directed != 0 && directed != self.edges.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Graph has both directed and undirected edges.",
+            ));
+        }
+
+        let mut lines: HashMap<&V, Vec<String>> = HashMap::new();
+
+        for vertex in self.vertices.iter() {
+            check_name(&vertex.to_string())?;
+            lines.insert(vertex, Vec::new());
+        }
+
+        for edge in self.edges.iter() {
+            let weight = edge.value.to_string();
+            check_name(&weight)?;
+
+            let line = lines.get_mut(&edge.v1).unwrap();
+            line.push(edge.v2.to_string());
+            line.push(weight);
+        }
+
+        for vertex in self.vertices.iter() {
+            let rest = &lines[vertex];
+
+            if rest.is_empty() {
+                writeln!(writer, "{}", vertex)?;
+            } else {
+                writeln!(writer, "{} {}", vertex, rest.join(" "))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn to_adj(&self) -> io::Result<String> {
+        let mut buffer = Vec::new();
+        self.write_adj(&mut buffer)?;
+        Ok(String::from_utf8(buffer).unwrap())
+    }
+}
+
+// the format has no quoting, so anything with whitespace in it (or
+// that would be read back as a comment) can't be written out.
+fn check_name(text: &str) -> io::Result<()> {
+    if text.is_empty() || text.starts_with('#') || text.contains(char::is_whitespace) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Value can't be written in the adjacency list format.",
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::bitset::BitSet;
+use super::frontier::Frontier;
+use super::Graph;
+use crate::cancel::{CancelToken, Cancelled};
+use crate::progress::Progress;
+
+struct Arc<E> {
+    to: usize,
+    weight: E,
+    // one flag per region, set if the arc starts a shortest path to
+    // somewhere in that region.
+    flags: Vec<bool>,
+}
+
+// the vertices are split into regions up front, and every arc is
+// marked with the regions it's useful for reaching. a query then only
+// follows arcs marked with the target's region, which skips most of
+// the graph once the search is a little way from the target. the
+// preprocessing takes one search per boundary vertex, so it's far
+// cheaper than a full distance index, but it does need a partition
+// with fairly few vertices on the borders between regions.
+pub struct ArcFlags<'g, V, E> {
+    vertices: Vec<&'g V>,
+    index: HashMap<&'g V, usize>,
+    region: Vec<usize>,
+    arcs: Vec<Vec<Arc<E>>>,
+}
+
+impl<'g, V, E> ArcFlags<'g, V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    pub fn new<R, F>(graph: &'g Graph<V, E>, region_of: F) -> Self
+    where
+        R: Hash + Eq,
+        F: Fn(&V) -> R,
+    {
+        Self::with_progress(graph, region_of, &mut ())
+    }
+
+    // reports progress after each vertex, whether or not it turned out
+    // to be on a boundary and needed a search.
+    pub fn with_progress<R, F, P>(graph: &'g Graph<V, E>, region_of: F, progress: &mut P) -> Self
+    where
+        R: Hash + Eq,
+        F: Fn(&V) -> R,
+        P: Progress,
+    {
+        Self::cancellable(graph, region_of, progress, &CancelToken::new()).unwrap()
+    }
+
+    // checks for cancellation before each boundary vertex's search.
+    pub fn cancellable<R, F, P>(
+        graph: &'g Graph<V, E>,
+        region_of: F,
+        progress: &mut P,
+        cancel: &CancelToken,
+    ) -> Result<Self, Cancelled>
+    where
+        R: Hash + Eq,
+        F: Fn(&V) -> R,
+        P: Progress,
+    {
+        let vertices: Vec<&V> = graph.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let mut regions: HashMap<R, usize> = HashMap::new();
+        let region: Vec<usize> = vertices
+            .iter()
+            .map(|&vertex| {
+                let next = regions.len();
+                *regions.entry(region_of(vertex)).or_insert(next)
+            })
+            .collect();
+
+        let mut arcs: Vec<Vec<Arc<E>>> = (0..vertices.len()).map(|_| Vec::new()).collect();
+        // the same arcs the other way round, for the searches towards
+        // each boundary vertex.
+        let mut into: Vec<Vec<(usize, E)>> = vec![Vec::new(); vertices.len()];
+
+        for edge in graph.edges.iter() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+
+            if a == b {
+                continue;
+            }
+
+            let ways = if edge.directed { 1 } else { 2 };
+
+            // arcs inside a region are always worth following to reach
+            // the rest of that region.
+            for &(from, to) in [(a, b), (b, a)][..ways].iter() {
+                into[to].push((from, edge.value.clone()));
+                let mut flags = vec![false; regions.len()];
+
+                if region[from] == region[to] {
+                    flags[region[from]] = true;
+                }
+
+                arcs[from].push(Arc {
+                    to,
+                    weight: edge.value.clone(),
+                    flags,
+                });
+            }
+        }
+
+        // any shortest path into a region last enters it through one of
+        // its boundary vertices (the ones with an arc in from another
+        // region), so an arc leading towards the region has to be on a
+        // shortest path to one of those. the distances to a boundary
+        // vertex come from searching backwards from it.
+        for boundary in 0..vertices.len() {
+            let target = region[boundary];
+            progress.on_progress(boundary + 1, vertices.len());
+
+            if into[boundary]
+                .iter()
+                .all(|(from, _)| region[*from] == target)
+            {
+                continue;
+            }
+
+            cancel.check()?;
+            let dist = distances_to(&into, boundary);
+
+            for from in 0..vertices.len() {
+                let here = match &dist[from] {
+                    Some(here) => here.clone(),
+                    None => continue,
+                };
+
+                for arc in arcs[from].iter_mut() {
+                    // the boundary vertex has no distance to itself,
+                    // so an arc straight into it is judged on its own.
+                    let via = match &dist[arc.to] {
+                        Some(there) => arc.weight.clone() + there.clone(),
+                        None if arc.to == boundary => arc.weight.clone(),
+                        None => continue,
+                    };
+
+                    if via <= here {
+                        arc.flags[target] = true;
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            vertices,
+            index,
+            region,
+            arcs,
+        })
+    }
+
+    pub fn shortest_path(&self, source: &V, target: &V) -> Option<(Vec<&'g V>, E)> {
+        let (source, target) = (*self.index.get(source)?, *self.index.get(target)?);
+
+        if source == target {
+            return None;
+        }
+
+        let goal = self.region[target];
+        let mut frontier = Frontier::new();
+        let mut settled = BitSet::new(self.vertices.len());
+        let mut previous: Vec<Option<usize>> = vec![None; self.vertices.len()];
+        settled.insert(source);
+
+        for arc in self.arcs[source].iter().filter(|arc| arc.flags[goal]) {
+            if frontier.improve(arc.to, arc.weight.clone()) {
+                previous[arc.to] = Some(source);
+            }
+        }
+
+        while let Some((vertex, dist)) = frontier.pop() {
+            settled.insert(vertex);
+
+            if vertex == target {
+                let mut path = vec![self.vertices[target]];
+                let mut current = target;
+
+                while let Some(before) = previous[current] {
+                    path.push(self.vertices[before]);
+                    current = before;
+                }
+
+                path.reverse();
+                return Some((path, dist));
+            }
+
+            for arc in self.arcs[vertex].iter() {
+                if !arc.flags[goal] || settled.contains(arc.to) {
+                    continue;
+                }
+
+                if frontier.improve(arc.to, dist.clone() + arc.weight.clone()) {
+                    previous[arc.to] = Some(vertex);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+// the distance from every vertex to the target, following the
+// reversed arcs out of it.
+fn distances_to<E>(into: &[Vec<(usize, E)>], target: usize) -> Vec<Option<E>>
+where
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    let mut dist: Vec<Option<E>> = vec![None; into.len()];
+    let mut settled = BitSet::new(into.len());
+    let mut frontier = Frontier::new();
+    settled.insert(target);
+
+    for (from, weight) in into[target].iter() {
+        frontier.improve(*from, weight.clone());
+    }
+
+    while let Some((vertex, here)) = frontier.pop() {
+        settled.insert(vertex);
+
+        for (from, weight) in into[vertex].iter() {
+            if !settled.contains(*from) {
+                frontier.improve(*from, here.clone() + weight.clone());
+            }
+        }
+
+        dist[vertex] = Some(here);
+    }
+
+    dist
+}
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::bitset::BitSet;
+use super::frontier::Frontier;
+use super::Graph;
+use crate::error::GraphError;
+
+// how traffic_assignment left things: the flow along every edge (in
+// the graph's edge order, with both ways of a two-way edge added up),
+// and the relative gap, which is how much the total travel time would
+// come down by if everyone switched to what's currently the shortest
+// path for them, as a fraction of the total. it's zero at equilibrium.
+#[derive(Debug, Clone)]
+pub struct Assignment<'g, V, E> {
+    pub flows: Vec<(&'g V, &'g V, &'g E, f64)>,
+    pub gap: f64,
+    pub iterations: usize,
+}
+
+// the bureau of public roads' travel time function, which is the usual
+// choice for the cost function: the free flow time, going up with the
+// fourth power of how close the flow is to the capacity.
+pub fn bpr(free_flow_time: f64, capacity: f64, flow: f64) -> f64 {
+    free_flow_time * (1.0 + 0.15 * (flow / capacity).powi(4))
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // sends every trip in the demand (an origin, a destination and how
+    // much traffic goes between them) through the graph, with the cost
+    // of each edge going up with the flow along it, until nobody could
+    // get there any quicker by taking a different route (wardrop's user
+    // equilibrium). the cost function gets an edge's value and its flow,
+    // and should be non-negative and never go down as the flow goes up.
+    //
+    // this is the frank-wolfe algorithm: every iteration finds the
+    // shortest paths at the current costs, sends all of the demand
+    // along them, and then moves the flows as far towards that as it
+    // can while the costs still come down. it stops once the gap is
+    // within the tolerance, or after the given number of iterations.
+    pub fn traffic_assignment<F>(
+        &self,
+        demand: &[(&V, &V, f64)],
+        cost: F,
+        max_iterations: usize,
+        tolerance: f64,
+    ) -> Result<Assignment<'_, V, E>, GraphError>
+    where
+        F: Fn(&E, f64) -> f64,
+    {
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let mut adjacent = vec![Vec::new(); vertices.len()];
+        for (i, edge) in self.edges.iter().enumerate() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+            adjacent[a].push((b, i));
+
+            if !edge.directed {
+                adjacent[b].push((a, i));
+            }
+        }
+
+        // trips are grouped by origin, so each iteration only needs one
+        // search from each of them.
+        let mut origins: Vec<(usize, Vec<(usize, f64)>)> = Vec::new();
+
+        for &(origin, destination, amount) in demand.iter() {
+            let (origin, destination) = match (index.get(origin), index.get(destination)) {
+                (Some(&origin), Some(&destination)) => (origin, destination),
+                _ => return Err(GraphError::VertexNotFound),
+            };
+
+            if amount < 0.0 {
+                return Err(GraphError::InvalidArgument("Demand must not be negative."));
+            }
+
+            match origins.iter_mut().find(|(from, _)| *from == origin) {
+                Some((_, trips)) => trips.push((destination, amount)),
+                None => origins.push((origin, vec![(destination, amount)])),
+            }
+        }
+
+        let network = Network {
+            adjacent,
+            origins,
+            edges: self.edges.len(),
+        };
+
+        let costs_at = |flows: &[f64]| -> Vec<f64> {
+            self.edges
+                .iter()
+                .zip(flows.iter())
+                .map(|(edge, &flow)| cost(&edge.value, flow))
+                .collect()
+        };
+
+        let mut flows = network.all_or_nothing(&costs_at(&vec![0.0; self.edges.len()]))?;
+        let mut gap = f64::INFINITY;
+        let mut iterations = 0;
+
+        while iterations < max_iterations {
+            let costs = costs_at(&flows);
+            let target = network.all_or_nothing(&costs)?;
+
+            let current: f64 = costs.iter().zip(flows.iter()).map(|(t, x)| t * x).sum();
+            let best: f64 = costs.iter().zip(target.iter()).map(|(t, y)| t * y).sum();
+            gap = if current > 0.0 {
+                (current - best) / current
+            } else {
+                0.0
+            };
+
+            if gap <= tolerance {
+                break;
+            }
+
+            iterations += 1;
+
+            // the costs keep coming down as long as the costs at the
+            // new flows, weighted by the direction they're moving in,
+            // add up to less than zero. that only ever goes up along the
+            // way, so bisection finds where it stops.
+            let slope = |step: f64| -> f64 {
+                let moved: Vec<f64> = flows
+                    .iter()
+                    .zip(target.iter())
+                    .map(|(x, y)| x + step * (y - x))
+                    .collect();
+
+                costs_at(&moved)
+                    .iter()
+                    .zip(flows.iter().zip(target.iter()))
+                    .map(|(t, (x, y))| t * (y - x))
+                    .sum()
+            };
+
+            let step = if slope(1.0) <= 0.0 {
+                1.0
+            } else {
+                let (mut low, mut high) = (0.0, 1.0);
+
+                for _ in 0..32 {
+                    let middle = (low + high) / 2.0;
+
+                    if slope(middle) <= 0.0 {
+                        low = middle;
+                    } else {
+                        high = middle;
+                    }
+                }
+
+                (low + high) / 2.0
+            };
+
+            for (x, y) in flows.iter_mut().zip(target.iter()) {
+                *x += step * (y - *x);
+            }
+        }
+
+        let flows = self
+            .edges
+            .iter()
+            .zip(flows)
+            .map(|(edge, flow)| (&edge.v1, &edge.v2, &edge.value, flow))
+            .collect();
+
+        Ok(Assignment {
+            flows,
+            gap,
+            iterations,
+        })
+    }
+}
+
+// the graph by number, with the edge each step uses, and the trips from
+// each origin.
+struct Network {
+    adjacent: Vec<Vec<(usize, usize)>>,
+    origins: Vec<(usize, Vec<(usize, f64)>)>,
+    edges: usize,
+}
+
+impl Network {
+    // the flow along every edge if every trip took the shortest path
+    // at the given costs.
+    fn all_or_nothing(&self, costs: &[f64]) -> Result<Vec<f64>, GraphError> {
+        let mut flows = vec![0.0; self.edges];
+
+        for (origin, trips) in self.origins.iter() {
+            let mut frontier = Frontier::new();
+            let mut via: HashMap<usize, (usize, usize)> = HashMap::new();
+            let mut settled = BitSet::new(self.adjacent.len());
+
+            frontier.improve(*origin, 0.0);
+
+            while let Some((vertex, dist)) = frontier.pop() {
+                settled.insert(vertex);
+
+                for &(next, edge) in self.adjacent[vertex].iter() {
+                    if !settled.contains(next) && frontier.improve(next, dist + costs[edge]) {
+                        via.insert(next, (vertex, edge));
+                    }
+                }
+            }
+
+            for &(destination, amount) in trips.iter() {
+                if !settled.contains(destination) {
+                    return Err(GraphError::Unreachable);
+                }
+
+                let mut vertex = destination;
+                while let Some(&(before, edge)) = via.get(&vertex) {
+                    flows[edge] += amount;
+                    vertex = before;
+                }
+            }
+        }
+
+        Ok(flows)
+    }
+}
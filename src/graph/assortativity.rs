@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::Graph;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    pub fn degree_assortativity(&self) -> Option<f64> {
+        let mut degrees: HashMap<&V, usize> = HashMap::new();
+
+        for edge in self.edges.iter() {
+            *degrees.entry(&edge.v1).or_default() += 1;
+            *degrees.entry(&edge.v2).or_default() += 1;
+        }
+
+        self.numeric_assortativity(|vertex| degrees[vertex] as f64)
+    }
+
+    pub fn numeric_assortativity<F>(&self, attribute: F) -> Option<f64>
+    where
+        F: Fn(&V) -> f64,
+    {
+        // the pearson correlation between the values at either end of
+        // each edge. every edge is counted in both directions, so the
+        // two ends have the same mean and variance. this is undefined
+        // (and gives none) when every endpoint has the same value.
+        if self.edges.is_empty() {
+            return None;
+        }
+
+        let pairs: Vec<(f64, f64)> = self
+            .edges
+            .iter()
+            .map(|edge| (attribute(&edge.v1), attribute(&edge.v2)))
+            .collect();
+
+        let count = 2.0 * pairs.len() as f64;
+        let mean = pairs.iter().map(|(x, y)| x + y).sum::<f64>() / count;
+        let variance = pairs
+            .iter()
+            .map(|(x, y)| (x - mean).powi(2) + (y - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        let covariance = pairs
+            .iter()
+            .map(|(x, y)| 2.0 * (x - mean) * (y - mean))
+            .sum::<f64>()
+            / count;
+
+        if variance > 0.0 {
+            Some(covariance / variance)
+        } else {
+            None
+        }
+    }
+
+    pub fn attribute_assortativity<A, F>(&self, attribute: F) -> Option<f64>
+    where
+        A: Hash + Eq,
+        F: Fn(&V) -> A,
+    {
+        // newman's assortativity coefficient for categories. it
+        // compares the fraction of edges joining vertices of the same
+        // category with what you'd expect if edges were placed at
+        // random, and is undefined if there's only the one category.
+        if self.edges.is_empty() {
+            return None;
+        }
+
+        let mut same = 0.0;
+        let mut ends: HashMap<A, f64> = HashMap::new();
+
+        for edge in self.edges.iter() {
+            let (a, b) = (attribute(&edge.v1), attribute(&edge.v2));
+
+            if a == b {
+                same += 1.0;
+            }
+
+            *ends.entry(a).or_default() += 1.0;
+            *ends.entry(b).or_default() += 1.0;
+        }
+
+        let edges = self.edges.len() as f64;
+        let within = same / edges;
+        let expected: f64 = ends.values().map(|&n| (n / (2.0 * edges)).powi(2)).sum();
+
+        if expected < 1.0 {
+            Some((within - expected) / (1.0 - expected))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::disjoint::DisjointSets;
+use super::Graph;
+
+// the graph by number, with one arc each way for an undirected edge
+// (and a single one for a loop), counting how many there are between
+// each pair.
+struct Arcs {
+    out: Vec<Vec<usize>>,
+    into: Vec<Vec<usize>>,
+    count: HashMap<(usize, usize), usize>,
+}
+
+// a step down the first path of the search: the colouring it started
+// from, the colour of the cell it picked a vertex from, and the vertex.
+struct Level {
+    colors: Vec<usize>,
+    cell: usize,
+    vertex: usize,
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // permutations of the vertices that keep every edge where it was,
+    // given as a set of generators that the whole group (every such
+    // permutation) can be built from. there are none if the only one is
+    // leaving everything alone. edge values are ignored, but direction
+    // and parallel edges count.
+    //
+    // this follows the same plan as nauty: colour refinement splits the
+    // vertices up by their neighbourhoods, and when it gets stuck, a
+    // vertex is picked out and given a colour of its own. always
+    // picking the first vertex gives one discrete colouring, and for
+    // each level of that, the search looks for an automorphism taking
+    // the picked vertex to each other vertex in its cell, with
+    // everything picked above it fixed. any vertex already known to be
+    // in the same orbit can be skipped. trying the deepest levels first
+    // means each generator only has to be found once.
+    pub fn automorphisms(&self) -> Vec<HashMap<&V, &V>> {
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let mut arcs = Arcs {
+            out: vec![Vec::new(); vertices.len()],
+            into: vec![Vec::new(); vertices.len()],
+            count: HashMap::new(),
+        };
+
+        for edge in self.edges.iter() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+            arcs.add(a, b);
+
+            if !edge.directed && a != b {
+                arcs.add(b, a);
+            }
+        }
+
+        let mut levels = Vec::new();
+        let mut colors = arcs.refine(vec![0; vertices.len()]);
+
+        while let Some(cell) = target(&colors) {
+            let vertex = (0..vertices.len()).find(|&v| colors[v] == cell).unwrap();
+            let next = arcs.refine(individualize(&colors, vertex));
+            levels.push(Level {
+                colors,
+                cell,
+                vertex,
+            });
+            colors = next;
+        }
+
+        let leaf = colors;
+        let mut orbits = DisjointSets::new(vertices.len());
+        let mut generators = Vec::new();
+
+        for (depth, level) in levels.iter().enumerate().rev() {
+            for other in 0..vertices.len() {
+                if level.colors[other] != level.cell
+                    || orbits.find(other) == orbits.find(level.vertex)
+                {
+                    continue;
+                }
+
+                let colors = arcs.refine(individualize(&level.colors, other));
+
+                if let Some(permutation) = arcs.search(&levels, &leaf, depth + 1, colors) {
+                    for (v, &image) in permutation.iter().enumerate() {
+                        orbits.union(v, image);
+                    }
+
+                    generators.push(permutation);
+                }
+            }
+        }
+
+        generators
+            .into_iter()
+            .map(|permutation| {
+                permutation
+                    .into_iter()
+                    .enumerate()
+                    .map(|(v, image)| (vertices[v], vertices[image]))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl Arcs {
+    fn add(&mut self, a: usize, b: usize) {
+        self.out[a].push(b);
+        self.into[b].push(a);
+        *self.count.entry((a, b)).or_default() += 1;
+    }
+
+    // splits up the colours until every vertex of a colour has the same
+    // number of neighbours of each colour, both ways. the new colours
+    // are numbered in order of what they're split by, starting with
+    // the old colour, so isomorphic colourings stay isomorphic.
+    fn refine(&self, mut colors: Vec<usize>) -> Vec<usize> {
+        loop {
+            let keys: Vec<(usize, Vec<usize>, Vec<usize>)> = (0..colors.len())
+                .map(|v| {
+                    let mut out: Vec<usize> = self.out[v].iter().map(|&u| colors[u]).collect();
+                    let mut into: Vec<usize> = self.into[v].iter().map(|&u| colors[u]).collect();
+                    out.sort_unstable();
+                    into.sort_unstable();
+                    (colors[v], out, into)
+                })
+                .collect();
+
+            let before = count(&colors);
+            colors = renumber(&keys);
+
+            if count(&colors) == before {
+                return colors;
+            }
+        }
+    }
+
+    // carries on down from a colouring of the given depth, picking from
+    // the same cells as the first path did. a colouring whose cells
+    // aren't the same sizes as the first path's can't lead to an
+    // automorphism, so the search gives up on it straight away.
+    fn search(
+        &self,
+        levels: &[Level],
+        leaf: &[usize],
+        depth: usize,
+        colors: Vec<usize>,
+    ) -> Option<Vec<usize>> {
+        let expected = match levels.get(depth) {
+            Some(level) => &level.colors,
+            None => leaf,
+        };
+
+        if sizes(&colors) != sizes(expected) {
+            return None;
+        }
+
+        if depth == levels.len() {
+            // the vertex with each colour in the first leaf goes to the
+            // vertex with the same colour here.
+            let mut permutation = vec![0; colors.len()];
+            let mut by_color = vec![0; colors.len()];
+
+            for (v, &color) in colors.iter().enumerate() {
+                by_color[color] = v;
+            }
+
+            for (v, &color) in leaf.iter().enumerate() {
+                permutation[v] = by_color[color];
+            }
+
+            let preserved = self
+                .count
+                .iter()
+                .all(|(&(a, b), &n)| self.count.get(&(permutation[a], permutation[b])) == Some(&n));
+
+            return if preserved { Some(permutation) } else { None };
+        }
+
+        let cell = levels[depth].cell;
+
+        (0..colors.len())
+            .filter(|&v| colors[v] == cell)
+            .find_map(|v| {
+                self.search(
+                    levels,
+                    leaf,
+                    depth + 1,
+                    self.refine(individualize(&colors, v)),
+                )
+            })
+    }
+}
+
+// the first colour that more than one vertex has, if there is one.
+fn target(colors: &[usize]) -> Option<usize> {
+    sizes(colors).iter().position(|&size| size > 1)
+}
+
+// gives the vertex a colour of its own, just before the rest of its cell.
+fn individualize(colors: &[usize], vertex: usize) -> Vec<usize> {
+    let keys: Vec<(usize, bool)> = colors
+        .iter()
+        .enumerate()
+        .map(|(v, &color)| (color, v != vertex))
+        .collect();
+
+    renumber(&keys)
+}
+
+fn renumber<K: Ord>(keys: &[K]) -> Vec<usize> {
+    let mut distinct: Vec<&K> = keys.iter().collect();
+    distinct.sort();
+    distinct.dedup();
+
+    keys.iter()
+        .map(|key| distinct.binary_search(&key).unwrap())
+        .collect()
+}
+
+fn sizes(colors: &[usize]) -> Vec<usize> {
+    let mut sizes = vec![0; colors.len()];
+    for &color in colors.iter() {
+        sizes[color] += 1;
+    }
+
+    sizes
+}
+
+fn count(colors: &[usize]) -> usize {
+    colors.iter().max().map_or(0, |&most| most + 1)
+}
@@ -0,0 +1,31 @@
+// a set of small numbers as one bit each, for the searches over
+// numbered vertices to keep track of which ones they've finished with.
+// it's an eighth of the size of a vec of bools, so more of it stays in
+// the cache on big graphs.
+#[derive(Debug, Clone)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    // says whether it wasn't already there.
+    pub fn insert(&mut self, i: usize) -> bool {
+        let fresh = !self.contains(i);
+        self.words[i / 64] |= 1 << (i % 64);
+        fresh
+    }
+
+    pub fn remove(&mut self, i: usize) {
+        self.words[i / 64] &= !(1 << (i % 64));
+    }
+}
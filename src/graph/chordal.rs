@@ -0,0 +1,108 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use super::Graph;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // a graph is chordal when every cycle of four or more vertices has
+    // a chord, i.e. it has no holes. only which vertices are adjacent
+    // matters here, so directions, loops and parallel edges are ignored.
+    pub fn is_chordal(&self) -> bool {
+        self.perfect_elimination_order().is_some()
+    }
+
+    // an order where each vertex's later neighbours are all adjacent
+    // to each other, which exists exactly when the graph is chordal.
+    pub fn perfect_elimination_order(&self) -> Option<Vec<&V>> {
+        let (vertices, adjacent) = undirected(self);
+        let order = elimination_order(&adjacent)?;
+
+        Some(order.into_iter().map(|i| vertices[i]).collect())
+    }
+}
+
+// the vertices in some order, along with who's next to whom, ignoring
+// everything about the edges except that they're there.
+pub(super) fn undirected<V, E>(graph: &Graph<V, E>) -> (Vec<&V>, Vec<HashSet<usize>>)
+where
+    V: Hash + Eq,
+{
+    let vertices: Vec<&V> = graph.vertices.iter().collect();
+    let index: HashMap<&V, usize> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, &vertex)| (vertex, i))
+        .collect();
+
+    let mut adjacent: Vec<HashSet<usize>> = vec![HashSet::new(); vertices.len()];
+
+    for edge in graph.edges.iter() {
+        let (a, b) = (index[&edge.v1], index[&edge.v2]);
+
+        if a != b {
+            adjacent[a].insert(b);
+            adjacent[b].insert(a);
+        }
+    }
+
+    (vertices, adjacent)
+}
+
+// maximum cardinality search (tarjan and yannakakis): repeatedly visit
+// whichever vertex has the most visited neighbours. if the graph is
+// chordal, the visiting order backwards is a perfect elimination
+// order, so it only has to be checked.
+pub(super) fn elimination_order(adjacent: &[HashSet<usize>]) -> Option<Vec<usize>> {
+    let count = adjacent.len();
+    let mut weight = vec![0; count];
+    let mut visited = vec![false; count];
+    let mut order = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let vertex = (0..count)
+            .filter(|&v| !visited[v])
+            .max_by_key(|&v| weight[v])
+            .unwrap();
+
+        visited[vertex] = true;
+        order.push(vertex);
+
+        for &next in adjacent[vertex].iter() {
+            if !visited[next] {
+                weight[next] += 1;
+            }
+        }
+    }
+
+    order.reverse();
+
+    let mut position = vec![0; count];
+    for (i, &vertex) in order.iter().enumerate() {
+        position[vertex] = i;
+    }
+
+    // it's enough to check that the later neighbours are adjacent to
+    // the first of them, since that one's later neighbours get checked
+    // in turn.
+    for &vertex in order.iter() {
+        let later: Vec<usize> = adjacent[vertex]
+            .iter()
+            .cloned()
+            .filter(|&next| position[next] > position[vertex])
+            .collect();
+
+        if let Some(&first) = later.iter().min_by_key(|&&next| position[next]) {
+            if later
+                .iter()
+                .any(|&next| next != first && !adjacent[first].contains(&next))
+            {
+                return None;
+            }
+        }
+    }
+
+    Some(order)
+}
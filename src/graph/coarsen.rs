@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::data::GraphData;
+use super::Graph;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + Clone,
+{
+    pub fn coarsen_by<B, F>(&self, block: F) -> GraphData<B, E>
+    where
+        B: Hash + Eq + Clone,
+        F: Fn(&V) -> B,
+    {
+        // each block of the partition becomes a single vertex, and all
+        // the edges between two blocks are merged into one edge whose
+        // value is their sum. edges inside a block become a loop on its
+        // vertex, so the total weight of the graph is kept intact.
+        let mut data = GraphData::new();
+        let mut index: HashMap<B, usize> = HashMap::new();
+
+        for vertex in self.vertices.iter() {
+            let id = block(vertex);
+
+            if !index.contains_key(&id) {
+                index.insert(id.clone(), data.add_vertex(id));
+            }
+        }
+
+        let mut merged: HashMap<(usize, usize), E> = HashMap::new();
+        let mut order: Vec<(usize, usize)> = Vec::new();
+
+        for edge in self.edges.iter() {
+            let (a, b) = (index[&block(&edge.v1)], index[&block(&edge.v2)]);
+            let key = (a.min(b), a.max(b));
+
+            let total = match merged.remove(&key) {
+                Some(total) => total + edge.value.clone(),
+                None => {
+                    order.push(key);
+                    edge.value.clone()
+                }
+            };
+
+            merged.insert(key, total);
+        }
+
+        for key in order {
+            let value = merged.remove(&key).unwrap();
+            data.connect(key.0, key.1, value);
+        }
+
+        data
+    }
+}
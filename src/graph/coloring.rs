@@ -0,0 +1,264 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use super::chordal::{elimination_order, undirected};
+use super::Graph;
+use crate::error::GraphError;
+
+// bookkeeping for misra and gries' algorithm, over numbered vertices.
+// each vertex maps the colours in use around it to the neighbour at
+// the other end of that edge, so free colours can be found quickly.
+struct Coloring {
+    used: Vec<HashMap<usize, usize>>,
+    colors: HashMap<(usize, usize), usize>,
+    palette: usize,
+}
+
+impl Coloring {
+    fn color(&self, a: usize, b: usize) -> Option<usize> {
+        self.colors.get(&(a.min(b), a.max(b))).cloned()
+    }
+
+    fn is_free(&self, vertex: usize, color: usize) -> bool {
+        !self.used[vertex].contains_key(&color)
+    }
+
+    fn free_color(&self, vertex: usize) -> usize {
+        (0..self.palette)
+            .find(|&c| self.is_free(vertex, c))
+            .unwrap()
+    }
+
+    fn set(&mut self, a: usize, b: usize, color: Option<usize>) {
+        if let Some(old) = self.colors.remove(&(a.min(b), a.max(b))) {
+            self.used[a].remove(&old);
+            self.used[b].remove(&old);
+        }
+
+        if let Some(color) = color {
+            self.colors.insert((a.min(b), a.max(b)), color);
+            self.used[a].insert(color, b);
+            self.used[b].insert(color, a);
+        }
+    }
+
+    fn color_edge(&mut self, u: usize, v: usize, neighbors: &[Vec<usize>]) {
+        // build a maximal fan around u, starting with the edge to v.
+        // each edge in the fan has a colour that's free on the fan
+        // vertex before it, which is what lets the colours be shifted
+        // along by one later on.
+        let mut fan = vec![v];
+        let mut in_fan: HashSet<usize> = HashSet::new();
+        in_fan.insert(v);
+
+        loop {
+            let last = *fan.last().unwrap();
+            let next = neighbors[u].iter().cloned().find(|&x| {
+                !in_fan.contains(&x)
+                    && self
+                        .color(u, x)
+                        .is_some_and(|color| self.is_free(last, color))
+            });
+
+            match next {
+                Some(x) => {
+                    fan.push(x);
+                    in_fan.insert(x);
+                }
+                None => break,
+            }
+        }
+
+        let c = self.free_color(u);
+        let d = self.free_color(*fan.last().unwrap());
+
+        // swap c and d along the path of alternating colours that
+        // starts at u. since c is free on u, the path begins with d.
+        let mut path = Vec::new();
+        let (mut current, mut wanted) = (u, d);
+
+        while let Some(&next) = self.used[current].get(&wanted) {
+            path.push((current, next, wanted));
+            current = next;
+            wanted = if wanted == c { d } else { c };
+        }
+
+        for &(a, b, _) in path.iter() {
+            self.set(a, b, None);
+        }
+
+        for &(a, b, color) in path.iter() {
+            self.set(a, b, Some(if color == c { d } else { c }));
+        }
+
+        // now d is free on u, so find the first fan vertex it's also
+        // free on such that everything up to it is still a fan.
+        let mut end = 0;
+
+        for (position, &w) in fan.iter().enumerate() {
+            if position > 0 {
+                let still_fan = self
+                    .color(u, w)
+                    .is_some_and(|color| self.is_free(fan[position - 1], color));
+
+                if !still_fan {
+                    break;
+                }
+            }
+
+            if self.is_free(w, d) {
+                end = position;
+                break;
+            }
+        }
+
+        // rotate the fan: each edge takes the colour of the next one,
+        // which leaves the edge to the end of the fan free for d.
+        let shifted: Vec<Option<usize>> = (0..end).map(|i| self.color(u, fan[i + 1])).collect();
+
+        for &x in fan[..=end].iter() {
+            self.set(u, x, None);
+        }
+
+        for (i, color) in shifted.into_iter().enumerate() {
+            self.set(u, fan[i], color);
+        }
+
+        self.set(u, fan[end], Some(d));
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    pub fn edge_coloring(&self) -> Result<Vec<(&V, &V, usize)>, GraphError> {
+        // misra and gries' constructive proof of vizing's theorem,
+        // which colours simple graphs with at most one more colour
+        // than the maximum degree. the colours are numbered from zero.
+        let index: HashMap<&V, usize> = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, vertex)| (vertex, i))
+            .collect();
+
+        let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); index.len()];
+        let mut seen: HashSet<(usize, usize)> = HashSet::new();
+
+        for edge in self.edges.iter() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+
+            if a == b || !seen.insert((a.min(b), a.max(b))) {
+                return Err(GraphError::Unsupported(
+                    "Edge coloring needs a graph without loops or parallel edges.",
+                ));
+            }
+
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+
+        let max_degree = neighbors.iter().map(|n| n.len()).max().unwrap_or(0);
+        let mut coloring = Coloring {
+            used: vec![HashMap::new(); index.len()],
+            colors: HashMap::new(),
+            palette: max_degree + 1,
+        };
+
+        for edge in self.edges.iter() {
+            coloring.color_edge(index[&edge.v1], index[&edge.v2], &neighbors);
+        }
+
+        Ok(self
+            .edges
+            .iter()
+            .map(|edge| {
+                let color = coloring.color(index[&edge.v1], index[&edge.v2]).unwrap();
+                (&edge.v1, &edge.v2, color)
+            })
+            .collect())
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // colours the vertices so that no two adjacent ones match, with
+    // colours numbered from zero. on a chordal graph, colouring along a
+    // perfect elimination order (backwards) only ever has a clique of
+    // neighbours coloured already, so it uses as few colours as there
+    // can be. anything else gets dsatur (brélaz), which picks whichever
+    // vertex has the most different colours around it next, and is
+    // usually close. loops are ignored, since nothing can colour them.
+    pub fn vertex_coloring(&self) -> HashMap<&V, usize> {
+        let (vertices, adjacent) = undirected(self);
+
+        let order = match elimination_order(&adjacent) {
+            Some(mut order) => {
+                order.reverse();
+                order
+            }
+            None => saturation_order(&adjacent),
+        };
+
+        let colors = greedy_coloring(&adjacent, &order);
+        vertices.into_iter().zip(colors).collect()
+    }
+
+    // the fewest colours a vertex coloring can use, when that can be
+    // worked out exactly (which it can for chordal graphs). otherwise
+    // it's none, and vertex_coloring is only a good guess.
+    pub fn chromatic_number(&self) -> Option<usize> {
+        let (_, adjacent) = undirected(self);
+        let mut order = elimination_order(&adjacent)?;
+        order.reverse();
+
+        let colors = greedy_coloring(&adjacent, &order);
+        Some(colors.iter().map(|&color| color + 1).max().unwrap_or(0))
+    }
+}
+
+// gives each vertex in turn the smallest colour its neighbours aren't
+// using so far.
+fn greedy_coloring(adjacent: &[HashSet<usize>], order: &[usize]) -> Vec<usize> {
+    let mut colors: Vec<Option<usize>> = vec![None; adjacent.len()];
+
+    for &vertex in order.iter() {
+        let taken: HashSet<usize> = adjacent[vertex]
+            .iter()
+            .filter_map(|&next| colors[next])
+            .collect();
+
+        colors[vertex] = (0..).find(|color| !taken.contains(color));
+    }
+
+    colors.into_iter().map(|color| color.unwrap()).collect()
+}
+
+// the order dsatur colours the vertices in, found by colouring them
+// along the way. ties go to the vertex with the most neighbours.
+fn saturation_order(adjacent: &[HashSet<usize>]) -> Vec<usize> {
+    let count = adjacent.len();
+    let mut colors: Vec<Option<usize>> = vec![None; count];
+    let mut around: Vec<HashSet<usize>> = vec![HashSet::new(); count];
+    let mut order = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let vertex = (0..count)
+            .filter(|&v| colors[v].is_none())
+            .max_by_key(|&v| (around[v].len(), adjacent[v].len()))
+            .unwrap();
+
+        let color = (0..).find(|color| !around[vertex].contains(color));
+        colors[vertex] = color;
+        order.push(vertex);
+
+        for &next in adjacent[vertex].iter() {
+            around[next].insert(color.unwrap());
+        }
+    }
+
+    order
+}
@@ -0,0 +1,183 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use super::Graph;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // the pieces the graph falls into, where two vertices are in the
+    // same piece if there's a path between them. directed edges count
+    // both ways here (so these are weakly connected components), and a
+    // vertex with no edges is a piece by itself. dijkstra_paths only
+    // gives the vertices in the source's piece.
+    pub fn connected_components(&self) -> Vec<HashSet<&V>> {
+        let mut seen: HashSet<&V> = HashSet::new();
+        let mut components = Vec::new();
+
+        for start in self.vertices.iter() {
+            if !seen.insert(start) {
+                continue;
+            }
+
+            let mut component = HashSet::new();
+            let mut stack = vec![start];
+
+            while let Some(vertex) = stack.pop() {
+                component.insert(vertex);
+
+                for edge in self.incident_edges(vertex) {
+                    for other in [&edge.v1, &edge.v2].iter() {
+                        if seen.insert(*other) {
+                            stack.push(*other);
+                        }
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        components
+    }
+
+    // whether there's a path between every pair of vertices, ignoring
+    // which way directed edges go. a graph without any vertices counts.
+    pub fn is_connected(&self) -> bool {
+        self.connected_components().len() <= 1
+    }
+
+    // the pieces the graph falls into when directed edges only count
+    // the way they go, so two vertices are in the same piece if each
+    // can reach the other. two-way edges count both ways. the pieces
+    // come out in topological order, so any edge between two of them
+    // goes from an earlier one to a later one.
+    //
+    // this is tarjan's algorithm, with an explicit stack so that a long
+    // path can't overflow the real one.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<&V>> {
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let mut out = vec![Vec::new(); vertices.len()];
+        for edge in self.edges.iter() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+            out[a].push(b);
+
+            if !edge.directed {
+                out[b].push(a);
+            }
+        }
+
+        const UNSEEN: usize = usize::MAX;
+        let mut order = vec![UNSEEN; vertices.len()];
+        let mut low = vec![0; vertices.len()];
+        let mut on_stack = vec![false; vertices.len()];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut visited = 0;
+
+        for root in 0..vertices.len() {
+            if order[root] != UNSEEN {
+                continue;
+            }
+
+            // each call is a vertex and how many of its edges it's
+            // been along so far.
+            let mut calls = vec![(root, 0)];
+            order[root] = visited;
+            low[root] = visited;
+            visited += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (vertex, ref mut next)) = calls.last_mut() {
+                if let Some(&to) = out[vertex].get(*next) {
+                    *next += 1;
+
+                    if order[to] == UNSEEN {
+                        order[to] = visited;
+                        low[to] = visited;
+                        visited += 1;
+                        stack.push(to);
+                        on_stack[to] = true;
+                        calls.push((to, 0));
+                    } else if on_stack[to] {
+                        low[vertex] = low[vertex].min(order[to]);
+                    }
+
+                    continue;
+                }
+
+                calls.pop();
+
+                if let Some(&(parent, _)) = calls.last() {
+                    low[parent] = low[parent].min(low[vertex]);
+                }
+
+                if low[vertex] == order[vertex] {
+                    let mut component = Vec::new();
+
+                    loop {
+                        let member = stack.pop().unwrap();
+                        on_stack[member] = false;
+                        component.push(vertices[member]);
+
+                        if member == vertex {
+                            break;
+                        }
+                    }
+
+                    components.push(component);
+                }
+            }
+        }
+
+        // tarjan's algorithm finishes a piece only after everything it
+        // leads to, so they come out backwards.
+        components.reverse();
+        components
+    }
+
+    // the graph of the strongly connected components, which never has a
+    // cycle. vertex i stands for the i-th piece that
+    // strongly_connected_components gives, and there's a directed edge
+    // between two pieces if any edges go from one to the other, valued
+    // with how many of them there are.
+    pub fn condensation(&self) -> Graph<usize, usize> {
+        let components = self.strongly_connected_components();
+        let piece: HashMap<&V, usize> = components
+            .iter()
+            .enumerate()
+            .flat_map(|(i, component)| component.iter().map(move |&vertex| (vertex, i)))
+            .collect();
+
+        let mut counts: HashMap<(usize, usize), usize> = HashMap::new();
+        for edge in self.edges.iter() {
+            let (a, b) = (piece[&edge.v1], piece[&edge.v2]);
+
+            if a != b {
+                *counts.entry((a, b)).or_default() += 1;
+            }
+        }
+
+        let mut condensed = Graph::empty();
+        for i in 0..components.len() {
+            condensed.add_vertex(i);
+        }
+
+        let mut counts: Vec<((usize, usize), usize)> = counts.into_iter().collect();
+        counts.sort_unstable();
+
+        for ((a, b), count) in counts {
+            condensed.connect_directed(&a, &b, count).unwrap();
+        }
+
+        condensed
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::Graph;
+use crate::error::GraphError;
+
+// an edge value for resource-constrained searches. the cost is what
+// gets minimised, while each resource is summed along the path and
+// must stay within its cap (think battery charge or time windows).
+#[derive(Debug, Clone)]
+pub struct ResourceEdge<C, R> {
+    pub cost: C,
+    pub resources: Vec<R>,
+}
+
+impl<C, R> ResourceEdge<C, R> {
+    pub fn new(cost: C, resources: Vec<R>) -> Self {
+        Self { cost, resources }
+    }
+}
+
+struct Label<'v, V, C, R> {
+    vertex: &'v V,
+    cost: C,
+    resources: Vec<R>,
+    previous: Option<usize>,
+}
+
+impl<'v, V, C, R> Label<'v, V, C, R>
+where
+    C: Ord,
+    R: Ord,
+{
+    // a label dominates another if it is no worse in every respect.
+    // ties count as domination so that equal labels aren't explored
+    // twice (which would otherwise loop forever on zero-cost cycles).
+    fn dominates(&self, other: &Self) -> bool {
+        self.cost <= other.cost
+            && self
+                .resources
+                .iter()
+                .zip(other.resources.iter())
+                .all(|(mine, theirs)| mine <= theirs)
+    }
+}
+
+impl<V, C, R> Graph<V, ResourceEdge<C, R>>
+where
+    V: Hash + Eq,
+    C: Add<Output = C> + Ord + Clone + Default,
+    R: Add<Output = R> + Ord + Clone,
+{
+    // every edge needs one of each resource, the same as there are
+    // limits. the source reaches itself at no cost (the default value),
+    // without using up any of them.
+    pub fn constrained_shortest_path(
+        &self,
+        source: &V,
+        target: &V,
+        limits: &[R],
+    ) -> Result<Option<(Vec<&V>, C)>, GraphError> {
+        if self
+            .edges
+            .iter()
+            .any(|edge| edge.value.resources.len() != limits.len())
+        {
+            return Err(GraphError::InvalidArgument(
+                "Every edge needs one value for each resource limit.",
+            ));
+        }
+
+        let source = match self.vertices.get(source) {
+            Some(source) => source,
+            None => return Ok(None),
+        };
+
+        if source == target {
+            return Ok(Some((vec![source], C::default())));
+        }
+
+        // this is a label-setting algorithm. unlike plain dijkstra,
+        // a vertex can hold several labels at once, since a pricier
+        // path might still be the only one that stays within budget.
+        // a label is only thrown away when another label at the same
+        // vertex dominates it.
+        let mut labels: Vec<Label<V, C, R>> = Vec::new();
+        let mut pending: Vec<usize> = Vec::new();
+        let mut settled: HashMap<&V, Vec<usize>> = HashMap::new();
+
+        let within_limits = |resources: &[R]| {
+            resources
+                .iter()
+                .zip(limits.iter())
+                .all(|(used, cap)| used <= cap)
+        };
+
+        // as with dijkstra_paths, there's no zero value to give the
+        // source, so the first labels are made from its own edges.
+        for (vertex, edge) in self.neighbors(source) {
+            if vertex == source || !within_limits(&edge.resources) {
+                continue;
+            }
+
+            pending.push(labels.len());
+            labels.push(Label {
+                vertex,
+                cost: edge.cost.clone(),
+                resources: edge.resources.clone(),
+                previous: None,
+            });
+        }
+
+        while !pending.is_empty() {
+            // take the cheapest pending label, regardless of vertex.
+            let (position, _) = pending
+                .iter()
+                .enumerate()
+                .min_by_key(|&(_, &index)| &labels[index].cost)
+                .unwrap();
+            let current = pending.swap_remove(position);
+            let vertex = labels[current].vertex;
+
+            // another label may have reached this vertex more cheaply
+            // since this one was created, so check again before use.
+            let existing = settled.entry(vertex).or_default();
+            if existing
+                .iter()
+                .any(|&index| labels[index].dominates(&labels[current]))
+            {
+                continue;
+            }
+            existing.push(current);
+
+            // labels come out in order of cost, so the first one to
+            // reach the target is the cheapest feasible path.
+            if vertex == target {
+                let cost = labels[current].cost.clone();
+                let mut path = vec![vertex];
+                let mut previous = labels[current].previous;
+
+                while let Some(index) = previous {
+                    path.push(labels[index].vertex);
+                    previous = labels[index].previous;
+                }
+
+                path.push(source);
+                path.reverse();
+                return Ok(Some((path, cost)));
+            }
+
+            for (neighbor, edge) in self.neighbors(vertex) {
+                // going back through the source can never help, and
+                // the source has no label of its own to compare with.
+                if neighbor == source {
+                    continue;
+                }
+
+                let resources: Vec<R> = labels[current]
+                    .resources
+                    .iter()
+                    .zip(edge.resources.iter())
+                    .map(|(used, extra)| used.clone() + extra.clone())
+                    .collect();
+
+                if !within_limits(&resources) {
+                    continue;
+                }
+
+                let label = Label {
+                    vertex: neighbor,
+                    cost: labels[current].cost.clone() + edge.cost.clone(),
+                    resources,
+                    previous: Some(current),
+                };
+
+                let dominated = settled.get(neighbor).is_some_and(|existing| {
+                    existing
+                        .iter()
+                        .any(|&index| labels[index].dominates(&label))
+                });
+
+                if !dominated {
+                    pending.push(labels.len());
+                    labels.push(label);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
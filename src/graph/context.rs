@@ -0,0 +1,215 @@
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::ops::Add;
+use std::ptr;
+
+use super::frontier::Queued;
+use super::Graph;
+
+// everything a search needs to keep track of, kept between searches so
+// that running lots of them on the same graph doesn't mean allocating it
+// all again each time. the graph is numbered once, the first time it's
+// searched, and the distances and flags are stamped with which search
+// they came from rather than cleared, so a search only costs as much as
+// the part of the graph it gets to. the context borrows the graph, so
+// it can't change while the context is around, and using it on another
+// graph just numbers that one instead.
+pub struct SearchContext<'g, V: Hash + Eq, E> {
+    graph: Option<&'g Graph<V, E>>,
+    vertices: Vec<&'g V>,
+    index: HashMap<&'g V, usize>,
+    out: Vec<Vec<(usize, E)>>,
+    dist: Vec<Option<E>>,
+    previous: Vec<usize>,
+    reached: Vec<u32>,
+    settled: Vec<u32>,
+    heap: BinaryHeap<Queued<E, usize>>,
+    search: u32,
+    source: usize,
+}
+
+impl<'g, V, E> SearchContext<'g, V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    pub fn new() -> Self {
+        Self {
+            graph: None,
+            vertices: Vec::new(),
+            index: HashMap::new(),
+            out: Vec::new(),
+            dist: Vec::new(),
+            previous: Vec::new(),
+            reached: Vec::new(),
+            settled: Vec::new(),
+            heap: BinaryHeap::new(),
+            search: 0,
+            source: 0,
+        }
+    }
+
+    // the distance to a vertex from the last search's source, as long
+    // as the search settled it. like dijkstra_paths, there's none for
+    // the source itself.
+    pub fn distance(&self, vertex: &V) -> Option<&E> {
+        let vertex = self.settled_index(vertex)?;
+        self.dist[vertex].as_ref()
+    }
+
+    // the last search's shortest path to a vertex it settled, including
+    // both ends.
+    pub fn path_to(&self, vertex: &V) -> Option<Vec<&'g V>> {
+        let mut current = self.settled_index(vertex)?;
+        let mut path = vec![self.vertices[current]];
+
+        while current != self.source {
+            current = self.previous[current];
+            path.push(self.vertices[current]);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    fn settled_index(&self, vertex: &V) -> Option<usize> {
+        let &vertex = self.index.get(vertex)?;
+
+        if self.search == 0 || self.settled[vertex] != self.search || vertex == self.source {
+            return None;
+        }
+
+        Some(vertex)
+    }
+
+    fn prepare(&mut self, graph: &'g Graph<V, E>) {
+        if self.graph.is_some_and(|current| ptr::eq(current, graph)) {
+            return;
+        }
+
+        self.graph = Some(graph);
+        self.vertices = graph.vertices.iter().collect();
+        self.index = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let mut out = vec![Vec::new(); self.vertices.len()];
+        for edge in graph.edges.iter() {
+            let (a, b) = (self.index[&edge.v1], self.index[&edge.v2]);
+            out[a].push((b, edge.value.clone()));
+
+            if !edge.directed && a != b {
+                out[b].push((a, edge.value.clone()));
+            }
+        }
+
+        let n = self.vertices.len();
+        self.out = out;
+        self.dist = vec![None; n];
+        self.previous = vec![0; n];
+        self.reached = vec![0; n];
+        self.settled = vec![0; n];
+        self.search = 0;
+    }
+
+    // starts the next search, wiping the stamps only in the unlikely
+    // event that they've all been used up.
+    fn next_search(&mut self) {
+        if self.search == u32::MAX {
+            self.reached.iter_mut().for_each(|stamp| *stamp = 0);
+            self.settled.iter_mut().for_each(|stamp| *stamp = 0);
+            self.search = 0;
+        }
+
+        self.search += 1;
+        self.heap.clear();
+    }
+
+    fn relax(&mut self, vertex: usize, dist: E, from: usize) {
+        let better = self.reached[vertex] != self.search
+            || self.dist[vertex]
+                .as_ref()
+                .is_none_or(|current| dist < *current);
+
+        if better {
+            self.reached[vertex] = self.search;
+            self.dist[vertex] = Some(dist.clone());
+            self.previous[vertex] = from;
+            self.heap.push(Queued { dist, item: vertex });
+        }
+    }
+}
+
+impl<'g, V, E> Default for SearchContext<'g, V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    // dijkstra's algorithm from the source, using (and reusing) the
+    // context's space, which is also where to look for the results
+    // afterwards. given a target, it stops as soon as that's settled.
+    pub fn dijkstra_with<'g>(
+        &'g self,
+        context: &mut SearchContext<'g, V, E>,
+        source: &V,
+        target: Option<&V>,
+    ) {
+        context.prepare(self);
+        context.next_search();
+
+        let source = match context.index.get(source) {
+            Some(&source) => source,
+            None => return,
+        };
+        let target = target.and_then(|target| context.index.get(target).cloned());
+
+        let search = context.search;
+        context.source = source;
+        context.settled[source] = search;
+
+        for i in 0..context.out[source].len() {
+            let (to, value) = context.out[source][i].clone();
+
+            if to != source {
+                context.relax(to, value, source);
+            }
+        }
+
+        while let Some(Queued { dist, item: vertex }) = context.heap.pop() {
+            if context.settled[vertex] == search
+                || context.dist[vertex]
+                    .as_ref()
+                    .is_some_and(|best| *best < dist)
+            {
+                continue;
+            }
+
+            context.settled[vertex] = search;
+
+            if Some(vertex) == target {
+                break;
+            }
+
+            for i in 0..context.out[vertex].len() {
+                let (to, value) = context.out[vertex][i].clone();
+
+                if context.settled[to] != search {
+                    context.relax(to, dist.clone() + value, vertex);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use super::disjoint::DisjointSets;
+use super::Graph;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // whether there's any way to leave a vertex and come back to it
+    // without using an edge twice.
+    pub fn has_cycle(&self) -> bool {
+        self.find_cycle().is_some()
+    }
+
+    // the vertices around a cycle, in the order it goes, so the last one
+    // leads back to the first. directed edges are only followed one way
+    // and two-way edges either way, but never straight back along the
+    // same edge, so it works for directed, undirected and mixed graphs
+    // alike. (topological_sort is stricter, and counts a two-way edge
+    // as a cycle by itself.) a loop or a pair of parallel edges is
+    // the shortest kind of cycle there is.
+    //
+    // a mixed graph can't just be searched depth first, since which of
+    // a two-way edge's ways gets tried first matters. instead, if the
+    // two-way edges don't already make a cycle between themselves, they
+    // make a forest, and there's a cycle exactly when the directed edges
+    // go round in a cycle between its trees (or back into one tree).
+    pub fn find_cycle(&self) -> Option<Vec<&V>> {
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let ends: Vec<(usize, usize, bool)> = self
+            .edges
+            .iter()
+            .map(|edge| (index[&edge.v1], index[&edge.v2], edge.directed))
+            .collect();
+
+        let mut trees = DisjointSets::new(vertices.len());
+        let mut adjacent = vec![Vec::new(); vertices.len()];
+        let mut closing = None;
+
+        for &(a, b, directed) in ends.iter() {
+            if directed {
+                continue;
+            }
+
+            if trees.union(a, b) {
+                adjacent[a].push(b);
+                adjacent[b].push(a);
+            } else if closing.is_none() {
+                closing = Some((a, b));
+            }
+        }
+
+        let forest = Forest::new(&adjacent);
+        let named = |path: Vec<usize>| path.into_iter().map(|v| vertices[v]).collect();
+
+        // an edge between two vertices that the forest already joins
+        // closes a cycle with the path it has between them.
+        if let Some((a, b)) = closing {
+            return Some(named(forest.path(b, a)));
+        }
+
+        let mut arcs: Vec<Vec<(usize, usize, usize)>> = vec![Vec::new(); vertices.len()];
+        let mut waiting = vec![0; vertices.len()];
+
+        for &(a, b, directed) in ends.iter() {
+            if !directed {
+                continue;
+            }
+
+            let (from, to) = (trees.find(a), trees.find(b));
+
+            if from == to {
+                return Some(named(forest.path(b, a)));
+            }
+
+            arcs[from].push((to, a, b));
+            waiting[to] += 1;
+        }
+
+        // kahn's algorithm over the trees, and if any are left over,
+        // walking backwards from one of them finds a cycle.
+        let mut ready: VecDeque<usize> = (0..vertices.len())
+            .filter(|&v| trees.find(v) == v && waiting[v] == 0)
+            .collect();
+
+        while let Some(tree) = ready.pop_front() {
+            for &(next, _, _) in arcs[tree].iter() {
+                waiting[next] -= 1;
+
+                if waiting[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        let mut into: Vec<Vec<(usize, usize, usize)>> = vec![Vec::new(); vertices.len()];
+        for (tree, out) in arcs.iter().enumerate() {
+            for &(next, a, b) in out.iter() {
+                if waiting[tree] > 0 && waiting[next] > 0 {
+                    into[next].push((tree, a, b));
+                }
+            }
+        }
+
+        let mut current = (0..vertices.len()).find(|&v| waiting[v] > 0)?;
+        let mut position = vec![usize::MAX; vertices.len()];
+        let mut walked = Vec::new();
+
+        while position[current] == usize::MAX {
+            position[current] = walked.len();
+            let (before, a, b) = into[current][0];
+            walked.push((a, b));
+            current = before;
+        }
+
+        // the arcs were found backwards, and each tree on the way is
+        // crossed from where one arc arrives to where the next leaves.
+        let mut crossings = walked.split_off(position[current]);
+        crossings.reverse();
+
+        let mut cycle = Vec::new();
+        for (i, &(_, arrives)) in crossings.iter().enumerate() {
+            let (leaves, _) = crossings[(i + 1) % crossings.len()];
+            cycle.extend(forest.path(arrives, leaves));
+        }
+
+        Some(named(cycle))
+    }
+}
+
+// a forest with every vertex's parent and depth, for finding the path
+// between two vertices in the same tree.
+struct Forest {
+    parent: Vec<usize>,
+    depth: Vec<usize>,
+}
+
+impl Forest {
+    fn new(adjacent: &[Vec<usize>]) -> Self {
+        let mut parent = vec![usize::MAX; adjacent.len()];
+        let mut depth = vec![0; adjacent.len()];
+
+        for root in 0..adjacent.len() {
+            if parent[root] != usize::MAX {
+                continue;
+            }
+
+            parent[root] = root;
+            let mut stack = vec![root];
+
+            while let Some(vertex) = stack.pop() {
+                for &next in adjacent[vertex].iter() {
+                    if parent[next] == usize::MAX {
+                        parent[next] = vertex;
+                        depth[next] = depth[vertex] + 1;
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+
+        Self { parent, depth }
+    }
+
+    // both ends included, climbing from whichever is deeper until they
+    // meet.
+    fn path(&self, from: usize, to: usize) -> Vec<usize> {
+        let (mut x, mut y) = (from, to);
+        let (mut up, mut down) = (Vec::new(), Vec::new());
+
+        while x != y {
+            if self.depth[x] >= self.depth[y] {
+                up.push(x);
+                x = self.parent[x];
+            } else {
+                down.push(y);
+                y = self.parent[y];
+            }
+        }
+
+        up.push(x);
+        up.extend(down.into_iter().rev());
+        up
+    }
+}
@@ -0,0 +1,343 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use super::bitset::BitSet;
+use super::frontier::Frontier;
+use super::Graph;
+use crate::error::GraphError;
+
+// a cycle as the edges it goes around, in order, each given as the
+// vertex it's left from, the one it arrives at, and its value.
+pub type Cycle<'g, V, E> = Vec<(&'g V, &'g V, &'g E)>;
+
+// the steps around a cycle by number, as (from, to, edge).
+type Steps = Vec<(usize, usize, usize)>;
+
+// for every vertex, the vertex above it in a tree and the edge
+// between them, along with how many steps it is from the root.
+struct Tree {
+    parent: Vec<Option<(usize, usize)>>,
+    depth: Vec<usize>,
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: PartialEq,
+{
+    // the cycles made by adding each edge outside of a spanning tree
+    // (or forest) to the tree path between its ends. these are
+    // independent and there's one for every edge the tree leaves out,
+    // so any cycle in the graph is a sum of some of them. for mesh
+    // analysis, the tree's edges are the branches and each cycle is a
+    // loop current. the tree has to be made out of this graph's edges,
+    // like the one from minimum_spanning_tree, and edges are treated
+    // as two-way.
+    pub fn fundamental_cycle_basis(
+        &self,
+        tree: &Graph<V, E>,
+    ) -> Result<Vec<Cycle<'_, V, E>>, GraphError> {
+        let (vertices, index) = self.numbered();
+        let mut in_tree = vec![false; self.edges.len()];
+
+        // parallel edges are told apart by their values, and if two of
+        // them are the same it doesn't matter which one the tree gets.
+        for branch in tree.edges.iter() {
+            let used = match self.incident.get(&branch.v1) {
+                Some(positions) => positions.iter().cloned().find(|&i| {
+                    let edge = &self.edges[i];
+                    !in_tree[i]
+                        && edge.value == branch.value
+                        && ((edge.v1 == branch.v1 && edge.v2 == branch.v2)
+                            || (edge.v1 == branch.v2 && edge.v2 == branch.v1))
+                }),
+                None => None,
+            };
+
+            match used {
+                Some(i) => in_tree[i] = true,
+                None => return Err(GraphError::EdgeNotFound),
+            }
+        }
+
+        let ends = self.ends(&index);
+        let mut adjacent: Vec<Vec<(usize, usize)>> = vec![Vec::new(); vertices.len()];
+        for (i, &(a, b)) in ends.iter().enumerate() {
+            if in_tree[i] {
+                adjacent[a].push((b, i));
+                adjacent[b].push((a, i));
+            }
+        }
+
+        // a search along the tree's edges, which shouldn't ever find a
+        // second way to a vertex.
+        let mut parent = vec![None; vertices.len()];
+        let mut depth = vec![0; vertices.len()];
+        let mut root = vec![usize::MAX; vertices.len()];
+
+        for start in 0..vertices.len() {
+            if root[start] != usize::MAX {
+                continue;
+            }
+
+            root[start] = start;
+            let mut stack = vec![start];
+
+            while let Some(vertex) = stack.pop() {
+                for &(next, edge) in adjacent[vertex].iter() {
+                    if parent[vertex] == Some((next, edge)) {
+                        continue;
+                    }
+
+                    if root[next] != usize::MAX {
+                        return Err(GraphError::InvalidArgument("Spanning tree has a cycle."));
+                    }
+
+                    root[next] = start;
+                    parent[next] = Some((vertex, edge));
+                    depth[next] = depth[vertex] + 1;
+                    stack.push(next);
+                }
+            }
+        }
+
+        let tree = Tree { parent, depth };
+        let mut cycles = Vec::new();
+
+        for (i, &(a, b)) in ends.iter().enumerate() {
+            if in_tree[i] {
+                continue;
+            }
+
+            if root[a] != root[b] {
+                return Err(GraphError::InvalidArgument(
+                    "Spanning tree doesn't span the graph.",
+                ));
+            }
+
+            cycles.push(self.cycle_from(&vertices, &tree.around(i, a, b)));
+        }
+
+        Ok(cycles)
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Into<f64> + Clone,
+{
+    // the cycle basis with the least total weight, which for mesh
+    // analysis keeps the loops as small as they can be. this is
+    // horton's algorithm: for every vertex and every edge outside its
+    // shortest path tree, the edge and the tree paths to its ends make
+    // a candidate, and a minimum basis is always among them. the
+    // candidates are tried from lightest to heaviest, and kept whenever
+    // they're independent of the ones kept so far, which is checked by
+    // gaussian elimination on their edges. that's a lot of candidates,
+    // so this is only for graphs up to a few hundred vertices. edges
+    // are treated as two-way, and weights can't be negative.
+    pub fn minimum_cycle_basis(&self) -> Result<Vec<Cycle<'_, V, E>>, GraphError> {
+        let (vertices, index) = self.numbered();
+        let ends = self.ends(&index);
+        let weights: Vec<f64> = self
+            .edges
+            .iter()
+            .map(|edge| edge.value.clone().into())
+            .collect();
+
+        if weights
+            .iter()
+            .any(|&weight| weight.is_nan() || weight < 0.0)
+        {
+            return Err(GraphError::NegativeWeight);
+        }
+
+        let mut adjacent: Vec<Vec<(usize, f64, usize)>> = vec![Vec::new(); vertices.len()];
+        for (i, &(a, b)) in ends.iter().enumerate() {
+            if a != b {
+                adjacent[a].push((b, weights[i], i));
+                adjacent[b].push((a, weights[i], i));
+            }
+        }
+
+        // the two paths to the ends of an edge are in the same tree, so
+        // once they split they never meet again, and every candidate is
+        // a simple cycle. the same cycle often turns up from several
+        // vertices, and only needs trying once.
+        let words = self.edges.len() / 64 + 1;
+        let mut seen: HashSet<Vec<u64>> = HashSet::new();
+        let mut candidates: Vec<(f64, Vec<u64>, Steps)> = Vec::new();
+
+        for (i, &(a, b)) in ends.iter().enumerate() {
+            if a == b {
+                let mut bits = vec![0; words];
+                bits[i / 64] |= 1 << (i % 64);
+                seen.insert(bits.clone());
+                candidates.push((weights[i], bits, vec![(a, b, i)]));
+            }
+        }
+
+        // there are m - n + c independent cycles, for c connected pieces.
+        let mut covered = vec![false; vertices.len()];
+        let mut pieces = 0;
+
+        for root in 0..vertices.len() {
+            let tree = Tree::shortest_paths(&adjacent, root);
+            let reached = |v: usize| v == root || tree.parent[v].is_some();
+
+            if !covered[root] {
+                pieces += 1;
+                for (v, covered) in covered.iter_mut().enumerate() {
+                    *covered |= reached(v);
+                }
+            }
+
+            for (i, &(a, b)) in ends.iter().enumerate() {
+                let branch = tree.parent[a] == Some((b, i)) || tree.parent[b] == Some((a, i));
+
+                if a == b || branch || !reached(a) || !reached(b) {
+                    continue;
+                }
+
+                let steps = tree.around(i, a, b);
+                let mut bits = vec![0; words];
+                for &(_, _, edge) in steps.iter() {
+                    bits[edge / 64] |= 1 << (edge % 64);
+                }
+
+                if seen.insert(bits.clone()) {
+                    let weight = steps.iter().map(|&(_, _, edge)| weights[edge]).sum();
+                    candidates.push((weight, bits, steps));
+                }
+            }
+        }
+
+        candidates.sort_by(|a, b| {
+            a.0.partial_cmp(&b.0)
+                .unwrap_or(Ordering::Equal)
+                .then(a.2.len().cmp(&b.2.len()))
+        });
+
+        let wanted = self.edges.len() + pieces - vertices.len();
+
+        // each kept cycle is reduced against the ones before it, and
+        // stored along with the first edge it still has.
+        let mut reduced: Vec<(usize, Vec<u64>)> = Vec::new();
+        let mut cycles = Vec::new();
+
+        for (_, mut bits, steps) in candidates {
+            if cycles.len() == wanted {
+                break;
+            }
+
+            for (pivot, row) in reduced.iter() {
+                if bits[pivot / 64] >> (pivot % 64) & 1 == 1 {
+                    for (bit, other) in bits.iter_mut().zip(row.iter()) {
+                        *bit ^= other;
+                    }
+                }
+            }
+
+            let pivot = bits
+                .iter()
+                .position(|&word| word != 0)
+                .map(|word| word * 64 + bits[word].trailing_zeros() as usize);
+
+            if let Some(pivot) = pivot {
+                reduced.push((pivot, bits));
+                cycles.push(self.cycle_from(&vertices, &steps));
+            }
+        }
+
+        Ok(cycles)
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    fn numbered(&self) -> (Vec<&V>, HashMap<&V, usize>) {
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        (vertices, index)
+    }
+
+    fn ends(&self, index: &HashMap<&V, usize>) -> Vec<(usize, usize)> {
+        self.edges
+            .iter()
+            .map(|edge| (index[&edge.v1], index[&edge.v2]))
+            .collect()
+    }
+
+    fn cycle_from<'g>(
+        &'g self,
+        vertices: &[&'g V],
+        steps: &[(usize, usize, usize)],
+    ) -> Cycle<'g, V, E> {
+        steps
+            .iter()
+            .map(|&(from, to, edge)| (vertices[from], vertices[to], &self.edges[edge].value))
+            .collect()
+    }
+}
+
+impl Tree {
+    // dijkstra's algorithm from the root, keeping the edge each vertex
+    // was reached by. anything it can't reach has no parent.
+    fn shortest_paths(adjacent: &[Vec<(usize, f64, usize)>], root: usize) -> Self {
+        let mut parent = vec![None; adjacent.len()];
+        let mut depth = vec![0; adjacent.len()];
+        let mut settled = BitSet::new(adjacent.len());
+        let mut frontier = Frontier::new();
+        frontier.improve(root, 0.0);
+
+        while let Some((vertex, dist)) = frontier.pop() {
+            settled.insert(vertex);
+
+            if let Some((above, _)) = parent[vertex] {
+                depth[vertex] = depth[above] + 1;
+            }
+
+            for &(next, weight, edge) in adjacent[vertex].iter() {
+                if !settled.contains(next) && frontier.improve(next, dist + weight) {
+                    parent[next] = Some((vertex, edge));
+                }
+            }
+        }
+
+        Self { parent, depth }
+    }
+
+    // the cycle that goes across the edge from a to b, then back along
+    // the tree from b to a, as steps of (from, to, edge).
+    fn around(&self, edge: usize, a: usize, b: usize) -> Steps {
+        let mut down = Vec::new();
+        let mut up = vec![(a, b, edge)];
+        let (mut x, mut y) = (b, a);
+
+        // climbing from both ends until they meet, where the steps from
+        // b go up the tree and the steps towards a come back down it.
+        while x != y {
+            if self.depth[x] >= self.depth[y] {
+                let (above, edge) = self.parent[x].unwrap();
+                up.push((x, above, edge));
+                x = above;
+            } else {
+                let (above, edge) = self.parent[y].unwrap();
+                down.push((above, y, edge));
+                y = above;
+            }
+        }
+
+        up.extend(down.into_iter().rev());
+        up
+    }
+}
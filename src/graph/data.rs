@@ -0,0 +1,67 @@
+use std::hash::Hash;
+
+use super::Graph;
+
+// a plain list of vertices, with edges referring to them by index. the
+// generators and parsers build these up, since it's cheaper than
+// hashing every vertex as it arrives, and a graph can be made from one
+// whenever it's needed.
+#[derive(Debug, Clone)]
+pub struct GraphData<V, E> {
+    vertices: Vec<V>,
+    edges: Vec<(usize, usize, E)>,
+}
+
+impl<V, E> GraphData<V, E> {
+    pub fn new() -> Self {
+        Self {
+            vertices: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn add_vertex(&mut self, value: V) -> usize {
+        self.vertices.push(value);
+        self.vertices.len() - 1
+    }
+
+    pub fn connect(&mut self, v1: usize, v2: usize, value: E) {
+        self.edges.push((v1, v2, value));
+    }
+
+    pub fn vertices(&self) -> &[V] {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> &[(usize, usize, E)] {
+        &self.edges
+    }
+}
+
+impl<V, E> Default for GraphData<V, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, E> GraphData<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Clone,
+{
+    pub fn graph(&self) -> Graph<V, E> {
+        let mut graph = Graph::empty();
+
+        for vertex in self.vertices.iter() {
+            graph.add_vertex(vertex.clone());
+        }
+
+        for (v1, v2, value) in self.edges.iter() {
+            graph
+                .connect_vertices(&self.vertices[*v1], &self.vertices[*v2], value.clone())
+                .unwrap();
+        }
+
+        graph
+    }
+}
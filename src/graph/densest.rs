@@ -0,0 +1,181 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+use super::Graph;
+use crate::flow::FlowNetwork;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // the vertices whose induced subgraph has the most edges per
+    // vertex, with that density. this is goldberg's reduction to
+    // minimum cuts: for a guess g, a cut in the right network has
+    // anything on the source side exactly when some subgraph is denser
+    // than g, and a binary search finds the best. guesses are kept as
+    // fractions over n(n - 1), which is fine enough that only one
+    // possible density falls between neighbouring guesses, so the
+    // answer is exact. each guess takes a max flow, so this is for
+    // graphs with up to a few thousand vertices; for bigger ones,
+    // densest_subgraph_peeling is much faster and within a factor of 2.
+    // parallel edges count separately, loops are ignored, and edges
+    // are treated as two-way. gives none if there aren't any edges.
+    pub fn densest_subgraph(&self) -> Option<(Vec<&V>, f64)> {
+        let (vertices, edges) = self.simple_edges();
+        let (n, m) = (vertices.len() as u128, edges.len() as u128);
+
+        if m == 0 {
+            return None;
+        }
+
+        let scale = n * (n - 1);
+        let mut degree = vec![0u128; vertices.len()];
+
+        for &(a, b) in edges.iter() {
+            degree[a] += 1;
+            degree[b] += 1;
+        }
+
+        // with everything scaled up by n(n - 1), a guess p stands for a
+        // density of p / n(n - 1), and the densest subgraph always has a
+        // density below m.
+        let denser = |guess: u128| -> Option<Vec<usize>> {
+            let (source, sink) = (vertices.len(), vertices.len() + 1);
+            let mut network = FlowNetwork::new(vertices.len() + 2);
+
+            for (v, &d) in degree.iter().enumerate() {
+                network.add_arc(source, v, m * scale);
+                network.add_arc(v, sink, m * scale + 2 * guess - d * scale);
+            }
+
+            for &(a, b) in edges.iter() {
+                network.add_arc(a, b, scale);
+                network.add_arc(b, a, scale);
+            }
+
+            network.max_flow(source, sink);
+            let side = network.min_cut(source);
+            let chosen: Vec<usize> = (0..vertices.len()).filter(|&v| side[v]).collect();
+
+            if chosen.is_empty() {
+                None
+            } else {
+                Some(chosen)
+            }
+        };
+
+        // any single edge has a density of a half.
+        let (mut low, mut high) = (0, m * scale);
+        let mut best = denser(0).unwrap();
+
+        while high - low > 1 {
+            let middle = (low + high) / 2;
+
+            match denser(middle) {
+                Some(chosen) => {
+                    best = chosen;
+                    low = middle;
+                }
+                None => high = middle,
+            }
+        }
+
+        Some(density(&vertices, &edges, best))
+    }
+
+    // charikar's greedy peeling: keep taking away whichever vertex has
+    // the fewest edges left, and the densest of the graphs on the way
+    // down is at least half as dense as the densest subgraph. it's
+    // about as quick as sorting the edges. the same rules as for
+    // densest_subgraph apply about which edges count.
+    pub fn densest_subgraph_peeling(&self) -> Option<(Vec<&V>, f64)> {
+        let (vertices, edges) = self.simple_edges();
+
+        if edges.is_empty() {
+            return None;
+        }
+
+        let mut adjacent: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+        for &(a, b) in edges.iter() {
+            adjacent[a].push(b);
+            adjacent[b].push(a);
+        }
+
+        let mut degree: Vec<usize> = adjacent.iter().map(|around| around.len()).collect();
+        let mut removed = vec![false; vertices.len()];
+        let mut queue: BinaryHeap<Reverse<(usize, usize)>> = degree
+            .iter()
+            .enumerate()
+            .map(|(v, &d)| Reverse((d, v)))
+            .collect();
+
+        let (mut left, mut remaining) = (vertices.len(), edges.len());
+        let mut best = (remaining as f64 / left as f64, 0);
+        let mut peeled = Vec::with_capacity(vertices.len());
+
+        while let Some(Reverse((d, vertex))) = queue.pop() {
+            if removed[vertex] || d != degree[vertex] {
+                continue;
+            }
+
+            removed[vertex] = true;
+            peeled.push(vertex);
+            remaining -= d;
+            left -= 1;
+
+            for &next in adjacent[vertex].iter() {
+                if !removed[next] {
+                    degree[next] -= 1;
+                    queue.push(Reverse((degree[next], next)));
+                }
+            }
+
+            if left > 0 && remaining as f64 / left as f64 > best.0 {
+                best = (remaining as f64 / left as f64, peeled.len());
+            }
+        }
+
+        // whatever was still there after the best number of removals.
+        let chosen = peeled[best.1..].to_vec();
+        Some(density(&vertices, &edges, chosen))
+    }
+
+    // the vertices in some order, with every edge that isn't a loop.
+    fn simple_edges(&self) -> (Vec<&V>, Vec<(usize, usize)>) {
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let edges = self
+            .edges
+            .iter()
+            .map(|edge| (index[&edge.v1], index[&edge.v2]))
+            .filter(|(a, b)| a != b)
+            .collect();
+
+        (vertices, edges)
+    }
+}
+
+fn density<'g, V>(
+    vertices: &[&'g V],
+    edges: &[(usize, usize)],
+    chosen: Vec<usize>,
+) -> (Vec<&'g V>, f64) {
+    let mut inside = vec![false; vertices.len()];
+    for &v in chosen.iter() {
+        inside[v] = true;
+    }
+
+    let count = edges
+        .iter()
+        .filter(|&&(a, b)| inside[a] && inside[b])
+        .count();
+    let density = count as f64 / chosen.len() as f64;
+
+    (chosen.into_iter().map(|v| vertices[v]).collect(), density)
+}
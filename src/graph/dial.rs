@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::bitset::BitSet;
+use super::queue::IntegerWeight;
+use super::Graph;
+use crate::error::GraphError;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: IntegerWeight + Add<Output = E> + Clone,
+{
+    // the same distances as dijkstra_paths, for graphs whose weights
+    // are whole numbers no bigger than the given bound. this is dial's
+    // algorithm: a vertex is queued in the bucket for its distance, and
+    // the buckets are gone through in order. everything queued at once
+    // is within the bound of the closest, so only one more bucket than
+    // that is needed, used round and round, and nothing is ever
+    // compared. it's a lot quicker than a heap when the bound is small,
+    // like on grids with a handful of different costs, but goes through
+    // every distance up to the furthest vertex, so a large bound makes
+    // it slower instead.
+    pub fn dial_paths(&self, source: &V, max_weight: E) -> Result<HashMap<&V, E>, GraphError> {
+        let bound = max_weight.as_u64();
+
+        if self.edges.iter().any(|edge| edge.value.as_u64() > bound) {
+            return Err(GraphError::InvalidArgument(
+                "Edge weights can't be bigger than the bound.",
+            ));
+        }
+
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let source = match index.get(source) {
+            Some(&source) => source,
+            None => return Ok(HashMap::new()),
+        };
+
+        let mut out: Vec<Vec<(usize, &E)>> = vec![Vec::new(); vertices.len()];
+        for edge in self.edges.iter() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+            out[a].push((b, &edge.value));
+
+            if !edge.directed && a != b {
+                out[b].push((a, &edge.value));
+            }
+        }
+
+        let size = bound + 1;
+        let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); size as usize];
+        let mut dist: Vec<Option<E>> = vec![None; vertices.len()];
+        let mut keys = vec![u64::MAX; vertices.len()];
+        let mut settled = BitSet::new(vertices.len());
+        let mut queued = 0;
+        settled.insert(source);
+
+        // the source's own neighbours go in first, as with the other
+        // searches, since there's no zero for E to start it off with.
+        for &(to, value) in out[source].iter() {
+            let key = value.as_u64();
+
+            if !settled.contains(to) && key < keys[to] {
+                keys[to] = key;
+                dist[to] = Some(value.clone());
+                buckets[(key % size) as usize].push(to);
+                queued += 1;
+            }
+        }
+
+        let mut current = 0;
+
+        while queued > 0 {
+            let bucket = (current % size) as usize;
+
+            // zero weight edges queue things into this same bucket while
+            // it's being emptied, and they get settled here too.
+            while let Some(vertex) = buckets[bucket].pop() {
+                queued -= 1;
+
+                // it's either been settled already, or it's an older
+                // entry for a vertex that's since got closer.
+                if settled.contains(vertex) || keys[vertex] != current {
+                    continue;
+                }
+
+                settled.insert(vertex);
+                let here = dist[vertex].clone().unwrap();
+
+                for &(to, value) in out[vertex].iter() {
+                    if settled.contains(to) {
+                        continue;
+                    }
+
+                    let through = here.clone() + value.clone();
+                    let key = through.as_u64();
+
+                    if key < keys[to] {
+                        keys[to] = key;
+                        dist[to] = Some(through);
+                        buckets[(key % size) as usize].push(to);
+                        queued += 1;
+                    }
+                }
+            }
+
+            current += 1;
+        }
+
+        Ok(dist
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, dist)| dist.map(|dist| (vertices[i], dist)))
+            .collect())
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::Add;
+
+use super::Graph;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    // the furthest any vertex is from this one, following the edges
+    // out of it, or none if it can't reach everything (or there's
+    // nothing else to reach).
+    pub fn eccentricity(&self, vertex: &V) -> Option<E> {
+        let distances = self.dijkstra_paths(vertex);
+        farthest(&distances, self.vertices.len()).map(|(_, dist)| dist.clone())
+    }
+
+    // the longest shortest path, found without searching from every
+    // vertex. double sweeps give a good lower bound to start with, then
+    // ifub (crescenzi et al.) works through the vertices from farthest
+    // to nearest around a central one, u. two vertices both within d of
+    // u can't be more than 2d apart, so once the lower bound reaches
+    // twice the distance of the next vertex, nothing left can beat it.
+    // on real networks that's usually after a handful of searches.
+    // gives none if the graph is disconnected or has one vertex.
+    //
+    // with directed edges, being close to u doesn't mean u is close to
+    // you, so none of that holds and it searches from every vertex.
+    pub fn diameter(&self) -> Option<E> {
+        let mut vertices: Vec<&V> = self.vertices.iter().collect();
+        vertices.sort_by_key(|&vertex| {
+            let mut hasher = DefaultHasher::new();
+            vertex.hash(&mut hasher);
+            hasher.finish()
+        });
+
+        let count = vertices.len();
+        let start = *vertices.first()?;
+
+        if self.edges.iter().any(|edge| edge.directed) {
+            let mut longest: Option<E> = None;
+
+            for vertex in vertices {
+                let ecc = self.eccentricity(vertex)?;
+
+                if longest.as_ref().is_none_or(|longest| ecc > *longest) {
+                    longest = Some(ecc);
+                }
+            }
+
+            return longest;
+        }
+
+        let (a, _) = farthest(&self.dijkstra_paths(start), count)?;
+        let from_a = self.dijkstra_paths(a);
+        let (b, mut lower) = farthest(&from_a, count).map(|(b, dist)| (b, dist.clone()))?;
+        let from_b = self.dijkstra_paths(b);
+
+        // the vertex nearest the middle of the a to b path makes a
+        // good centre, since everything tends to be close to it.
+        let mut center = a;
+        let mut best: Option<E> = None;
+
+        for &vertex in vertices.iter() {
+            let reach = match (from_a.get(vertex), from_b.get(vertex)) {
+                (Some(x), Some(y)) => {
+                    if x > y {
+                        x.clone()
+                    } else {
+                        y.clone()
+                    }
+                }
+                _ => continue,
+            };
+
+            let better = match &best {
+                Some(current) => reach < *current,
+                None => true,
+            };
+
+            if better {
+                best = Some(reach);
+                center = vertex;
+            }
+        }
+
+        let from_center = self.dijkstra_paths(center);
+        if let Some((_, dist)) = farthest(&from_center, count) {
+            if *dist > lower {
+                lower = dist.clone();
+            }
+        }
+
+        let mut fringe: Vec<(&V, &E)> = from_center.iter().map(|(&v, d)| (v, d)).collect();
+        fringe.sort_by(|x, y| y.1.partial_cmp(x.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        for (vertex, dist) in fringe {
+            if lower >= dist.clone() + dist.clone() {
+                break;
+            }
+
+            if let Some((_, ecc)) = farthest(&self.dijkstra_paths(vertex), count) {
+                if *ecc > lower {
+                    lower = ecc.clone();
+                }
+            }
+        }
+
+        Some(lower)
+    }
+}
+
+// the farthest of the vertices a search reached, provided it reached
+// every vertex besides where it started.
+fn farthest<'g, 'd, V, E: PartialOrd>(
+    distances: &'d HashMap<&'g V, E>,
+    count: usize,
+) -> Option<(&'g V, &'d E)> {
+    if distances.len() + 1 != count {
+        return None;
+    }
+
+    let mut best: Option<(&'g V, &'d E)> = None;
+
+    for (&vertex, dist) in distances.iter() {
+        let further = match best {
+            Some((_, current)) => dist > current,
+            None => true,
+        };
+
+        if further {
+            best = Some((vertex, dist));
+        }
+    }
+
+    best
+}
@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::bitset::BitSet;
+use super::frontier::Frontier;
+use super::Graph;
+use crate::error::GraphError;
+use crate::flow::FlowNetwork;
+
+const NONE: usize = usize::MAX;
+
+// the edges along a path in the order they're taken, each one given
+// the way it's being gone along.
+pub type Path<'g, V, E> = Vec<(&'g V, &'g V, &'g E)>;
+
+// two paths and their total length.
+pub type PathPair<'g, V, E> = (Path<'g, V, E>, Path<'g, V, E>, f64);
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // up to k paths from the source to the target that never use the
+    // same edge, or fewer if that's all there are, so the target can
+    // still be reached if any edge but one of each path goes down.
+    // directed edges are only used the way they go.
+    pub fn edge_disjoint_paths(
+        &self,
+        source: &V,
+        target: &V,
+        k: usize,
+    ) -> Result<Vec<Path<'_, V, E>>, GraphError> {
+        self.disjoint_paths(source, target, k, false)
+    }
+
+    // the same, except that the paths don't share any vertices either,
+    // besides the source and target themselves.
+    pub fn vertex_disjoint_paths(
+        &self,
+        source: &V,
+        target: &V,
+        k: usize,
+    ) -> Result<Vec<Path<'_, V, E>>, GraphError> {
+        self.disjoint_paths(source, target, k, true)
+    }
+
+    // every edge can carry one unit of flow, or one each way for a
+    // two-way edge, and a super source lets no more than k units out of
+    // the source. for vertex disjoint paths, each vertex is also split
+    // into an in and an out half, joined by an arc that carries one unit.
+    // a maximum flow is then the most paths there can be, and it's
+    // taken apart into paths by walking along the edges it uses.
+    fn disjoint_paths(
+        &self,
+        source: &V,
+        target: &V,
+        k: usize,
+        split: bool,
+    ) -> Result<Vec<Path<'_, V, E>>, GraphError> {
+        if !self.contains(source) || !self.contains(target) {
+            return Err(GraphError::VertexNotFound);
+        }
+
+        if source == target {
+            return Err(GraphError::InvalidArgument(
+                "Source and target must be different.",
+            ));
+        }
+
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let (from, to) = (index[source], index[target]);
+        let halves = if split { 2 } else { 1 };
+        let into = |v: usize| v * halves;
+        let out = |v: usize| v * halves + halves - 1;
+
+        let mut network = FlowNetwork::new(vertices.len() * halves + 1);
+        let start = vertices.len() * halves;
+        network.add_arc(start, out(from), k);
+
+        if split {
+            for v in 0..vertices.len() {
+                let capacity = if v == from || v == to { k } else { 1 };
+                network.add_arc(into(v), out(v), capacity);
+            }
+        }
+
+        let arcs = self.numbered_arcs(&index);
+        let ids: Vec<usize> = arcs
+            .iter()
+            .map(|&(a, b, _)| network.add_arc(out(a), into(b), 1))
+            .collect();
+
+        let count = network.max_flow(start, into(to));
+        let mut steps = untangle(vertices.len(), &arcs, |j| network.flow(ids[j]) > 0);
+
+        Ok(self.named(&vertices, walk(&mut steps, from, to, count)))
+    }
+
+    // every way an edge can be gone along, as the vertex it leaves, the
+    // vertex it gets to and the edge. loops never help a path.
+    fn numbered_arcs(&self, index: &HashMap<&V, usize>) -> Vec<(usize, usize, usize)> {
+        let mut arcs = Vec::new();
+
+        for (i, edge) in self.edges.iter().enumerate() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+
+            if a == b {
+                continue;
+            }
+
+            arcs.push((a, b, i));
+
+            if !edge.directed {
+                arcs.push((b, a, i));
+            }
+        }
+
+        arcs
+    }
+
+    fn named<'g>(&'g self, vertices: &[&'g V], paths: Vec<Steps>) -> Vec<Path<'g, V, E>> {
+        paths
+            .into_iter()
+            .map(|path| {
+                path.into_iter()
+                    .map(|(a, b, i)| (vertices[a], vertices[b], &self.edges[i].value))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Into<f64> + Clone,
+{
+    // two paths from the source to the target that don't share an edge,
+    // as short as possible between them, along with their total length.
+    // taking the shortest path and then the shortest one avoiding it
+    // can do worse, or find no second path at all when there is a pair.
+    //
+    // this is suurballe's algorithm: after the first shortest path, its
+    // edges are turned around with their lengths made negative, so the
+    // second search can undo part of the first. the distances from the
+    // first search make every length non-negative again, so it can still
+    // be dijkstra's algorithm. where the second path goes back along the
+    // first, both leave that edge out, and what's left is the pair.
+    pub fn shortest_disjoint_path_pair(
+        &self,
+        source: &V,
+        target: &V,
+    ) -> Result<PathPair<'_, V, E>, GraphError> {
+        if !self.contains(source) || !self.contains(target) {
+            return Err(GraphError::VertexNotFound);
+        }
+
+        if source == target {
+            return Err(GraphError::InvalidArgument(
+                "Source and target must be different.",
+            ));
+        }
+
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let lengths: Vec<f64> = self
+            .edges
+            .iter()
+            .map(|edge| edge.value.clone().into())
+            .collect();
+
+        if lengths.iter().any(|&length| length < 0.0) {
+            return Err(GraphError::NegativeWeight);
+        }
+
+        let (from, to) = (index[source], index[target]);
+        let arcs = self.numbered_arcs(&index);
+        let mut used = vec![false; arcs.len()];
+        let mut potential = vec![0.0; vertices.len()];
+
+        for _ in 0..2 {
+            // an arc that's in use can only be gone back along.
+            let mut adjacent = vec![Vec::new(); vertices.len()];
+            for (j, &(a, b, i)) in arcs.iter().enumerate() {
+                let (a, b, length) = if used[j] {
+                    (b, a, -lengths[i])
+                } else {
+                    (a, b, lengths[i])
+                };
+
+                let reduced = length + potential[a] - potential[b];
+                adjacent[a].push((b, reduced.max(0.0), j));
+            }
+
+            let mut frontier = Frontier::new();
+            let mut dist = vec![f64::INFINITY; vertices.len()];
+            let mut via = vec![NONE; vertices.len()];
+            let mut settled = BitSet::new(vertices.len());
+
+            frontier.improve(from, 0.0);
+
+            while let Some((vertex, length)) = frontier.pop() {
+                settled.insert(vertex);
+                dist[vertex] = length;
+
+                for &(next, weight, j) in adjacent[vertex].iter() {
+                    if !settled.contains(next) && frontier.improve(next, length + weight) {
+                        via[next] = j;
+                    }
+                }
+            }
+
+            if !settled.contains(to) {
+                return Err(GraphError::Infeasible(
+                    "There aren't two edge-disjoint paths between the vertices.",
+                ));
+            }
+
+            let mut vertex = to;
+            while vertex != from {
+                let j = via[vertex];
+                let (a, b, _) = arcs[j];
+                vertex = if used[j] { b } else { a };
+                used[j] = !used[j];
+            }
+
+            for (v, &length) in dist.iter().enumerate() {
+                if length.is_finite() {
+                    potential[v] += length;
+                }
+            }
+        }
+
+        let mut steps = untangle(vertices.len(), &arcs, |j| used[j]);
+        let paths = walk(&mut steps, from, to, 2);
+        let total = paths.iter().flatten().map(|&(_, _, i)| lengths[i]).sum();
+
+        let mut named = self.named(&vertices, paths);
+        let second = named.pop().unwrap();
+        let first = named.pop().unwrap();
+
+        Ok((first, second, total))
+    }
+}
+
+// a path by number, as the vertex each step leaves, the vertex it gets
+// to and the edge it uses.
+type Steps = Vec<(usize, usize, usize)>;
+
+// the steps leaving each vertex, out of the arcs with flow along them.
+// a two-way edge with flow going each way is the same as the edge not
+// being used at all.
+fn untangle<F>(count: usize, arcs: &[(usize, usize, usize)], flowing: F) -> Vec<Vec<(usize, usize)>>
+where
+    F: Fn(usize) -> bool,
+{
+    let mut ways: HashMap<usize, usize> = HashMap::new();
+    for (j, &(_, _, i)) in arcs.iter().enumerate() {
+        if flowing(j) {
+            *ways.entry(i).or_default() += 1;
+        }
+    }
+
+    let mut steps = vec![Vec::new(); count];
+    for (j, &(a, b, i)) in arcs.iter().enumerate() {
+        if flowing(j) && ways[&i] == 1 {
+            steps[a].push((b, i));
+        }
+    }
+
+    steps
+}
+
+// takes apart a flow of the given amount into paths, by following the
+// steps it uses (where each vertex has the steps leaving it) from the
+// source until reaching the target.
+fn walk(steps: &mut [Vec<(usize, usize)>], from: usize, to: usize, count: usize) -> Vec<Steps> {
+    let mut paths = Vec::with_capacity(count);
+    let mut position = vec![NONE; steps.len()];
+
+    for _ in 0..count {
+        let mut path: Steps = Vec::new();
+        let mut current = from;
+        position[from] = 0;
+
+        while current != to {
+            let (next, i) = steps[current].pop().unwrap();
+            path.push((current, next, i));
+
+            // a loop back to somewhere the path has already been
+            // is just cut out.
+            let seen = position[next];
+            if seen != NONE {
+                for &(visited, _, _) in path[seen..].iter() {
+                    position[visited] = NONE;
+                }
+
+                path.truncate(seen);
+            }
+
+            position[next] = path.len();
+            current = next;
+        }
+
+        for &(visited, _, _) in path.iter() {
+            position[visited] = NONE;
+        }
+        position[to] = NONE;
+
+        paths.push(path);
+    }
+
+    paths
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io::{self, Write};
+
+use super::Graph;
+
+// a double quoted dot string. backslashes start dot's own escapes in
+// labels (like \N for the node's name), so they're escaped too.
+fn quoted(text: &str) -> String {
+    let mut escaped = String::from("\"");
+
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Display,
+    E: Display,
+{
+    // the graph in graphviz's dot language, for drawing with something
+    // like `dot -Tsvg`. vertices are labelled with their values and edges
+    // with theirs. if every edge is two-way it's written as an undirected
+    // graph, and otherwise as a directed one, with any two-way edges
+    // drawn without arrowheads. the vertices are given names of their
+    // own (n0, n1 and so on), so that two vertices that display the same
+    // way don't get merged, in order of their labels. vertices with the
+    // same label go in order of their edges (which edges they're at, and
+    // which end of each they're on), which tells apart any two that
+    // aren't both on their own, so the same graph always gives the same
+    // output.
+    pub fn write_dot<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut labelled: Vec<_> = self
+            .vertices
+            .iter()
+            .map(|vertex| {
+                let mut ends: Vec<(usize, bool)> = self
+                    .incident
+                    .get(vertex)
+                    .into_iter()
+                    .flatten()
+                    .map(|&i| (i, self.edges[i].v1 != *vertex))
+                    .collect();
+                ends.sort_unstable();
+
+                (vertex.to_string(), ends, vertex)
+            })
+            .collect();
+        labelled.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+
+        let name: HashMap<&V, usize> = labelled
+            .iter()
+            .enumerate()
+            .map(|(i, &(_, _, vertex))| (vertex, i))
+            .collect();
+
+        let directed = self.edges.iter().any(|edge| edge.directed);
+        let (kind, arrow) = if directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        writeln!(writer, "{} {{", kind)?;
+
+        for (i, (label, _, _)) in labelled.iter().enumerate() {
+            writeln!(writer, "    n{} [label={}];", i, quoted(label))?;
+        }
+
+        for edge in self.edges.iter() {
+            let extra = if directed && !edge.directed {
+                ", dir=none"
+            } else {
+                ""
+            };
+
+            writeln!(
+                writer,
+                "    n{} {} n{} [label={}{}];",
+                name[&edge.v1],
+                arrow,
+                name[&edge.v2],
+                quoted(&edge.value.to_string()),
+                extra
+            )?;
+        }
+
+        writeln!(writer, "}}")
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut bytes = Vec::new();
+
+        // writing to a vec can't fail, and everything written was a str.
+        self.write_dot(&mut bytes).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+}
@@ -0,0 +1,190 @@
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use super::Graph;
+use crate::error::GraphError;
+
+// a plain text edge list, with one edge per line as its two ends and
+// then its weight, like "A B 5", or "A,B,5" as a csv. a line with just
+// one name on it (or one name and then the delimiter) is a vertex
+// without any edges. blank lines and lines starting with # are skipped,
+// and anything after the weight is ignored. every edge is directed, or
+// none of them are, going by the flag rather than anything in the file.
+//
+// fields are split on the given delimiter, with the spaces around them
+// trimmed off. a whitespace delimiter splits on any run of whitespace,
+// so names can't have spaces in them then, but with anything else
+// they can. the file has to be read with the delimiter it was written
+// with.
+impl<E: FromStr> Graph<String, E> {
+    // edges without a weight are read as having a weight of one.
+    pub fn from_edge_list<R: BufRead>(
+        reader: R,
+        delimiter: char,
+        directed: bool,
+    ) -> Result<Self, GraphError> {
+        Self::from_edge_list_with(reader, delimiter, directed, |weight| {
+            weight.unwrap_or("1").parse().ok()
+        })
+    }
+
+    pub fn parse_edge_list(
+        text: &str,
+        delimiter: char,
+        directed: bool,
+    ) -> Result<Self, GraphError> {
+        Self::from_edge_list(text.as_bytes(), delimiter, directed)
+    }
+}
+
+impl<E> Graph<String, E> {
+    // the same, but reading the weights with the given function, which
+    // gets None for an edge without one. it gives back None if the
+    // weight can't be read.
+    pub fn from_edge_list_with<R, F>(
+        reader: R,
+        delimiter: char,
+        directed: bool,
+        weight: F,
+    ) -> Result<Self, GraphError>
+    where
+        R: BufRead,
+        F: Fn(Option<&str>) -> Option<E>,
+    {
+        let mut graph = Graph::empty();
+
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields: Vec<&str> = if delimiter.is_whitespace() {
+                line.split_whitespace().collect()
+            } else {
+                line.split(delimiter).map(str::trim).collect()
+            };
+
+            // the delimiter after a vertex on its own.
+            if fields.len() == 2 && fields[1].is_empty() {
+                fields.pop();
+            }
+
+            if fields.iter().take(2).any(|field| field.is_empty()) {
+                return Err(GraphError::syntax(number + 1, "Vertex name is empty."));
+            }
+
+            for name in fields.iter().take(2) {
+                if !graph.vertices.contains(*name) {
+                    graph.add_vertex(name.to_string());
+                }
+            }
+
+            if fields.len() < 2 {
+                continue;
+            }
+
+            let value = weight(fields.get(2).cloned()).ok_or_else(|| {
+                GraphError::syntax(number + 1, "Edge weight could not be parsed.")
+            })?;
+
+            let (a, b) = (fields[0].to_string(), fields[1].to_string());
+
+            // both ends were just added, so connecting them can't fail.
+            if directed {
+                graph.connect_directed(&a, &b, value).unwrap();
+            } else {
+                graph.connect_vertices(&a, &b, value).unwrap();
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Display,
+    E: Display,
+{
+    // every edge as a line of its ends and its weight, split by the
+    // delimiter, and then a line for each vertex without any edges, so
+    // they survive the trip. those end with the delimiter too (unless
+    // it's whitespace), so it's clear which one the file uses. the file
+    // doesn't say which edges are directed, so a graph with both kinds
+    // can't be written out.
+    pub fn write_edge_list<W: Write>(&self, writer: &mut W, delimiter: char) -> io::Result<()> {
+        let directed = self.edges.iter().filter(|edge| edge.directed).count();
+
+        if directed != 0 && directed != self.edges.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Graph has both directed and undirected edges.",
+            ));
+        }
+
+        let separator = if delimiter.is_whitespace() {
+            delimiter.to_string()
+        } else {
+            format!("{} ", delimiter)
+        };
+
+        for edge in self.edges.iter() {
+            let fields = [
+                edge.v1.to_string(),
+                edge.v2.to_string(),
+                edge.value.to_string(),
+            ];
+
+            for field in fields.iter() {
+                check_field(field, delimiter)?;
+            }
+
+            writeln!(writer, "{}", fields.join(&separator))?;
+        }
+
+        for vertex in self.vertices.iter() {
+            if self.incident_edges(vertex).next().is_none() {
+                let name = vertex.to_string();
+                check_field(&name, delimiter)?;
+
+                if delimiter.is_whitespace() {
+                    writeln!(writer, "{}", name)?;
+                } else {
+                    writeln!(writer, "{}{}", name, delimiter)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn to_edge_list(&self, delimiter: char) -> io::Result<String> {
+        let mut buffer = Vec::new();
+        self.write_edge_list(&mut buffer, delimiter)?;
+        Ok(String::from_utf8(buffer).unwrap())
+    }
+}
+
+// there's no quoting, so a field can't have the delimiter in it, or
+// anything that would be trimmed off or read as a comment.
+fn check_field(text: &str, delimiter: char) -> io::Result<()> {
+    let bad = text.is_empty()
+        || text.starts_with('#')
+        || text.trim() != text
+        || text.contains([delimiter, '\n', '\r'])
+        || (delimiter.is_whitespace() && text.contains(char::is_whitespace));
+
+    if bad {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Value can't be written in the edge list format.",
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::Graph;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Clone,
+{
+    // the neighbourhood of a vertex, as far as the given number of
+    // hops away from it (regardless of edge values).
+    pub fn ego_network(&self, center: &V, radius: usize) -> Graph<V, E> {
+        let mut within: HashSet<&V> = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        if self.contains(center) {
+            within.insert(center);
+            queue.push_back((center, 0));
+        }
+
+        while let Some((vertex, hops)) = queue.pop_front() {
+            if hops == radius {
+                continue;
+            }
+
+            for (neighbor, _) in self.neighbors(vertex) {
+                if within.insert(neighbor) {
+                    queue.push_back((neighbor, hops + 1));
+                }
+            }
+        }
+
+        self.induced_subgraph(|vertex| within.contains(vertex))
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    // like ego_network, but measuring by shortest path length instead
+    // of hops. the search doesn't go any further than it has to.
+    pub fn weighted_ego_network(&self, center: &V, radius: E) -> Graph<V, E> {
+        let mut within: HashSet<&V> = self
+            .dijkstra_by(center, |edge| edge.clone(), Some(radius))
+            .into_keys()
+            .collect();
+
+        if self.contains(center) {
+            within.insert(center);
+        }
+
+        self.induced_subgraph(|vertex| within.contains(vertex))
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::{Graph, Stop};
+use crate::limits::SearchLimits;
+use crate::metrics::Counters;
+
+// which way to go from every vertex to get to one goal, so any number
+// of agents heading for the same place can each just follow it from
+// wherever they are, without searching for themselves.
+#[derive(Debug, Clone)]
+pub struct FlowField<'g, V, E> {
+    goal: Option<&'g V>,
+    next: HashMap<&'g V, &'g V>,
+    distances: HashMap<&'g V, E>,
+}
+
+impl<'g, V: Hash + Eq, E> FlowField<'g, V, E> {
+    pub fn goal(&self) -> Option<&'g V> {
+        self.goal
+    }
+
+    // the neighbour to move to next from here. there isn't one at the
+    // goal, or anywhere the goal can't be reached from.
+    pub fn next_hop(&self, vertex: &V) -> Option<&'g V> {
+        self.next.get(vertex).cloned()
+    }
+
+    // how far there is left to go from here.
+    pub fn distance(&self, vertex: &V) -> Option<&E> {
+        self.distances.get(vertex)
+    }
+
+    // the whole path from here to the goal, including both ends.
+    pub fn path_from(&self, vertex: &V) -> Option<Vec<&'g V>> {
+        let (&first, _) = self.next.get_key_value(vertex)?;
+        let mut path = vec![first];
+        let mut current = first;
+
+        while let Some(&next) = self.next.get(current) {
+            path.push(next);
+            current = next;
+        }
+
+        Some(path)
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    // a single search backwards from the goal, following edges against
+    // their direction, finds the shortest path to it from everywhere at
+    // once. the vertex each one was reached from in that search is the
+    // one to go to next.
+    pub fn flow_field(&self, goal: &V) -> FlowField<'_, V, E> {
+        let mut next = HashMap::new();
+
+        let distances = match self.dijkstra_within(
+            goal,
+            |edge| edge.clone(),
+            Stop::Never,
+            true,
+            &SearchLimits::new(),
+            &mut Counters::default(),
+            &mut next,
+        ) {
+            Ok(distances) => distances,
+            Err(exceeded) => exceeded.partial,
+        };
+
+        FlowField {
+            goal: self.vertices.get(goal),
+            next,
+            distances,
+        }
+    }
+}
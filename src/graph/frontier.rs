@@ -0,0 +1,92 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+// something waiting in a search's priority queue. BinaryHeap wants a
+// total order and pops the largest first, so entries compare by their
+// distance alone, backwards, and any two distances that can't be
+// compared (like a nan) are treated as equal.
+pub struct Queued<W, T> {
+    pub dist: W,
+    pub item: T,
+}
+
+impl<W: PartialOrd, T> Ord for Queued<W, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .dist
+            .partial_cmp(&self.dist)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<W: PartialOrd, T> PartialOrd for Queued<W, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: PartialOrd, T> PartialEq for Queued<W, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: PartialOrd, T> Eq for Queued<W, T> {}
+
+// the provisional distances of a search over numbered vertices, for
+// the preprocessing steps that need dijkstra's algorithm with a little
+// more control than dijkstra_by gives. improving a distance just
+// queues it again, and the old entries are skipped when they come up.
+// the distances are kept by number, growing to fit the biggest one
+// that's been seen, which is much cheaper than hashing every vertex.
+pub struct Frontier<E> {
+    dists: Vec<Option<E>>,
+    queue: BinaryHeap<Queued<E, usize>>,
+}
+
+impl<E: PartialOrd + Clone> Frontier<E> {
+    pub fn new() -> Self {
+        Self {
+            dists: Vec::new(),
+            queue: BinaryHeap::new(),
+        }
+    }
+
+    // keeps the new distance if it's an improvement, and says whether
+    // it was.
+    pub fn improve(&mut self, vertex: usize, dist: E) -> bool {
+        if vertex >= self.dists.len() {
+            self.dists.resize(vertex + 1, None);
+        }
+
+        let better = match &self.dists[vertex] {
+            Some(current) => dist < *current,
+            None => true,
+        };
+
+        if better {
+            self.dists[vertex] = Some(dist.clone());
+            self.queue.push(Queued { dist, item: vertex });
+        }
+
+        better
+    }
+
+    pub fn pop(&mut self) -> Option<(usize, E)> {
+        while let Some(Queued { dist, item: vertex }) = self.queue.pop() {
+            let current = match &self.dists[vertex] {
+                Some(current) => current,
+                None => continue,
+            };
+
+            if *current < dist {
+                continue;
+            }
+
+            self.dists[vertex] = None;
+            return Some((vertex, dist));
+        }
+
+        None
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt::Display;
+use std::hash::Hash;
+
+use super::itinerary::Itinerary;
+use super::Graph;
+
+// vertex values that know where they are, as (longitude, latitude)
+// since that's the order geojson wants them in.
+pub trait Located {
+    fn coordinates(&self) -> (f64, f64);
+}
+
+fn position<V: Located>(vertex: &V) -> String {
+    let (x, y) = vertex.coordinates();
+    format!("[{},{}]", x, y)
+}
+
+fn string(text: &str) -> String {
+    let mut escaped = String::from("\"");
+
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+// edge values are written as numbers when they're written like one, so
+// that map styling can use them, and as strings otherwise.
+fn value<T: Display>(value: &T) -> String {
+    let text = value.to_string();
+
+    if is_number(&text) {
+        text
+    } else {
+        string(&text)
+    }
+}
+
+// whether the text follows json's grammar for numbers, which is a lot
+// stricter than what rust will parse: no "inf" or "NaN", no leading
+// zeros or plus sign, and digits on both sides of any decimal point.
+fn is_number(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+
+    let digits = |i: &mut usize| {
+        let start = *i;
+
+        while *i < bytes.len() && bytes[*i].is_ascii_digit() {
+            *i += 1;
+        }
+
+        *i - start
+    };
+
+    if bytes.first() == Some(&b'-') {
+        i += 1;
+    }
+
+    let whole = i;
+
+    match digits(&mut i) {
+        0 => return false,
+        1 => {}
+        _ if bytes[whole] == b'0' => return false,
+        _ => {}
+    }
+
+    if bytes.get(i) == Some(&b'.') {
+        i += 1;
+
+        if digits(&mut i) == 0 {
+            return false;
+        }
+    }
+
+    if let Some(b'e') | Some(b'E') = bytes.get(i) {
+        i += 1;
+
+        if let Some(b'+') | Some(b'-') = bytes.get(i) {
+            i += 1;
+        }
+
+        if digits(&mut i) == 0 {
+            return false;
+        }
+    }
+
+    i == bytes.len()
+}
+
+fn feature_collection(features: &[String]) -> String {
+    format!(
+        "{{\"type\":\"FeatureCollection\",\"features\":[{}]}}",
+        features.join(",")
+    )
+}
+
+impl<'g, V, E> Itinerary<'g, V, E>
+where
+    V: Located + Display,
+    E: Display,
+{
+    // one linestring per segment, annotated with its endpoints, the
+    // edge value and the running total, so a path found by any of the
+    // searches can be drawn over the graph itself.
+    pub fn to_geojson(&self) -> String {
+        let features: Vec<String> = self
+            .segments
+            .iter()
+            .map(|segment| {
+                format!(
+                    "{{\"type\":\"Feature\",\"geometry\":{{\"type\":\"LineString\",\"coordinates\":[{},{}]}},\
+                     \"properties\":{{\"from\":{},\"to\":{},\"value\":{},\"cumulative\":{}}}}}",
+                    position(segment.from),
+                    position(segment.to),
+                    string(&segment.from.to_string()),
+                    string(&segment.to.to_string()),
+                    value(segment.value),
+                    value(&segment.cumulative)
+                )
+            })
+            .collect();
+
+        feature_collection(&features)
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Located + Display,
+    E: Display,
+{
+    // every vertex as a point and every edge as a two point linestring
+    // carrying its value as the weight. loops are left out since they
+    // can't be drawn as a line.
+    pub fn to_geojson(&self) -> String {
+        let mut features: Vec<String> = self
+            .vertices
+            .iter()
+            .map(|vertex| {
+                format!(
+                    "{{\"type\":\"Feature\",\"geometry\":{{\"type\":\"Point\",\"coordinates\":{}}},\
+                     \"properties\":{{\"name\":{}}}}}",
+                    position(vertex),
+                    string(&vertex.to_string())
+                )
+            })
+            .collect();
+
+        for edge in self.edges.iter().filter(|edge| edge.v1 != edge.v2) {
+            features.push(format!(
+                "{{\"type\":\"Feature\",\"geometry\":{{\"type\":\"LineString\",\"coordinates\":[{},{}]}},\
+                 \"properties\":{{\"from\":{},\"to\":{},\"weight\":{}}}}}",
+                position(&edge.v1),
+                position(&edge.v2),
+                string(&edge.v1.to_string()),
+                string(&edge.v2.to_string()),
+                value(&edge.value)
+            ));
+        }
+
+        feature_collection(&features)
+    }
+}
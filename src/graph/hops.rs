@@ -0,0 +1,101 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use super::Graph;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // the number of edges on the shortest route from the source to
+    // each vertex it can reach, ignoring edge values entirely. it's a
+    // breadth-first search, so there's no need for weights of one.
+    pub fn hop_distances(&self, source: &V) -> HashMap<&V, usize> {
+        let adjacency = self.adjacency();
+        let mut hops: HashMap<&V, usize> = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        if let Some(source) = self.vertices.get(source) {
+            hops.insert(source, 0);
+            queue.push_back(source);
+        }
+
+        while let Some(vertex) = queue.pop_front() {
+            let next = hops[vertex] + 1;
+
+            for &neighbor in adjacency.get(vertex).into_iter().flatten() {
+                if !hops.contains_key(neighbor) {
+                    hops.insert(neighbor, next);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        // as with dijkstra_paths, the source itself isn't included.
+        hops.remove(source);
+        hops
+    }
+
+    // the same, between a single pair, stopping as soon as it gets there.
+    pub fn hop_distance(&self, source: &V, target: &V) -> Option<usize> {
+        if source == target || !self.contains(source) {
+            return None;
+        }
+
+        let adjacency = self.adjacency();
+        let mut hops: HashMap<&V, usize> = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        hops.insert(source, 0);
+        queue.push_back(source);
+
+        while let Some(vertex) = queue.pop_front() {
+            let next = hops[vertex] + 1;
+
+            for &neighbor in adjacency.get(vertex).into_iter().flatten() {
+                if neighbor == target {
+                    return Some(next);
+                }
+
+                if !hops.contains_key(neighbor) {
+                    hops.insert(neighbor, next);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        None
+    }
+
+    // where each vertex's edges lead, so a directed edge only shows up
+    // at the vertex it starts from.
+    fn adjacency(&self) -> HashMap<&V, Vec<&V>> {
+        let mut adjacency: HashMap<&V, Vec<&V>> = HashMap::new();
+
+        for edge in self.edges.iter() {
+            adjacency.entry(&edge.v1).or_default().push(&edge.v2);
+
+            if !edge.directed {
+                adjacency.entry(&edge.v2).or_default().push(&edge.v1);
+            }
+        }
+
+        adjacency
+    }
+}
+
+// an edge value for graphs whose edges are only there or not. there's
+// no sensible way to add these up, so instead of dijkstra's algorithm,
+// dijkstra_paths does a breadth-first search and measures in hops.
+// (graphs with () edges can use hop_distances directly.)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Unweighted;
+
+impl<V> Graph<V, Unweighted>
+where
+    V: Hash + Eq,
+{
+    pub fn dijkstra_paths(&self, source: &V) -> HashMap<&V, usize> {
+        self.hop_distances(source)
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::bitset::BitSet;
+use super::frontier::Frontier;
+use super::Graph;
+
+const NONE: usize = usize::MAX;
+
+// how the query search got to a vertex, so the concrete path can be
+// filled back in afterwards.
+#[derive(Clone, Copy)]
+enum Step {
+    // within the source's cluster, straight from the source.
+    FromSource,
+    // along an edge from another cluster.
+    Across(usize),
+    // along one of the stored paths through a cluster.
+    Through(usize, usize),
+    // within the target's cluster, from an entrance.
+    IntoTarget(usize),
+}
+
+// hierarchical pathfinding (hpa*): the vertices are split into
+// clusters, and every vertex with an edge to another cluster is an
+// entrance. the shortest paths between the entrances of each cluster,
+// staying inside it, are worked out up front, and together with the
+// edges between clusters they make a much smaller abstract graph. a
+// query only searches inside the source's and target's own clusters,
+// and then across the abstract graph, before filling in the stored
+// paths. since every border vertex is an entrance, this always finds
+// a shortest path, but it's only quick when borders are short compared
+// to the clusters, as they are on grids and road networks.
+pub struct ClusterHierarchy<'g, V, E> {
+    vertices: Vec<&'g V>,
+    index: HashMap<&'g V, usize>,
+    cluster: Vec<usize>,
+    out: Vec<Vec<(usize, E)>>,
+    into: Vec<Vec<(usize, E)>>,
+    // for each entrance, the other entrances of its cluster it can get
+    // to without leaving, with the distance and which stored path it is.
+    through: Vec<Vec<(usize, E, usize)>>,
+    paths: Vec<Vec<usize>>,
+}
+
+impl<'g, V, E> ClusterHierarchy<'g, V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    pub fn new<C, F>(graph: &'g Graph<V, E>, cluster_of: F) -> Self
+    where
+        C: Hash + Eq,
+        F: Fn(&V) -> C,
+    {
+        let vertices: Vec<&V> = graph.vertices.iter().collect();
+        let mut clusters: HashMap<C, usize> = HashMap::new();
+        let cluster = vertices
+            .iter()
+            .map(|&vertex| {
+                let next = clusters.len();
+                *clusters.entry(cluster_of(vertex)).or_insert(next)
+            })
+            .collect();
+
+        Self::build(graph, vertices, cluster)
+    }
+
+    // picks the clusters itself, by growing each one breadth first from
+    // a vertex that isn't in one yet until it has the given number of
+    // vertices (or can't grow any more), which keeps them in one piece.
+    pub fn with_cluster_size(graph: &'g Graph<V, E>, size: usize) -> Self {
+        let vertices: Vec<&V> = graph.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let mut cluster = vec![NONE; vertices.len()];
+        let mut count = 0;
+
+        for seed in 0..vertices.len() {
+            if cluster[seed] != NONE {
+                continue;
+            }
+
+            let mut members = 0;
+            let mut queue = VecDeque::new();
+            cluster[seed] = count;
+            queue.push_back(seed);
+
+            while let Some(vertex) = queue.pop_front() {
+                members += 1;
+
+                for edge in graph.incident_edges(vertices[vertex]) {
+                    for other in [&edge.v1, &edge.v2].iter() {
+                        let other = index[*other];
+
+                        if cluster[other] == NONE && members + queue.len() < size.max(1) {
+                            cluster[other] = count;
+                            queue.push_back(other);
+                        }
+                    }
+                }
+            }
+
+            count += 1;
+        }
+
+        Self::build(graph, vertices, cluster)
+    }
+
+    fn build(graph: &'g Graph<V, E>, vertices: Vec<&'g V>, cluster: Vec<usize>) -> Self {
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let mut out = vec![Vec::new(); vertices.len()];
+        let mut into = vec![Vec::new(); vertices.len()];
+
+        for edge in graph.edges.iter() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+
+            if a == b {
+                continue;
+            }
+
+            out[a].push((b, edge.value.clone()));
+            into[b].push((a, edge.value.clone()));
+
+            if !edge.directed {
+                out[b].push((a, edge.value.clone()));
+                into[a].push((b, edge.value.clone()));
+            }
+        }
+
+        let entrance: Vec<bool> = (0..vertices.len())
+            .map(|v| {
+                out[v]
+                    .iter()
+                    .chain(into[v].iter())
+                    .any(|(other, _)| cluster[*other] != cluster[v])
+            })
+            .collect();
+
+        let mut hierarchy = Self {
+            vertices,
+            index,
+            cluster,
+            out,
+            into,
+            through: Vec::new(),
+            paths: Vec::new(),
+        };
+
+        let mut through = vec![Vec::new(); hierarchy.vertices.len()];
+
+        for from in (0..hierarchy.vertices.len()).filter(|&v| entrance[v]) {
+            let (dist, previous) = hierarchy.local(from, false);
+
+            for to in (0..hierarchy.vertices.len()).filter(|&v| entrance[v]) {
+                let length = match &dist[to] {
+                    Some(length) if to != from => length.clone(),
+                    _ => continue,
+                };
+
+                let mut path = vec![to];
+                let mut current = to;
+
+                while previous[current] != from {
+                    current = previous[current];
+                    path.push(current);
+                }
+
+                path.reverse();
+                through[from].push((to, length, hierarchy.paths.len()));
+                hierarchy.paths.push(path);
+            }
+        }
+
+        hierarchy.through = through;
+        hierarchy
+    }
+
+    pub fn cluster_count(&self) -> usize {
+        self.cluster.iter().max().map_or(0, |&most| most + 1)
+    }
+
+    pub fn shortest_path(&self, source: &V, target: &V) -> Option<(Vec<&'g V>, E)> {
+        let (source, target) = (*self.index.get(source)?, *self.index.get(target)?);
+
+        if source == target {
+            return None;
+        }
+
+        let (from_source, before) = self.local(source, false);
+        let (to_target, after) = self.local(target, true);
+
+        let mut frontier = Frontier::new();
+        let mut settled = BitSet::new(self.vertices.len());
+        let mut came: Vec<Option<Step>> = vec![None; self.vertices.len()];
+        settled.insert(source);
+
+        let improve = |frontier: &mut Frontier<E>, came: &mut [Option<Step>], to, dist, step| {
+            if frontier.improve(to, dist) {
+                came[to] = Some(step);
+            }
+        };
+
+        // the source can get anywhere in its cluster directly, or leave
+        // it straight away if it's an entrance itself.
+        for (v, dist) in from_source.iter().enumerate() {
+            if let Some(dist) = dist {
+                improve(&mut frontier, &mut came, v, dist.clone(), Step::FromSource);
+            }
+        }
+
+        for (to, weight) in self.out[source].iter() {
+            if self.cluster[*to] != self.cluster[source] {
+                improve(
+                    &mut frontier,
+                    &mut came,
+                    *to,
+                    weight.clone(),
+                    Step::Across(source),
+                );
+            }
+        }
+
+        while let Some((vertex, dist)) = frontier.pop() {
+            settled.insert(vertex);
+
+            if vertex == target {
+                return Some((self.unfold(source, target, &came, &before, &after), dist));
+            }
+
+            let mut steps: Vec<(usize, E, Step)> = Vec::new();
+
+            for (to, weight) in self.out[vertex].iter() {
+                if self.cluster[*to] != self.cluster[vertex] {
+                    steps.push((*to, weight.clone(), Step::Across(vertex)));
+                }
+            }
+
+            for (to, length, path) in self.through[vertex].iter() {
+                steps.push((*to, length.clone(), Step::Through(vertex, *path)));
+            }
+
+            if self.cluster[vertex] == self.cluster[target] {
+                if let Some(length) = &to_target[vertex] {
+                    steps.push((target, length.clone(), Step::IntoTarget(vertex)));
+                }
+            }
+
+            for (to, length, step) in steps {
+                if !settled.contains(to) {
+                    improve(&mut frontier, &mut came, to, dist.clone() + length, step);
+                }
+            }
+        }
+
+        None
+    }
+
+    // follows the steps back from the target, filling each one in.
+    fn unfold(
+        &self,
+        source: usize,
+        target: usize,
+        came: &[Option<Step>],
+        before: &[usize],
+        after: &[usize],
+    ) -> Vec<&'g V> {
+        let mut backwards = vec![target];
+        let mut current = target;
+
+        while current != source {
+            current = match came[current].unwrap() {
+                Step::FromSource => {
+                    let mut v = current;
+
+                    while before[v] != source {
+                        v = before[v];
+                        backwards.push(v);
+                    }
+
+                    source
+                }
+                Step::Across(from) => from,
+                Step::Through(from, path) => {
+                    let path = &self.paths[path];
+                    backwards.extend(path[..path.len() - 1].iter().rev());
+                    from
+                }
+                Step::IntoTarget(from) => {
+                    // the search into the target went backwards, so
+                    // its steps lead forwards towards the target.
+                    let mut forwards = Vec::new();
+                    let mut v = from;
+
+                    while after[v] != target {
+                        v = after[v];
+                        forwards.push(v);
+                    }
+
+                    backwards.extend(forwards.into_iter().rev());
+                    from
+                }
+            };
+
+            backwards.push(current);
+        }
+
+        backwards.reverse();
+        backwards.into_iter().map(|v| self.vertices[v]).collect()
+    }
+
+    // dijkstra's algorithm from one vertex without leaving its cluster,
+    // giving every distance and the vertex each one was reached from.
+    // going backwards follows edges the wrong way, for distances to it.
+    fn local(&self, start: usize, backwards: bool) -> (Vec<Option<E>>, Vec<usize>) {
+        let arcs = if backwards { &self.into } else { &self.out };
+        let home = self.cluster[start];
+
+        let mut dist: Vec<Option<E>> = vec![None; self.vertices.len()];
+        let mut previous = vec![NONE; self.vertices.len()];
+        let mut settled = BitSet::new(self.vertices.len());
+        let mut frontier = Frontier::new();
+        settled.insert(start);
+
+        for (to, weight) in arcs[start].iter() {
+            if self.cluster[*to] == home && frontier.improve(*to, weight.clone()) {
+                previous[*to] = start;
+            }
+        }
+
+        while let Some((vertex, length)) = frontier.pop() {
+            settled.insert(vertex);
+
+            for (to, weight) in arcs[vertex].iter() {
+                if self.cluster[*to] == home
+                    && !settled.contains(*to)
+                    && frontier.improve(*to, length.clone() + weight.clone())
+                {
+                    previous[*to] = vertex;
+                }
+            }
+
+            dist[vertex] = Some(length);
+        }
+
+        (dist, previous)
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::Add;
+
+use super::bitset::BitSet;
+use super::frontier::Frontier;
+use super::Graph;
+use crate::cancel::{CancelToken, Cancelled};
+use crate::progress::Progress;
+
+// a 2-hop cover: every vertex gets a label of (hub, distance) pairs,
+// chosen so that for any two vertices, some hub on a shortest path
+// between them appears in both labels (or one is a hub of the other).
+// a query is then just a merge of two short sorted lists. with directed
+// edges, the distance to a hub isn't the distance from it, so each
+// vertex needs a second label of the hubs it can get to.
+pub struct HubLabeling<'g, V, E> {
+    rank: HashMap<&'g V, usize>,
+    // the distances from each hub.
+    from_hubs: Vec<Vec<(usize, E)>>,
+    // the distances to each hub, if they're any different.
+    to_hubs: Option<Vec<Vec<(usize, E)>>>,
+}
+
+impl<'g, V, E> HubLabeling<'g, V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    pub fn new(graph: &'g Graph<V, E>) -> Self {
+        Self::with_progress(graph, &mut ())
+    }
+
+    // pruned landmark labeling (akiba, iwata and yoshida). a pruned
+    // search is run from each vertex in turn, most important first,
+    // and it stops at any vertex whose distance is already covered by
+    // the labels so far. high degree vertices go first, since they sit
+    // on the most shortest paths, which keeps the later searches tiny.
+    // progress is reported after each vertex's search, though the
+    // early searches take much longer than the later ones.
+    pub fn with_progress<P: Progress>(graph: &'g Graph<V, E>, progress: &mut P) -> Self {
+        Self::cancellable(graph, progress, &CancelToken::new()).unwrap()
+    }
+
+    // checks for cancellation before each vertex's search.
+    pub fn cancellable<P: Progress>(
+        graph: &'g Graph<V, E>,
+        progress: &mut P,
+        cancel: &CancelToken,
+    ) -> Result<Self, Cancelled> {
+        let mut degree: HashMap<&V, usize> = HashMap::new();
+
+        for edge in graph.edges.iter() {
+            *degree.entry(&edge.v1).or_default() += 1;
+            *degree.entry(&edge.v2).or_default() += 1;
+        }
+
+        let mut vertices: Vec<&V> = graph.vertices.iter().collect();
+        vertices.sort_by_key(|&vertex| {
+            let mut hasher = DefaultHasher::new();
+            vertex.hash(&mut hasher);
+            let degree = degree.get(vertex).cloned().unwrap_or(0);
+            (std::cmp::Reverse(degree), hasher.finish())
+        });
+
+        let rank: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let directed = graph.edges.iter().any(|edge| edge.directed);
+        let mut out: Vec<Vec<(usize, E)>> = vec![Vec::new(); vertices.len()];
+        let mut into: Vec<Vec<(usize, E)>> = vec![Vec::new(); vertices.len()];
+
+        for edge in graph.edges.iter() {
+            let (a, b) = (rank[&edge.v1], rank[&edge.v2]);
+
+            if a == b {
+                continue;
+            }
+
+            out[a].push((b, edge.value.clone()));
+            into[b].push((a, edge.value.clone()));
+
+            if !edge.directed {
+                out[b].push((a, edge.value.clone()));
+                into[a].push((b, edge.value.clone()));
+            }
+        }
+
+        let mut from_hubs: Vec<Vec<(usize, E)>> = vec![Vec::new(); vertices.len()];
+        let mut to_hubs: Option<Vec<Vec<(usize, E)>>> = if directed {
+            Some(vec![Vec::new(); vertices.len()])
+        } else {
+            None
+        };
+        let mut settled = BitSet::new(vertices.len());
+
+        for root in 0..vertices.len() {
+            cancel.check()?;
+
+            match to_hubs.as_mut() {
+                Some(to_hubs) => {
+                    pruned_search(&out, root, &mut settled, &mut from_hubs, Some(to_hubs));
+                    pruned_search(&into, root, &mut settled, to_hubs, Some(&from_hubs));
+                }
+                None => pruned_search(&out, root, &mut settled, &mut from_hubs, None),
+            }
+
+            progress.on_progress(root + 1, vertices.len());
+        }
+
+        Ok(Self {
+            rank,
+            from_hubs,
+            to_hubs,
+        })
+    }
+
+    // as with dijkstra_paths, there's no distance from a vertex to
+    // itself, or to anything it can't reach.
+    pub fn distance(&self, a: &V, b: &V) -> Option<E> {
+        let (a, b) = (*self.rank.get(a)?, *self.rank.get(b)?);
+
+        if a == b {
+            return None;
+        }
+
+        let to_hubs = self.to_hubs.as_ref().unwrap_or(&self.from_hubs);
+        query(&to_hubs[a], &self.from_hubs[b], a, b)
+    }
+
+    // the average number of hubs per vertex, counting both labels on a
+    // directed graph, which is what the query time (and the memory use)
+    // depend on.
+    pub fn average_label_size(&self) -> f64 {
+        if self.from_hubs.is_empty() {
+            return 0.0;
+        }
+
+        let total: usize = self
+            .from_hubs
+            .iter()
+            .chain(self.to_hubs.iter().flatten())
+            .map(|label| label.len())
+            .sum();
+        total as f64 / self.from_hubs.len() as f64
+    }
+}
+
+// one root's pruned search over the given arcs, adding it as a hub to
+// the labels of everything it reaches that isn't covered already. the
+// other labels are the ones going the opposite way, which are the same
+// labels on an undirected graph.
+fn pruned_search<E>(
+    arcs: &[Vec<(usize, E)>],
+    root: usize,
+    settled: &mut BitSet,
+    labels: &mut [Vec<(usize, E)>],
+    other: Option<&[Vec<(usize, E)>]>,
+) where
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    // there's no zero for E, so the root's own entry is left implicit,
+    // and the search starts from its neighbours.
+    let mut frontier = Frontier::new();
+    let mut touched = vec![root];
+    settled.insert(root);
+
+    for (next, weight) in arcs[root].iter() {
+        frontier.improve(*next, weight.clone());
+    }
+
+    while let Some((vertex, dist)) = frontier.pop() {
+        settled.insert(vertex);
+        touched.push(vertex);
+
+        let known = match other {
+            Some(other) => query(&labels[vertex], &other[root], vertex, root),
+            None => query(&labels[vertex], &labels[root], vertex, root),
+        };
+
+        if known.is_some_and(|known| known <= dist) {
+            continue;
+        }
+
+        for (next, weight) in arcs[vertex].iter() {
+            if !settled.contains(*next) {
+                frontier.improve(*next, dist.clone() + weight.clone());
+            }
+        }
+
+        // roots are processed in rank order, so every label stays
+        // sorted by hub without any extra effort.
+        labels[vertex].push((root, dist));
+    }
+
+    for vertex in touched {
+        settled.remove(vertex);
+    }
+}
+
+// the shortest distance the labels know of between two vertices, given
+// a's distances to its hubs and b's distances from its hubs. either one
+// may be a hub of the other, which accounts for the entries that would
+// otherwise need a distance of zero.
+fn query<E>(first: &[(usize, E)], second: &[(usize, E)], a: usize, b: usize) -> Option<E>
+where
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    let mut best: Option<E> = None;
+
+    let mut offer = |dist: E| {
+        let shorter = match &best {
+            Some(current) => dist < *current,
+            None => true,
+        };
+
+        if shorter {
+            best = Some(dist);
+        }
+    };
+
+    if let Ok(i) = first.binary_search_by_key(&b, |&(hub, _)| hub) {
+        offer(first[i].1.clone());
+    }
+
+    if let Ok(j) = second.binary_search_by_key(&a, |&(hub, _)| hub) {
+        offer(second[j].1.clone());
+    }
+
+    let (mut i, mut j) = (0, 0);
+
+    while i < first.len() && j < second.len() {
+        let (hub_a, hub_b) = (first[i].0, second[j].0);
+
+        if hub_a < hub_b {
+            i += 1;
+        } else if hub_b < hub_a {
+            j += 1;
+        } else {
+            offer(first[i].1.clone() + second[j].1.clone());
+            i += 1;
+            j += 1;
+        }
+    }
+
+    best
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::chordal::{elimination_order, undirected};
+use super::Graph;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // whether the vertices could be intervals on a line, adjacent
+    // exactly when they overlap. as with is_chordal, only adjacency
+    // matters.
+    pub fn is_interval_graph(&self) -> bool {
+        self.interval_representation().is_some()
+    }
+
+    // an interval graph where the intervals can all be the same length
+    // (or equivalently, where none has to sit strictly inside another).
+    // those are the interval graphs without a claw: a vertex with three
+    // neighbours that aren't adjacent to each other.
+    pub fn is_unit_interval_graph(&self) -> bool {
+        if !self.is_interval_graph() {
+            return false;
+        }
+
+        let (_, adjacent) = undirected(self);
+
+        adjacent.iter().all(|around| {
+            let around: Vec<usize> = around.iter().cloned().collect();
+
+            !(0..around.len()).any(|i| {
+                (i + 1..around.len()).any(|j| {
+                    (j + 1..around.len()).any(|k| {
+                        let (x, y, z) = (around[i], around[j], around[k]);
+                        !adjacent[x].contains(&y)
+                            && !adjacent[x].contains(&z)
+                            && !adjacent[y].contains(&z)
+                    })
+                })
+            })
+        })
+    }
+
+    // closed intervals, one per vertex, that overlap exactly where the
+    // graph has an edge, or none if that can't be done. this uses
+    // gilmore and hoffman's characterisation: a graph is an interval
+    // graph when it's chordal and the pairs that aren't adjacent can be
+    // ordered transitively (as in "entirely to the left of"). finding
+    // that order takes o(n^3) time in the worst case, which is fine up
+    // to a few thousand vertices.
+    pub fn interval_representation(&self) -> Option<HashMap<&V, (usize, usize)>> {
+        let (vertices, adjacent) = undirected(self);
+        let count = vertices.len();
+
+        elimination_order(&adjacent)?;
+
+        // golumbic's algorithm for orienting the complement. orienting
+        // a to b forces a to c for any c next to a but not b (in what's
+        // left of the complement), and likewise at the other end. each
+        // such implication class gets oriented all at once and taken
+        // out, and the orientation fails if a class ends up forcing an
+        // edge both ways.
+        let mut remaining: Vec<Vec<bool>> = (0..count)
+            .map(|a| {
+                (0..count)
+                    .map(|b| a != b && !adjacent[a].contains(&b))
+                    .collect()
+            })
+            .collect();
+        let mut before = vec![vec![false; count]; count];
+        let mut class = vec![vec![0; count]; count];
+        let mut classes = 0;
+
+        for a in 0..count {
+            for b in 0..count {
+                if !remaining[a][b] {
+                    continue;
+                }
+
+                classes += 1;
+                class[a][b] = classes;
+                let mut members = vec![(a, b)];
+                let mut stack = vec![(a, b)];
+
+                while let Some((x, y)) = stack.pop() {
+                    if class[y][x] == classes {
+                        return None;
+                    }
+
+                    // the complement is symmetric, so only the rows for x
+                    // and y are needed.
+                    let rows = remaining[x].iter().zip(remaining[y].iter());
+
+                    for (z, (&from_x, &from_y)) in rows.enumerate() {
+                        let mut forced = Vec::new();
+
+                        if from_x && z != y && !from_y {
+                            forced.push((x, z));
+                        }
+
+                        if from_y && z != x && !from_x {
+                            forced.push((z, y));
+                        }
+
+                        for (p, q) in forced {
+                            if class[p][q] != classes {
+                                class[p][q] = classes;
+                                members.push((p, q));
+                                stack.push((p, q));
+                            }
+                        }
+                    }
+                }
+
+                for (x, y) in members {
+                    before[x][y] = true;
+                    remaining[x][y] = false;
+                    remaining[y][x] = false;
+                }
+            }
+        }
+
+        // with no holes, that order is an interval order, so the sets
+        // of vertices before each one are nested. each distinct set is
+        // a point on the line where some intervals start, and every
+        // vertex has to end just before the first point whose set
+        // includes it.
+        let earlier: Vec<usize> = (0..count)
+            .map(|v| (0..count).filter(|&u| before[u][v]).count())
+            .collect();
+        let mut points: Vec<usize> = earlier.clone();
+        points.sort_unstable();
+        points.dedup();
+
+        let start: Vec<usize> = earlier
+            .iter()
+            .map(|size| points.binary_search(size).unwrap())
+            .collect();
+
+        let intervals: Vec<(usize, usize)> = (0..count)
+            .map(|v| {
+                let end = (0..count)
+                    .filter(|&w| before[v][w])
+                    .map(|w| 2 * start[w] - 1)
+                    .min()
+                    .unwrap_or(2 * points.len() + 1);
+
+                (2 * start[v], end)
+            })
+            .collect();
+
+        // the theory says this always works, but it's cheap to make
+        // sure the intervals really do overlap in the right places.
+        for a in 0..count {
+            for b in a + 1..count {
+                let ((s, t), (u, w)) = (intervals[a], intervals[b]);
+                let overlap = s <= w && u <= t;
+
+                if overlap != adjacent[a].contains(&b) {
+                    return None;
+                }
+            }
+        }
+
+        Some(vertices.into_iter().zip(intervals).collect())
+    }
+}
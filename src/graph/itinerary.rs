@@ -0,0 +1,108 @@
+use std::fmt::{self, Display};
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::{Edge, Graph};
+use crate::error::GraphError;
+
+// one leg of a path, along with the value of the edge it follows
+// (which may carry more than just a cost) and the cost of the path up
+// to and including it.
+#[derive(Debug, Clone)]
+pub struct Segment<'g, V, E> {
+    pub from: &'g V,
+    pub to: &'g V,
+    pub value: &'g E,
+    pub cumulative: E,
+}
+
+#[derive(Debug, Clone)]
+pub struct Itinerary<'g, V, E> {
+    pub segments: Vec<Segment<'g, V, E>>,
+}
+
+impl<'g, V, E: Clone> Itinerary<'g, V, E> {
+    pub fn total(&self) -> E {
+        // describe_path never gives an itinerary without segments.
+        self.segments.last().unwrap().cumulative.clone()
+    }
+
+    pub fn vertices(&self) -> Vec<&'g V> {
+        let mut vertices = vec![self.segments[0].from];
+        vertices.extend(self.segments.iter().map(|segment| segment.to));
+        vertices
+    }
+}
+
+// one numbered line per segment, like "2. B -> D: 4 (10 so far)".
+impl<'g, V: Display, E: Display> Display for Itinerary<'g, V, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (step, segment) in self.segments.iter().enumerate() {
+            writeln!(
+                f,
+                "{}. {} -> {}: {} ({} so far)",
+                step + 1,
+                segment.from,
+                segment.to,
+                segment.value,
+                segment.cumulative
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    // turns a list of vertices (from one of the path searches, say)
+    // into its segments. where there are parallel edges, each segment
+    // follows the cheapest one, and directed edges only count the way
+    // they go.
+    pub fn describe_path<'g>(&'g self, path: &[&V]) -> Result<Itinerary<'g, V, E>, GraphError> {
+        if path.len() < 2 {
+            return Err(GraphError::InvalidPath("Path needs at least two vertices."));
+        }
+
+        let mut segments: Vec<Segment<V, E>> = Vec::new();
+
+        for pair in path.windows(2) {
+            let mut cheapest: Option<&Edge<V, E>> = None;
+
+            for edge in self.edges.iter() {
+                let better = match cheapest {
+                    Some(best) => edge.value < best.value,
+                    None => true,
+                };
+
+                if edge.leads(pair[0], pair[1]) && better {
+                    cheapest = Some(edge);
+                }
+            }
+
+            let edge = cheapest.ok_or(GraphError::EdgeNotFound)?;
+            let (from, to) = if &edge.v1 == pair[0] {
+                (&edge.v1, &edge.v2)
+            } else {
+                (&edge.v2, &edge.v1)
+            };
+
+            let cumulative = match segments.last() {
+                Some(previous) => previous.cumulative.clone() + edge.value.clone(),
+                None => edge.value.clone(),
+            };
+
+            segments.push(Segment {
+                from,
+                to,
+                value: &edge.value,
+                cumulative,
+            });
+        }
+
+        Ok(Itinerary { segments })
+    }
+}
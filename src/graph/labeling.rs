@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+use super::Graph;
+use crate::flow::FlowNetwork;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + Sub<Output = E> + PartialOrd + Copy + Default,
+{
+    pub fn binary_labeling<F>(&self, unary: F) -> (HashMap<&V, bool>, E)
+    where
+        F: Fn(&V) -> (E, E),
+    {
+        // the unary function gives the cost of labelling a vertex
+        // false and true respectively, while each edge value is the
+        // cost paid when its two endpoints end up with different
+        // labels. an energy like this is minimised exactly by a
+        // minimum cut, where the source side is labelled false.
+        let indices: HashMap<&V, usize> = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(index, vertex)| (vertex, index))
+            .collect();
+
+        let source = indices.len();
+        let sink = source + 1;
+        let mut network = FlowNetwork::new(indices.len() + 2);
+
+        for (&vertex, &index) in indices.iter() {
+            let (false_cost, true_cost) = unary(vertex);
+
+            // cutting the source arc puts the vertex on the sink side,
+            // and cutting the sink arc keeps it on the source side.
+            network.add_arc(source, index, true_cost);
+            network.add_arc(index, sink, false_cost);
+        }
+
+        for edge in self.edges.iter() {
+            let (v1, v2) = (indices[&edge.v1], indices[&edge.v2]);
+
+            network.add_arc(v1, v2, edge.value);
+            network.add_arc(v2, v1, edge.value);
+        }
+
+        let energy = network.max_flow(source, sink);
+        let source_side = network.min_cut(source);
+
+        let labels = indices
+            .into_iter()
+            .map(|(vertex, index)| (vertex, !source_side[index]))
+            .collect();
+
+        (labels, energy)
+    }
+}
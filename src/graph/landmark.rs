@@ -0,0 +1,233 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Sub};
+
+use super::Graph;
+use crate::cancel::{CancelToken, Cancelled};
+use crate::progress::Progress;
+
+// the exact distance from each of a few landmark vertices to every
+// other vertex. by the triangle inequality, d(a, b) is at most
+// d(a, l) + d(l, b) and at least d(l, b) - d(l, a) and d(a, l) - d(b, l)
+// for any landmark l, which gives cheap bounds on any distance after
+// only k searches. with directed edges, the distances to a landmark
+// aren't the same as the ones from it, so they take another k.
+pub struct LandmarkEmbedding<'g, V, E> {
+    landmarks: Vec<&'g V>,
+    distances: HashMap<&'g V, Vec<Option<E>>>,
+    // the distances to each landmark, if they're any different.
+    towards: Option<HashMap<&'g V, Vec<Option<E>>>>,
+}
+
+impl<'g, V, E> LandmarkEmbedding<'g, V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + Sub<Output = E> + PartialOrd + Clone,
+{
+    pub fn new(graph: &'g Graph<V, E>, k: usize) -> Self {
+        Self::with_progress(graph, k, &mut ())
+    }
+
+    // reports progress after each landmark's search.
+    pub fn with_progress<P: Progress>(graph: &'g Graph<V, E>, k: usize, progress: &mut P) -> Self {
+        Self::cancellable(graph, k, progress, &CancelToken::new()).unwrap()
+    }
+
+    // checks for cancellation before each landmark's search.
+    pub fn cancellable<P: Progress>(
+        graph: &'g Graph<V, E>,
+        k: usize,
+        progress: &mut P,
+        cancel: &CancelToken,
+    ) -> Result<Self, Cancelled> {
+        // sorting by a fixed hash keeps the choice of landmarks the
+        // same from one run to the next.
+        let mut vertices: Vec<&V> = graph.vertices.iter().collect();
+        vertices.sort_by_key(|&vertex| {
+            let mut hasher = DefaultHasher::new();
+            vertex.hash(&mut hasher);
+            hasher.finish()
+        });
+
+        let mut landmarks: Vec<&V> = Vec::new();
+        let mut distances: HashMap<&V, Vec<Option<E>>> = vertices
+            .iter()
+            .map(|&vertex| (vertex, Vec::new()))
+            .collect();
+        let mut towards = if graph.edges.iter().any(|edge| edge.directed) {
+            Some(distances.clone())
+        } else {
+            None
+        };
+
+        // landmarks are picked one at a time, each as far as possible
+        // from the ones before it. anything none of them can reach
+        // counts as being infinitely far away, so every component
+        // gets a landmark before any of them gets a second one.
+        let mut closest: HashMap<&V, Option<E>> =
+            vertices.iter().map(|&vertex| (vertex, None)).collect();
+
+        let wanted = k.min(vertices.len());
+
+        while landmarks.len() < wanted {
+            cancel.check()?;
+            let mut farthest: Option<&V> = None;
+
+            for &vertex in vertices.iter() {
+                if landmarks.contains(&vertex) {
+                    continue;
+                }
+
+                let further = match farthest {
+                    None => true,
+                    Some(best) => match (&closest[vertex], &closest[best]) {
+                        (None, Some(_)) => true,
+                        (Some(d), Some(best)) => d > best,
+                        _ => false,
+                    },
+                };
+
+                if further {
+                    farthest = Some(vertex);
+                }
+            }
+
+            let landmark = farthest.unwrap();
+            let reached = graph.dijkstra_paths(landmark);
+
+            for &vertex in vertices.iter() {
+                let dist = reached.get(vertex).cloned();
+
+                if let Some(d) = dist.clone() {
+                    let nearer = match &closest[vertex] {
+                        None => true,
+                        Some(current) => d < *current,
+                    };
+
+                    if nearer {
+                        closest.insert(vertex, Some(d));
+                    }
+                }
+
+                distances.get_mut(vertex).unwrap().push(dist);
+            }
+
+            if let Some(towards) = towards.as_mut() {
+                let reached = graph.dijkstra_paths_to(landmark);
+
+                for &vertex in vertices.iter() {
+                    let dist = reached.get(vertex).cloned();
+                    towards.get_mut(vertex).unwrap().push(dist);
+                }
+            }
+
+            landmarks.push(landmark);
+            progress.on_progress(landmarks.len(), wanted);
+        }
+
+        Ok(Self {
+            landmarks,
+            distances,
+            towards,
+        })
+    }
+
+    pub fn landmarks(&self) -> &[&'g V] {
+        &self.landmarks
+    }
+
+    // the exact distance when either end is a landmark, and otherwise
+    // the shortest route through one. as with dijkstra_paths, there's
+    // no distance from a vertex to itself, and none between vertices
+    // that no landmark can reach both of.
+    pub fn approx_distance(&self, a: &V, b: &V) -> Option<E> {
+        if a == b {
+            return None;
+        }
+
+        if let Some(exact) = self.exact(a, b) {
+            return exact;
+        }
+
+        let (to_a, from_b) = (self.towards().get(a)?, self.distances.get(b)?);
+        let mut best: Option<E> = None;
+
+        for (da, db) in to_a.iter().zip(from_b.iter()) {
+            if let (Some(da), Some(db)) = (da, db) {
+                let through = da.clone() + db.clone();
+
+                let better = match &best {
+                    None => true,
+                    Some(best) => through < *best,
+                };
+
+                if better {
+                    best = Some(through);
+                }
+            }
+        }
+
+        best
+    }
+
+    // the best lower bound the landmarks can give, which is useful as
+    // an a* heuristic since it never overestimates.
+    pub fn lower_bound(&self, a: &V, b: &V) -> Option<E> {
+        if a == b {
+            return None;
+        }
+
+        if let Some(exact) = self.exact(a, b) {
+            return exact;
+        }
+
+        let (from_a, from_b) = (self.distances.get(a)?, self.distances.get(b)?);
+        let (to_a, to_b) = (self.towards().get(a)?, self.towards().get(b)?);
+        let mut best: Option<E> = None;
+
+        let mut offer = |far: &Option<E>, near: &Option<E>| {
+            if let (Some(far), Some(near)) = (far, near) {
+                if far < near {
+                    return;
+                }
+
+                let gap = far.clone() - near.clone();
+
+                let better = match &best {
+                    None => true,
+                    Some(best) => gap > *best,
+                };
+
+                if better {
+                    best = Some(gap);
+                }
+            }
+        };
+
+        for i in 0..self.landmarks.len() {
+            offer(&from_b[i], &from_a[i]);
+            offer(&to_a[i], &to_b[i]);
+        }
+
+        best
+    }
+
+    fn towards(&self) -> &HashMap<&'g V, Vec<Option<E>>> {
+        self.towards.as_ref().unwrap_or(&self.distances)
+    }
+
+    // landmarks know their own distances exactly, so there's nothing
+    // to estimate if either end of the query is one of them.
+    fn exact(&self, a: &V, b: &V) -> Option<Option<E>> {
+        if let Some(i) = self.landmarks.iter().position(|&l| l == a) {
+            return Some(self.distances.get(b)?[i].clone());
+        }
+
+        if let Some(i) = self.landmarks.iter().position(|&l| l == b) {
+            return Some(self.towards().get(a)?[i].clone());
+        }
+
+        None
+    }
+}
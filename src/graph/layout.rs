@@ -0,0 +1,459 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use super::Graph;
+use crate::random::Rng;
+
+// positions are in layout units, with one unit between neighbouring
+// vertices in a layer and one unit between consecutive layers.
+pub type Positions<'g, V> = HashMap<&'g V, (f64, f64)>;
+
+// the number of up and down passes made while reordering layers.
+const ORDERING_SWEEPS: usize = 12;
+
+pub fn sugiyama<'g, V, E>(graph: &'g Graph<V, E>) -> Positions<'g, V>
+where
+    V: Hash + Eq,
+{
+    let hierarchy = hierarchy(graph);
+
+    hierarchy
+        .vertices
+        .iter()
+        .enumerate()
+        .map(|(node, &vertex)| (vertex, (hierarchy.x[node], hierarchy.layer[node] as f64)))
+        .collect()
+}
+
+// the sugiyama pipeline before it's boiled down to plain positions.
+// the real vertices are numbered first and the dummy nodes after them,
+// and each edge is kept as the chain of nodes it passes through, from
+// the upper endpoint down to the lower one.
+struct Hierarchy<'g, V> {
+    vertices: Vec<&'g V>,
+    layer: Vec<usize>,
+    x: Vec<f64>,
+    chains: Vec<(&'g V, &'g V, Vec<usize>)>,
+}
+
+fn hierarchy<'g, V, E>(graph: &'g Graph<V, E>) -> Hierarchy<'g, V>
+where
+    V: Hash + Eq,
+{
+    // number the vertices, then point every edge from the lower number
+    // to the higher one, which gives an acyclic orientation.
+    let order = if graph.edges.iter().any(|edge| edge.directed) {
+        depth_first_order(graph)
+    } else {
+        breadth_first_order(graph)
+    };
+
+    let index: HashMap<&V, usize> = order
+        .iter()
+        .enumerate()
+        .map(|(i, &vertex)| (vertex, i))
+        .collect();
+
+    let oriented: Vec<(&V, &V, usize, usize)> = graph
+        .edges
+        .iter()
+        .map(|edge| (&edge.v1, &edge.v2, index[&edge.v1], index[&edge.v2]))
+        .filter(|&(_, _, a, b)| a != b)
+        .map(|(v1, v2, a, b)| (v1, v2, a.min(b), a.max(b)))
+        .collect();
+
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); order.len()];
+
+    for &(_, _, upper, lower) in oriented.iter() {
+        successors[upper].push(lower);
+    }
+
+    // longest-path layering. the numbering is already a topological
+    // order for the orientation, so one pass over it is enough.
+    let mut layer = vec![0; order.len()];
+
+    for vertex in 0..order.len() {
+        for &next in successors[vertex].iter() {
+            layer[next] = layer[next].max(layer[vertex] + 1);
+        }
+    }
+
+    // edges spanning more than one layer get broken up by dummy nodes,
+    // so that every link is between adjacent layers.
+    let mut node_layer = layer.clone();
+    let mut chains = Vec::new();
+
+    for (v1, v2, upper, lower) in oriented {
+        let mut chain = vec![upper];
+
+        for dummy_layer in layer[upper] + 1..layer[lower] {
+            node_layer.push(dummy_layer);
+            chain.push(node_layer.len() - 1);
+        }
+
+        chain.push(lower);
+        chains.push((v1, v2, chain));
+    }
+
+    let layer_count = node_layer.iter().max().map_or(0, |&deepest| deepest + 1);
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); layer_count];
+    let mut above: Vec<Vec<usize>> = vec![Vec::new(); node_layer.len()];
+    let mut below: Vec<Vec<usize>> = vec![Vec::new(); node_layer.len()];
+
+    for (node, &depth) in node_layer.iter().enumerate() {
+        layers[depth].push(node);
+    }
+
+    for (_, _, chain) in chains.iter() {
+        for link in chain.windows(2) {
+            below[link[0]].push(link[1]);
+            above[link[1]].push(link[0]);
+        }
+    }
+
+    // crossing minimisation with the barycenter heuristic, sweeping
+    // down and then up the layers so both neighbours get a say.
+    let mut position = vec![0.0; node_layer.len()];
+    update_positions(&layers, &mut position);
+
+    for sweep in 0..ORDERING_SWEEPS {
+        let downward = sweep % 2 == 0;
+        let (range, reference): (Vec<usize>, &Vec<Vec<usize>>) = if downward {
+            ((1..layer_count).collect(), &above)
+        } else {
+            ((0..layer_count.saturating_sub(1)).rev().collect(), &below)
+        };
+
+        for depth in range {
+            let barycenter = |node: usize| {
+                let adjacent = &reference[node];
+
+                if adjacent.is_empty() {
+                    position[node]
+                } else {
+                    adjacent.iter().map(|&n| position[n]).sum::<f64>() / adjacent.len() as f64
+                }
+            };
+
+            let mut keyed: Vec<(f64, usize)> =
+                layers[depth].iter().map(|&n| (barycenter(n), n)).collect();
+            keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            layers[depth] = keyed.into_iter().map(|(_, node)| node).collect();
+            update_positions(&layers, &mut position);
+        }
+    }
+
+    // coordinate assignment. start from the ordering, then pull each
+    // node towards the average of its upper neighbours, pushing the
+    // rest of the layer along whenever two nodes get too close.
+    let mut x: Vec<f64> = position.clone();
+
+    for nodes in layers.iter().skip(1) {
+        let mut previous: Option<f64> = None;
+
+        for &node in nodes.iter() {
+            let desired = if above[node].is_empty() {
+                x[node]
+            } else {
+                above[node].iter().map(|&n| x[n]).sum::<f64>() / above[node].len() as f64
+            };
+
+            let placed = match previous {
+                Some(left) if desired < left + 1.0 => left + 1.0,
+                _ => desired,
+            };
+
+            x[node] = placed;
+            previous = Some(placed);
+        }
+    }
+
+    Hierarchy {
+        vertices: order,
+        layer: node_layer,
+        x,
+        chains,
+    }
+}
+
+// breadth-first order, one component at a time. for an undirected
+// graph, this is all the sugiyama cycle removal step needs, and it
+// keeps the layers short.
+fn breadth_first_order<V: Hash + Eq, E>(graph: &Graph<V, E>) -> Vec<&V> {
+    let mut order: Vec<&V> = Vec::new();
+    let mut seen: HashSet<&V> = HashSet::new();
+
+    for root in graph.vertices.iter() {
+        if !seen.insert(root) {
+            continue;
+        }
+
+        let mut queue = VecDeque::new();
+        order.push(root);
+        queue.push_back(root);
+
+        while let Some(vertex) = queue.pop_front() {
+            for (neighbor, _) in graph.neighbors(vertex) {
+                if seen.insert(neighbor) {
+                    order.push(neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    order
+}
+
+// reverse postorder of a depth-first search along the edges' own
+// directions. every directed edge then points down the layers, except
+// for the back edges closing a cycle, which get turned around as in
+// the usual depth-first cycle removal.
+fn depth_first_order<V: Hash + Eq, E>(graph: &Graph<V, E>) -> Vec<&V> {
+    let mut finished: Vec<&V> = Vec::new();
+    let mut seen: HashSet<&V> = HashSet::new();
+
+    for root in graph.vertices.iter() {
+        if !seen.insert(root) {
+            continue;
+        }
+
+        let mut stack = vec![(root, graph.neighbors(root).into_iter())];
+
+        while let Some((vertex, rest)) = stack.last_mut() {
+            let vertex = *vertex;
+
+            match rest.next() {
+                Some((next, _)) => {
+                    if seen.insert(next) {
+                        stack.push((next, graph.neighbors(next).into_iter()));
+                    }
+                }
+                None => {
+                    finished.push(vertex);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    finished.reverse();
+    finished
+}
+
+// lay each layer out left to right, centred on zero.
+fn update_positions(layers: &[Vec<usize>], position: &mut [f64]) {
+    for layer in layers.iter() {
+        let middle = (layer.len() as f64 - 1.0) / 2.0;
+
+        for (slot, &node) in layer.iter().enumerate() {
+            position[node] = slot as f64 - middle;
+        }
+    }
+}
+
+pub fn force_directed<'g, V, E, R>(
+    graph: &'g Graph<V, E>,
+    iterations: usize,
+    initial: Option<&Positions<'g, V>>,
+    rng: &mut R,
+) -> Positions<'g, V>
+where
+    V: Hash + Eq,
+    R: Rng + ?Sized,
+{
+    // fruchterman and reingold's spring embedder. every pair of
+    // vertices repels, while edges pull their endpoints together, and
+    // the distance a vertex may move shrinks as the layout "cools".
+    // the ideal edge length k is one unit, on a square whose area is
+    // one unit per vertex.
+    let mut vertices: Vec<&V> = graph.vertices.iter().collect();
+
+    // hash set order changes from run to run, which would hand out
+    // the random numbers differently each time. sorting by a fixed
+    // hash means a seeded rng always gives the same layout.
+    vertices.sort_by_key(|&vertex| {
+        let mut hasher = DefaultHasher::new();
+        vertex.hash(&mut hasher);
+        hasher.finish()
+    });
+
+    let index: HashMap<&V, usize> = vertices
+        .iter()
+        .enumerate()
+        .map(|(i, &vertex)| (vertex, i))
+        .collect();
+
+    let side = (vertices.len() as f64).sqrt().max(1.0);
+    let mut position: Vec<(f64, f64)> = vertices
+        .iter()
+        .map(|vertex| {
+            let random = (rng.next_f64() * side, rng.next_f64() * side);
+            initial
+                .and_then(|p| p.get(vertex))
+                .cloned()
+                .unwrap_or(random)
+        })
+        .collect();
+
+    let links: Vec<(usize, usize)> = graph
+        .edges
+        .iter()
+        .map(|edge| (index[&edge.v1], index[&edge.v2]))
+        .filter(|(a, b)| a != b)
+        .collect();
+
+    let mut temperature = side / 10.0;
+    let cooling = temperature / (iterations.max(1) as f64);
+
+    for _ in 0..iterations {
+        let mut displacement = vec![(0.0, 0.0); vertices.len()];
+
+        for a in 0..vertices.len() {
+            for b in a + 1..vertices.len() {
+                let (dx, dy, distance) = separation(position[a], position[b]);
+                let force = 1.0 / distance;
+
+                displacement[a].0 += dx / distance * force;
+                displacement[a].1 += dy / distance * force;
+                displacement[b].0 -= dx / distance * force;
+                displacement[b].1 -= dy / distance * force;
+            }
+        }
+
+        for &(a, b) in links.iter() {
+            let (dx, dy, distance) = separation(position[a], position[b]);
+            let force = distance * distance;
+
+            displacement[a].0 -= dx / distance * force;
+            displacement[a].1 -= dy / distance * force;
+            displacement[b].0 += dx / distance * force;
+            displacement[b].1 += dy / distance * force;
+        }
+
+        for (point, (dx, dy)) in position.iter_mut().zip(displacement) {
+            let length = (dx * dx + dy * dy).sqrt();
+
+            if length > 0.0 {
+                let step = length.min(temperature);
+                point.0 += dx / length * step;
+                point.1 += dy / length * step;
+            }
+        }
+
+        temperature -= cooling;
+    }
+
+    vertices.into_iter().zip(position).collect()
+}
+
+// the offset from b to a and its length. coincident points are
+// treated as being a tiny distance apart so they can still separate.
+fn separation(a: (f64, f64), b: (f64, f64)) -> (f64, f64, f64) {
+    let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+    let distance = (dx * dx + dy * dy).sqrt();
+
+    if distance > 1e-9 {
+        (dx, dy, distance)
+    } else {
+        (1e-9, 0.0, 1e-9)
+    }
+}
+
+// vertices sit on integer grid points, and every edge is drawn as a
+// sequence of horizontal and vertical segments through its bends.
+#[derive(Debug)]
+pub struct OrthogonalLayout<'g, V> {
+    pub positions: HashMap<&'g V, (i64, i64)>,
+    pub routes: Vec<Route<'g, V>>,
+}
+
+// the two endpoints of an edge and the points its drawing runs through,
+// starting from whichever endpoint is in the upper row.
+pub type Route<'g, V> = (&'g V, &'g V, Vec<(f64, f64)>);
+
+pub fn orthogonal<'g, V, E>(graph: &'g Graph<V, E>) -> OrthogonalLayout<'g, V>
+where
+    V: Hash + Eq,
+{
+    // there's no planar embedding to work from, so this builds on the
+    // layered layout instead: its crossing minimisation does the job
+    // of keeping schematics tidy, although crossings can't be ruled
+    // out like they could be with a proper planar embedding.
+    let hierarchy = hierarchy(graph);
+    let mut rows: HashMap<usize, Vec<usize>> = HashMap::new();
+
+    for (node, &row) in hierarchy.layer.iter().enumerate() {
+        rows.entry(row).or_default().push(node);
+    }
+
+    // snap each row onto whole columns, keeping its left to right
+    // order and making sure no two nodes share a grid point. dummy
+    // nodes get grid points too, and become the bends of long edges.
+    let mut grid = vec![(0, 0); hierarchy.layer.len()];
+
+    for (row, mut members) in rows {
+        members.sort_by(|&a, &b| hierarchy.x[a].total_cmp(&hierarchy.x[b]));
+        let mut previous: Option<i64> = None;
+
+        for node in members {
+            let rounded = hierarchy.x[node].round() as i64;
+            let column = match previous {
+                Some(left) if rounded <= left => left + 1,
+                _ => rounded,
+            };
+
+            grid[node] = (column, row as i64);
+            previous = Some(column);
+        }
+    }
+
+    // between two adjacent rows, an edge leaves its upper node going
+    // down, crosses over in the gap between the rows and then drops
+    // into the lower node. the gaps never contain any vertices, so
+    // edges can't run through one, and every edge crossing over in a
+    // gap gets a track of its own there, so none of them overlap.
+    let point = |node: usize| (grid[node].0 as f64, grid[node].1 as f64);
+    let crosses = |link: &[usize]| grid[link[0]].0 != grid[link[1]].0;
+    let mut tracks: HashMap<i64, (usize, usize)> = HashMap::new();
+
+    for (_, _, chain) in hierarchy.chains.iter() {
+        for link in chain.windows(2).filter(|link| crosses(link)) {
+            tracks.entry(grid[link[0]].1).or_default().1 += 1;
+        }
+    }
+
+    let mut routes = Vec::new();
+
+    for (v1, v2, chain) in hierarchy.chains.iter() {
+        let mut points = vec![point(chain[0])];
+
+        for link in chain.windows(2) {
+            let (upper, lower) = (point(link[0]), point(link[1]));
+
+            if crosses(link) {
+                let (used, total) = tracks.get_mut(&grid[link[0]].1).unwrap();
+                *used += 1;
+
+                let gap = upper.1 + *used as f64 / (*total + 1) as f64;
+                points.push((upper.0, gap));
+                points.push((lower.0, gap));
+            }
+
+            points.push(lower);
+        }
+
+        routes.push((*v1, *v2, points));
+    }
+
+    let positions = hierarchy
+        .vertices
+        .iter()
+        .enumerate()
+        .map(|(node, &vertex)| (vertex, grid[node]))
+        .collect();
+
+    OrthogonalLayout { positions, routes }
+}
@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use super::{Edge, Graph};
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+{
+    // each vertex of the line graph is an edge of this one, given as
+    // its position in insertion order (which keeps parallel edges
+    // apart) along with its endpoints. two undirected edges are joined
+    // when they share an endpoint, valued by combining the two edges.
+    // otherwise the link is directed, going from one edge to another
+    // when the first can be followed into a vertex and the second out
+    // of it, so a directed graph gives its directed line graph, with a
+    // link from each edge to every edge that starts where it ends.
+    pub fn line_graph<W, F>(&self, combine: F) -> Graph<(usize, V, V), W>
+    where
+        F: Fn(&E, &E) -> W,
+    {
+        let vertex = |index: usize| {
+            let edge = &self.edges[index];
+            (index, edge.v1.clone(), edge.v2.clone())
+        };
+
+        let vertices: HashSet<(usize, V, V)> = (0..self.edges.len()).map(vertex).collect();
+        let mut edges = Vec::new();
+
+        for (index, edge) in self.edges.iter().enumerate() {
+            let mut others: Vec<usize> = self.incident[&edge.v1]
+                .iter()
+                .chain(self.incident[&edge.v2].iter())
+                .cloned()
+                .filter(|&other| other > index)
+                .collect();
+
+            // parallel edges meet at both ends, but only get one link
+            // each way.
+            others.sort_unstable();
+            others.dedup();
+
+            for other in others {
+                let next = &self.edges[other];
+
+                if !edge.directed && !next.directed {
+                    let value = combine(&edge.value, &next.value);
+                    edges.push(Edge::new(vertex(index), vertex(other), value));
+                    continue;
+                }
+
+                if follows(edge, next) {
+                    let value = combine(&edge.value, &next.value);
+                    edges.push(Edge::directed(vertex(index), vertex(other), value));
+                }
+
+                if follows(next, edge) {
+                    let value = combine(&next.value, &edge.value);
+                    edges.push(Edge::directed(vertex(other), vertex(index), value));
+                }
+            }
+        }
+
+        Graph::from_parts(vertices, edges)
+    }
+}
+
+// whether the second edge can be taken straight after the first, which
+// for an undirected edge could be from either end.
+fn follows<V, E>(first: &Edge<V, E>, second: &Edge<V, E>) -> bool
+where
+    V: Hash + Eq,
+{
+    let into = |vertex: &V| first.v2 == *vertex || (!first.directed && first.v1 == *vertex);
+    into(&second.v1) || (!second.directed && into(&second.v2))
+}
@@ -0,0 +1,200 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use super::frontier::Frontier;
+use super::Graph;
+use crate::random::Rng;
+
+// numbered vertices, with each one's neighbours, the edge weights as
+// floats, and which edge each step uses.
+type Adjacency = Vec<Vec<(usize, f64, usize)>>;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Into<f64> + Clone,
+{
+    // a spanning tree (or forest, if the graph is disconnected) where
+    // the path between the ends of an edge is, on average, not much
+    // longer than the edge itself. a shortest path tree or an mst can do
+    // badly at this, such as on a grid, where either can leave plenty
+    // of neighbours a long way round from each other.
+    //
+    // this is the star decomposition of elkin, emek, spielman and teng:
+    // a ball of random radius is carved out around the centre, the rest
+    // is split into smaller balls grown from where a shortest path
+    // leaves the ones before, and each ball is solved the same way.
+    // each ball joins the tree by the edge that first reached it. the
+    // radii halve at every level, which keeps every edge's expected
+    // stretch polylogarithmic. edges are treated as two-way here.
+    pub fn low_stretch_spanning_tree<R: Rng + ?Sized>(&self, rng: &mut R) -> Graph<V, E> {
+        let mut vertices: Vec<&V> = self.vertices.iter().collect();
+        vertices.sort_by_key(|&vertex| {
+            let mut hasher = DefaultHasher::new();
+            vertex.hash(&mut hasher);
+            hasher.finish()
+        });
+
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let mut adjacent: Adjacency = vec![Vec::new(); vertices.len()];
+
+        for (i, edge) in self.edges.iter().enumerate() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+            let weight: f64 = edge.value.clone().into();
+
+            if a != b {
+                adjacent[a].push((b, weight, i));
+                adjacent[b].push((a, weight, i));
+            }
+        }
+
+        // every vertex starts out in region 0, which is split up into
+        // connected pieces, each then decomposed on its own.
+        let mut region = vec![0; vertices.len()];
+        let mut regions = 1;
+        let mut in_tree = vec![false; self.edges.len()];
+
+        for root in 0..vertices.len() {
+            if region[root] != 0 {
+                continue;
+            }
+
+            let component: Vec<usize> = search(&adjacent, &region, root, f64::INFINITY)
+                .into_iter()
+                .map(|(vertex, _, _)| vertex)
+                .collect();
+
+            for &vertex in component.iter() {
+                region[vertex] = regions;
+            }
+
+            regions += 1;
+            decompose(
+                &adjacent,
+                &mut region,
+                &mut regions,
+                root,
+                &mut in_tree,
+                &mut *rng,
+            );
+        }
+
+        let edges = self
+            .edges
+            .iter()
+            .zip(in_tree)
+            .filter(|(_, kept)| *kept)
+            .map(|(edge, _)| edge.clone())
+            .collect();
+
+        Graph::from_parts(self.vertices.clone(), edges)
+    }
+}
+
+// splits the center's region into balls, joins them up, and carries on
+// inside each ball.
+fn decompose<R: Rng + ?Sized>(
+    adjacent: &Adjacency,
+    region: &mut [usize],
+    regions: &mut usize,
+    center: usize,
+    in_tree: &mut [bool],
+    rng: &mut R,
+) {
+    let reached = search(adjacent, region, center, f64::INFINITY);
+    let radius = reached.iter().map(|&(_, dist, _)| dist).fold(0.0, f64::max);
+
+    // small enough (or close enough together) that the shortest path
+    // tree will do.
+    if reached.len() <= 2 || radius <= 0.0 {
+        for &(_, _, via) in reached.iter() {
+            if let Some(edge) = via {
+                in_tree[edge] = true;
+            }
+        }
+
+        return;
+    }
+
+    let home = region[center];
+    let mut balls = Vec::new();
+
+    let central = *regions;
+    *regions += 1;
+    let carved = radius * (0.25 + 0.25 * rng.next_f64());
+
+    for &(vertex, dist, _) in reached.iter() {
+        if dist <= carved {
+            region[vertex] = central;
+        }
+    }
+
+    balls.push(center);
+
+    // the rest are taken in the order the search settled them, so the
+    // vertex each one was reached from is already in some ball.
+    for &(vertex, _, via) in reached.iter() {
+        if region[vertex] != home {
+            continue;
+        }
+
+        in_tree[via.unwrap()] = true;
+
+        let ball = *regions;
+        *regions += 1;
+
+        for (inside, _, _) in search(adjacent, region, vertex, radius * 0.25 * rng.next_f64()) {
+            region[inside] = ball;
+        }
+
+        balls.push(vertex);
+    }
+
+    for center in balls {
+        decompose(adjacent, region, regions, center, in_tree, rng);
+    }
+}
+
+// dijkstra's algorithm within the source's region, as far as the
+// radius. gives the vertices in the order they were settled, each
+// with its distance and the edge its shortest path arrived by.
+fn search(
+    adjacent: &Adjacency,
+    region: &[usize],
+    source: usize,
+    radius: f64,
+) -> Vec<(usize, f64, Option<usize>)> {
+    let home = region[source];
+    let mut frontier = Frontier::new();
+    let mut via: HashMap<usize, usize> = HashMap::new();
+    let mut settled: HashSet<usize> = HashSet::new();
+    let mut order = Vec::new();
+
+    frontier.improve(source, 0.0);
+
+    while let Some((vertex, dist)) = frontier.pop() {
+        if dist > radius {
+            break;
+        }
+
+        settled.insert(vertex);
+        order.push((vertex, dist, via.get(&vertex).cloned()));
+
+        for &(next, weight, edge) in adjacent[vertex].iter() {
+            if region[next] == home
+                && !settled.contains(&next)
+                && frontier.improve(next, dist + weight)
+            {
+                via.insert(next, edge);
+            }
+        }
+    }
+
+    order
+}
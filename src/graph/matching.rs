@@ -0,0 +1,249 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use super::Graph;
+
+const NONE: usize = usize::MAX;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // as many edges as possible, without any vertex having more than
+    // its capacity of them. each edge is either in or out, though
+    // parallel edges can both be. edges are treated as two-way, and
+    // loops are never used.
+    //
+    // this turns it into an ordinary matching problem in a bigger
+    // graph (tutte's reduction): every vertex is copied as many times as
+    // its capacity, and every edge becomes a path of two new vertices,
+    // a and b, with a joined to all the copies of one end and b to all
+    // the copies of the other. a maximum matching can always match a
+    // with b, so using the edge (matching a and b to copies instead)
+    // gains exactly one. edmonds' blossom algorithm then finds the
+    // maximum matching, which takes cubic time in the size of the
+    // bigger graph, so it's good for a few thousand edges.
+    pub fn b_matching<F>(&self, capacity: F) -> Vec<(&V, &V, &E)>
+    where
+        F: Fn(&V) -> usize,
+    {
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let ends: Vec<(usize, usize)> = self
+            .edges
+            .iter()
+            .map(|edge| (index[&edge.v1], index[&edge.v2]))
+            .collect();
+
+        let mut degree = vec![0; vertices.len()];
+        for &(a, b) in ends.iter().filter(|(a, b)| a != b) {
+            degree[a] += 1;
+            degree[b] += 1;
+        }
+
+        // nobody can use more copies than they have edges.
+        let mut copies: Vec<Vec<usize>> = Vec::with_capacity(vertices.len());
+        let mut nodes = 0;
+
+        for (v, &vertex) in vertices.iter().enumerate() {
+            let count = capacity(vertex).min(degree[v]);
+            copies.push((nodes..nodes + count).collect());
+            nodes += count;
+        }
+
+        let mut adjacent: Vec<Vec<usize>> = vec![Vec::new(); nodes];
+        let mut gadgets = Vec::new();
+
+        for (i, &(a, b)) in ends.iter().enumerate() {
+            if a == b || copies[a].is_empty() || copies[b].is_empty() {
+                continue;
+            }
+
+            let (near_a, near_b) = (adjacent.len(), adjacent.len() + 1);
+            adjacent.push(vec![near_b]);
+            adjacent.push(vec![near_a]);
+
+            for &(end, near) in [(a, near_a), (b, near_b)].iter() {
+                for &copy in copies[end].iter() {
+                    adjacent[copy].push(near);
+                    adjacent[near].push(copy);
+                }
+            }
+
+            gadgets.push((i, near_a, near_b));
+        }
+
+        let mate = maximum_matching(&adjacent);
+
+        gadgets
+            .into_iter()
+            .filter(|&(_, near_a, near_b)| mate[near_a] < nodes && mate[near_b] < nodes)
+            .map(|(i, _, _)| {
+                let edge = &self.edges[i];
+                (&edge.v1, &edge.v2, &edge.value)
+            })
+            .collect()
+    }
+
+    // as many edges as possible with no two touching, which is the same
+    // thing with every capacity being one.
+    pub fn maximum_matching(&self) -> Vec<(&V, &V, &E)> {
+        self.b_matching(|_| 1)
+    }
+}
+
+// edmonds' blossom algorithm, giving each node's partner. augmenting
+// paths are searched for breadth first from each unmatched node, and an
+// odd cycle (a blossom) is shrunk to its base whenever one turns up.
+pub(super) fn maximum_matching(adjacent: &[Vec<usize>]) -> Vec<usize> {
+    let count = adjacent.len();
+    let mut mate = vec![NONE; count];
+
+    // a greedy start leaves far fewer paths to find.
+    for v in 0..count {
+        if mate[v] == NONE {
+            if let Some(&u) = adjacent[v].iter().find(|&&u| mate[u] == NONE) {
+                mate[v] = u;
+                mate[u] = v;
+            }
+        }
+    }
+
+    for root in 0..count {
+        if mate[root] != NONE {
+            continue;
+        }
+
+        let mut search = Search::new(count, root);
+
+        if let Some(end) = search.augmenting_path(adjacent, &mate) {
+            // flipping every edge along the path from its far end.
+            let mut v = end;
+
+            while v != NONE {
+                let before = search.parent[v];
+                let next = mate[before];
+                mate[v] = before;
+                mate[before] = v;
+                v = next;
+            }
+        }
+    }
+
+    mate
+}
+
+struct Search {
+    root: usize,
+    parent: Vec<usize>,
+    base: Vec<usize>,
+    used: Vec<bool>,
+    queue: VecDeque<usize>,
+}
+
+impl Search {
+    fn new(count: usize, root: usize) -> Self {
+        let mut used = vec![false; count];
+        used[root] = true;
+
+        Self {
+            root,
+            parent: vec![NONE; count],
+            base: (0..count).collect(),
+            used,
+            queue: vec![root].into(),
+        }
+    }
+
+    fn augmenting_path(&mut self, adjacent: &[Vec<usize>], mate: &[usize]) -> Option<usize> {
+        while let Some(v) = self.queue.pop_front() {
+            for &to in adjacent[v].iter() {
+                if self.base[v] == self.base[to] || mate[v] == to {
+                    continue;
+                }
+
+                if to == self.root || (mate[to] != NONE && self.parent[mate[to]] != NONE) {
+                    self.shrink(v, to, mate);
+                } else if self.parent[to] == NONE {
+                    self.parent[to] = v;
+
+                    if mate[to] == NONE {
+                        return Some(to);
+                    }
+
+                    self.used[mate[to]] = true;
+                    self.queue.push_back(mate[to]);
+                }
+            }
+        }
+
+        None
+    }
+
+    // v and to are both even in the search tree, so the edge between
+    // them closes an odd cycle, which becomes a single node at its base.
+    fn shrink(&mut self, v: usize, to: usize, mate: &[usize]) {
+        let base = self.common_base(v, to, mate);
+        let mut blossom = vec![false; mate.len()];
+        self.mark(v, base, to, mate, &mut blossom);
+        self.mark(to, base, v, mate, &mut blossom);
+
+        for i in 0..mate.len() {
+            if blossom[self.base[i]] {
+                self.base[i] = base;
+
+                if !self.used[i] {
+                    self.used[i] = true;
+                    self.queue.push_back(i);
+                }
+            }
+        }
+    }
+
+    fn common_base(&self, mut a: usize, mut b: usize, mate: &[usize]) -> usize {
+        let mut on_path = vec![false; mate.len()];
+
+        loop {
+            a = self.base[a];
+            on_path[a] = true;
+
+            if mate[a] == NONE {
+                break;
+            }
+
+            a = self.parent[mate[a]];
+        }
+
+        loop {
+            b = self.base[b];
+
+            if on_path[b] {
+                return b;
+            }
+
+            b = self.parent[mate[b]];
+        }
+    }
+
+    fn mark(
+        &mut self,
+        mut v: usize,
+        base: usize,
+        mut child: usize,
+        mate: &[usize],
+        blossom: &mut [bool],
+    ) {
+        while self.base[v] != base {
+            blossom[self.base[v]] = true;
+            blossom[self.base[mate[v]]] = true;
+            self.parent[v] = child;
+            child = mate[v];
+            v = self.parent[mate[v]];
+        }
+    }
+}
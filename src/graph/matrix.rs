@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io::{self, Write};
+use std::ops::Add;
+
+use super::Graph;
+use crate::cancel::{CancelToken, Cancelled};
+use crate::progress::Progress;
+
+// shortest distances from each of a set of sources to each of a set
+// of targets. a missing entry means the target can't be reached (and
+// as with dijkstra_paths, there's no entry from a vertex to itself).
+#[derive(Debug, Clone)]
+pub struct DistanceMatrix<'g, V, E> {
+    sources: Vec<&'g V>,
+    targets: Vec<&'g V>,
+    source_index: HashMap<&'g V, usize>,
+    target_index: HashMap<&'g V, usize>,
+    cells: Vec<Option<E>>,
+}
+
+impl<'g, V, E> DistanceMatrix<'g, V, E>
+where
+    V: Hash + Eq,
+{
+    pub fn sources(&self) -> &[&'g V] {
+        &self.sources
+    }
+
+    pub fn targets(&self) -> &[&'g V] {
+        &self.targets
+    }
+
+    pub fn get(&self, source: &V, target: &V) -> Option<&E> {
+        let row = *self.source_index.get(source)?;
+        let column = *self.target_index.get(target)?;
+
+        self.cells[row * self.targets.len() + column].as_ref()
+    }
+
+    pub fn row<'m>(&'m self, source: &V) -> impl Iterator<Item = (&'g V, Option<&'m E>)> + 'm {
+        let cells = match self.source_index.get(source) {
+            Some(&row) => {
+                let start = row * self.targets.len();
+                &self.cells[start..start + self.targets.len()]
+            }
+            None => &[][..],
+        };
+
+        self.targets
+            .iter()
+            .cloned()
+            .zip(cells.iter().map(|cell| cell.as_ref()))
+    }
+
+    pub fn column<'m>(&'m self, target: &V) -> impl Iterator<Item = (&'g V, Option<&'m E>)> + 'm {
+        // the cells are stored row by row, so a column is every
+        // width-th one, starting from the column's own offset.
+        let cells = match self.target_index.get(target) {
+            Some(&column) => &self.cells[column..],
+            None => &[][..],
+        };
+
+        self.sources.iter().cloned().zip(
+            cells
+                .iter()
+                .step_by(self.targets.len().max(1))
+                .map(|cell| cell.as_ref()),
+        )
+    }
+}
+
+impl<'g, V, E> DistanceMatrix<'g, V, E>
+where
+    V: Hash + Eq + Display,
+    E: Display,
+{
+    // one row per source, with the targets along the header row.
+    // unreachable pairs are left as empty fields.
+    pub fn write_csv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let header: Vec<String> = self.targets.iter().map(csv_field).collect();
+        writeln!(writer, ",{}", header.join(","))?;
+
+        for (row, source) in self.sources.iter().enumerate() {
+            let start = row * self.targets.len();
+            let fields: Vec<String> = self.cells[start..start + self.targets.len()]
+                .iter()
+                .map(|cell| cell.as_ref().map_or_else(String::new, csv_field))
+                .collect();
+
+            writeln!(writer, "{},{}", csv_field(source), fields.join(","))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write_csv(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+}
+
+// fields with commas, quotes or line breaks in them need quoting, with
+// any quotes inside doubled up.
+fn csv_field<T: Display>(value: T) -> String {
+    let text = value.to_string();
+
+    if text.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    pub fn distance_matrix<'g>(
+        &'g self,
+        sources: &[&'g V],
+        targets: &[&'g V],
+    ) -> DistanceMatrix<'g, V, E> {
+        self.distance_matrix_with_progress(sources, targets, &mut ())
+    }
+
+    // reports progress after the search from each source.
+    pub fn distance_matrix_with_progress<'g, P: Progress>(
+        &'g self,
+        sources: &[&'g V],
+        targets: &[&'g V],
+        progress: &mut P,
+    ) -> DistanceMatrix<'g, V, E> {
+        self.distance_matrix_cancellable(sources, targets, progress, &CancelToken::new())
+            .unwrap()
+    }
+
+    // checks for cancellation before the search from each source.
+    pub fn distance_matrix_cancellable<'g, P: Progress>(
+        &'g self,
+        sources: &[&'g V],
+        targets: &[&'g V],
+        progress: &mut P,
+        cancel: &CancelToken,
+    ) -> Result<DistanceMatrix<'g, V, E>, Cancelled> {
+        // one full search per source covers every target at once.
+        let mut cells = Vec::with_capacity(sources.len() * targets.len());
+
+        for (done, source) in sources.iter().enumerate() {
+            cancel.check()?;
+            let distances = self.dijkstra_paths(source);
+
+            for target in targets.iter() {
+                cells.push(distances.get(target).cloned());
+            }
+
+            progress.on_progress(done + 1, sources.len());
+        }
+
+        let index = |vertices: &[&'g V]| {
+            vertices
+                .iter()
+                .enumerate()
+                .map(|(i, &vertex)| (vertex, i))
+                .collect()
+        };
+
+        Ok(DistanceMatrix {
+            sources: sources.to_vec(),
+            targets: targets.to_vec(),
+            source_index: index(sources),
+            target_index: index(targets),
+            cells,
+        })
+    }
+}
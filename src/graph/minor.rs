@@ -0,0 +1,450 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use super::Graph;
+
+// both searches work on numbered vertices, with the pattern treated
+// as a simple graph (loops and parallel edges make no difference to
+// which structures it can be found in, for these purposes).
+struct Simple {
+    neighbors: Vec<Vec<usize>>,
+    adjacent: Vec<Vec<bool>>,
+}
+
+impl Simple {
+    fn new<V: Hash + Eq, E>(graph: &Graph<V, E>) -> Self {
+        let index: HashMap<&V, usize> = graph
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, vertex)| (vertex, i))
+            .collect();
+
+        let count = index.len();
+        let mut adjacent = vec![vec![false; count]; count];
+
+        for edge in graph.edges.iter() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+
+            if a != b {
+                adjacent[a][b] = true;
+                adjacent[b][a] = true;
+            }
+        }
+
+        let neighbors = adjacent
+            .iter()
+            .map(|row| (0..count).filter(|&b| row[b]).collect())
+            .collect();
+
+        Self {
+            neighbors,
+            adjacent,
+        }
+    }
+
+    // shrinks the host without changing the answer for a pattern with
+    // the given minimum degree. a vertex of degree one can't be in a
+    // branch set (or on a path) that a pattern of minimum degree two
+    // needs, and if the pattern's minimum degree is at least three, a
+    // vertex of degree two can only ever be passed straight through,
+    // so it may as well be replaced by an edge between its neighbours.
+    fn reduced(&self, min_degree: usize) -> Self {
+        let count = self.len();
+        let mut adjacent = self.adjacent.clone();
+        let mut alive = vec![true; count];
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for x in 0..count {
+                if !alive[x] {
+                    continue;
+                }
+
+                let around: Vec<usize> = (0..count).filter(|&y| adjacent[x][y]).collect();
+                let removable = (around.len() <= 1 && min_degree >= 2)
+                    || (around.len() == 2 && min_degree >= 3);
+
+                if !removable {
+                    continue;
+                }
+
+                for &y in around.iter() {
+                    adjacent[x][y] = false;
+                    adjacent[y][x] = false;
+                }
+
+                if around.len() == 2 {
+                    let (y, z) = (around[0], around[1]);
+                    adjacent[y][z] = true;
+                    adjacent[z][y] = true;
+                }
+
+                alive[x] = false;
+                changed = true;
+            }
+        }
+
+        let kept: Vec<usize> = (0..count).filter(|&x| alive[x]).collect();
+        let adjacent: Vec<Vec<bool>> = kept
+            .iter()
+            .map(|&a| kept.iter().map(|&b| adjacent[a][b]).collect())
+            .collect();
+        let neighbors = adjacent
+            .iter()
+            .map(|row| (0..kept.len()).filter(|&b| row[b]).collect())
+            .collect();
+
+        Self {
+            neighbors,
+            adjacent,
+        }
+    }
+
+    fn min_degree(&self) -> usize {
+        self.neighbors.iter().map(|n| n.len()).min().unwrap_or(0)
+    }
+
+    fn len(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.neighbors.iter().map(|n| n.len()).sum::<usize>() / 2
+    }
+
+    // an order where every vertex (besides the first of each
+    // component) comes after one of its neighbours, so the searches
+    // below can check pattern edges as early as possible.
+    fn connected_order(&self) -> Vec<usize> {
+        let mut order = Vec::new();
+        let mut seen = vec![false; self.len()];
+
+        for root in 0..self.len() {
+            if seen[root] {
+                continue;
+            }
+
+            let mut queue = VecDeque::new();
+            seen[root] = true;
+            queue.push_back(root);
+
+            while let Some(vertex) = queue.pop_front() {
+                order.push(vertex);
+
+                for &next in self.neighbors[vertex].iter() {
+                    if !seen[next] {
+                        seen[next] = true;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        order
+    }
+}
+
+// the search for a minor picks a connected "branch set" of host
+// vertices for each pattern vertex in turn. contracting each branch
+// set down to one vertex (and deleting everything else) leaves the
+// pattern, provided every pattern edge has a host edge between the
+// corresponding branch sets.
+struct MinorSearch<'s> {
+    host: &'s Simple,
+    pattern: &'s Simple,
+    order: Vec<usize>,
+    owner: Vec<Option<usize>>,
+    free: usize,
+}
+
+impl<'s> MinorSearch<'s> {
+    fn place(&mut self, step: usize) -> bool {
+        if step == self.order.len() {
+            return true;
+        }
+
+        // branch sets are enumerated by their lowest numbered vertex,
+        // using wernicke's esu algorithm so that each connected set
+        // turns up exactly once.
+        for root in 0..self.host.len() {
+            if self.owner[root].is_some() {
+                continue;
+            }
+
+            let extension = self.host.neighbors[root]
+                .iter()
+                .cloned()
+                .filter(|&n| n > root && self.owner[n].is_none())
+                .collect();
+
+            if self.grow(step, &mut vec![root], extension, root) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn grow(
+        &mut self,
+        step: usize,
+        set: &mut Vec<usize>,
+        extension: Vec<usize>,
+        root: usize,
+    ) -> bool {
+        // growing the set any further would leave too few vertices for
+        // the pattern vertices that still need branch sets.
+        let remaining = self.order.len() - step - 1;
+        if self.free < set.len() + remaining {
+            return false;
+        }
+
+        if self.try_set(step, set) {
+            return true;
+        }
+
+        let mut extension = extension;
+
+        while let Some(w) = extension.pop() {
+            let mut next_extension = extension.clone();
+
+            for &u in self.host.neighbors[w].iter() {
+                let exclusive = u > root
+                    && self.owner[u].is_none()
+                    && !set.contains(&u)
+                    && !set.iter().any(|&s| self.host.adjacent[s][u])
+                    && !extension.contains(&u);
+
+                if exclusive {
+                    next_extension.push(u);
+                }
+            }
+
+            set.push(w);
+            if self.grow(step, set, next_extension, root) {
+                return true;
+            }
+            set.pop();
+        }
+
+        false
+    }
+
+    // a cheap check to rule out dead ends early: every branch set so
+    // far needs as many distinct free vertices next to it as it has
+    // pattern neighbours that haven't been given branch sets yet.
+    fn looks_ahead(&self, step: usize) -> bool {
+        let later = &self.order[step + 1..];
+
+        self.order[..=step].iter().all(|&placed| {
+            let needed = later
+                .iter()
+                .filter(|&&next| self.pattern.adjacent[placed][next])
+                .count();
+
+            if needed == 0 {
+                return true;
+            }
+
+            let mut free: Vec<usize> = (0..self.host.len())
+                .filter(|&member| self.owner[member] == Some(placed))
+                .flat_map(|member| self.host.neighbors[member].iter().cloned())
+                .filter(|&n| self.owner[n].is_none())
+                .collect();
+            free.sort_unstable();
+            free.dedup();
+
+            free.len() >= needed
+        })
+    }
+
+    fn try_set(&mut self, step: usize, set: &[usize]) -> bool {
+        let vertex = self.order[step];
+
+        for &member in set.iter() {
+            self.owner[member] = Some(vertex);
+        }
+        self.free -= set.len();
+
+        // the branch sets of the pattern vertex's neighbours are all
+        // disjoint, so the set needs enough distinct vertices around it
+        // that are either free or in one of those neighbouring sets.
+        let mut outside: Vec<usize> = set
+            .iter()
+            .flat_map(|&member| self.host.neighbors[member].iter().cloned())
+            .filter(|&n| match self.owner[n] {
+                None => true,
+                Some(other) => other != vertex && self.pattern.adjacent[vertex][other],
+            })
+            .collect();
+        outside.sort_unstable();
+        outside.dedup();
+
+        let roomy = outside.len() >= self.pattern.neighbors[vertex].len();
+
+        // every pattern edge back to an earlier branch set needs at
+        // least one host edge leaving this set and landing in that one.
+        let connected = roomy
+            && self.order[..step].iter().all(|&earlier| {
+                !self.pattern.adjacent[vertex][earlier]
+                    || set.iter().any(|&member| {
+                        self.host.neighbors[member]
+                            .iter()
+                            .any(|&n| self.owner[n] == Some(earlier))
+                    })
+            });
+
+        let found = connected && self.looks_ahead(step) && self.place(step + 1);
+
+        for &member in set.iter() {
+            self.owner[member] = None;
+        }
+        self.free += set.len();
+
+        found
+    }
+}
+
+// the search for a topological minor maps each pattern vertex to a
+// distinct host vertex, then tries to route each pattern edge along a
+// path whose inner vertices aren't used by anything else.
+struct SubdivisionSearch<'s> {
+    host: &'s Simple,
+    pattern: &'s Simple,
+    order: Vec<usize>,
+    image: Vec<Option<usize>>,
+    used: Vec<bool>,
+}
+
+impl<'s> SubdivisionSearch<'s> {
+    fn place(&mut self, step: usize) -> bool {
+        if step == self.order.len() {
+            let edges: Vec<(usize, usize)> = (0..self.pattern.len())
+                .flat_map(|a| {
+                    self.pattern.neighbors[a]
+                        .iter()
+                        .filter(move |&&b| a < b)
+                        .map(move |&b| (a, b))
+                })
+                .collect();
+
+            return self.route(&edges);
+        }
+
+        let vertex = self.order[step];
+
+        for candidate in 0..self.host.len() {
+            let degree_ok =
+                self.host.neighbors[candidate].len() >= self.pattern.neighbors[vertex].len();
+
+            if self.used[candidate] || !degree_ok {
+                continue;
+            }
+
+            self.image[vertex] = Some(candidate);
+            self.used[candidate] = true;
+
+            if self.place(step + 1) {
+                return true;
+            }
+
+            self.image[vertex] = None;
+            self.used[candidate] = false;
+        }
+
+        false
+    }
+
+    fn route(&mut self, edges: &[(usize, usize)]) -> bool {
+        let (a, b) = match edges.first() {
+            Some(&edge) => edge,
+            None => return true,
+        };
+
+        let (start, end) = (self.image[a].unwrap(), self.image[b].unwrap());
+        self.walk(start, end, &edges[1..])
+    }
+
+    // extend a path from the current vertex by one step at a time,
+    // trying to route the rest of the edges whenever it reaches the
+    // end, and backing out of it again if that doesn't work out.
+    fn walk(&mut self, current: usize, end: usize, rest: &[(usize, usize)]) -> bool {
+        for i in 0..self.host.neighbors[current].len() {
+            let next = self.host.neighbors[current][i];
+
+            if next == end {
+                if self.route(rest) {
+                    return true;
+                }
+                continue;
+            }
+
+            if self.used[next] {
+                continue;
+            }
+
+            self.used[next] = true;
+            if self.walk(next, end, rest) {
+                return true;
+            }
+            self.used[next] = false;
+        }
+
+        false
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // both of these are exponential in the worst case, and are meant
+    // for small fixed patterns like k5 and k3,3 rather than general
+    // minor testing.
+    pub fn contains_minor<P, F>(&self, pattern: &Graph<P, F>) -> bool
+    where
+        P: Hash + Eq,
+    {
+        let pattern = Simple::new(pattern);
+        let host = Simple::new(self).reduced(pattern.min_degree());
+
+        if pattern.len() > host.len() || pattern.edge_count() > host.edge_count() {
+            return false;
+        }
+
+        let mut search = MinorSearch {
+            host: &host,
+            pattern: &pattern,
+            order: pattern.connected_order(),
+            owner: vec![None; host.len()],
+            free: host.len(),
+        };
+
+        search.place(0)
+    }
+
+    pub fn contains_topological_minor<P, F>(&self, pattern: &Graph<P, F>) -> bool
+    where
+        P: Hash + Eq,
+    {
+        let pattern = Simple::new(pattern);
+        let host = Simple::new(self).reduced(pattern.min_degree());
+
+        if pattern.len() > host.len() || pattern.edge_count() > host.edge_count() {
+            return false;
+        }
+
+        let mut search = SubdivisionSearch {
+            host: &host,
+            pattern: &pattern,
+            order: pattern.connected_order(),
+            image: vec![None; pattern.len()],
+            used: vec![false; host.len()],
+        };
+
+        search.place(0)
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+use super::Graph;
+
+// region operations from image processing, where a region is a set of
+// vertices. on a graph made from a grid, with an edge between each pair
+// of neighbouring cells, they're the usual ones, with the neighbours the
+// grid was made with as the structuring element (so a four-connected
+// grid dilates into diamonds, and an eight-connected one into squares).
+// they work on any graph, though. edges are treated as two-way.
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // every vertex within the given number of edges of the region, such
+    // as the cells an agent of that radius can't stand on, when the
+    // region is the obstacles.
+    pub fn dilate(&self, region: &HashSet<&V>, radius: usize) -> HashSet<&V> {
+        let starts = region
+            .iter()
+            .filter_map(|&vertex| self.vertices.get(vertex));
+        self.within(starts, radius)
+    }
+
+    // the vertices of the region that are more than the given number of
+    // edges from anything outside it, which is the region with the same
+    // amount taken off around its edges.
+    pub fn erode(&self, region: &HashSet<&V>, radius: usize) -> HashSet<&V> {
+        let outside = self
+            .vertices
+            .iter()
+            .filter(|&vertex| !region.contains(vertex));
+
+        let near_outside = self.within(outside, radius);
+
+        self.vertices
+            .iter()
+            .filter(|&vertex| region.contains(vertex) && !near_outside.contains(vertex))
+            .collect()
+    }
+
+    // every vertex that can be reached from the start without leaving
+    // the vertices that pass the check, like the paint bucket in an
+    // image editor. it's empty if the start doesn't pass the check.
+    pub fn flood_fill<F>(&self, start: &V, check: F) -> HashSet<&V>
+    where
+        F: Fn(&V) -> bool,
+    {
+        let start = match self.vertices.get(start) {
+            Some(start) if check(start) => start,
+            _ => return HashSet::new(),
+        };
+
+        let mut filled = HashSet::new();
+        filled.insert(start);
+        let mut stack = vec![start];
+
+        while let Some(vertex) = stack.pop() {
+            for edge in self.incident_edges(vertex) {
+                for other in [&edge.v1, &edge.v2].iter() {
+                    if check(other) && filled.insert(*other) {
+                        stack.push(*other);
+                    }
+                }
+            }
+        }
+
+        filled
+    }
+
+    // everything within the radius of any of the starts, breadth first.
+    fn within<'g, I>(&'g self, starts: I, radius: usize) -> HashSet<&'g V>
+    where
+        I: Iterator<Item = &'g V>,
+    {
+        let mut reached: HashSet<&V> = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        for start in starts {
+            if reached.insert(start) {
+                queue.push_back((start, 0));
+            }
+        }
+
+        while let Some((vertex, hops)) = queue.pop_front() {
+            if hops == radius {
+                continue;
+            }
+
+            for edge in self.incident_edges(vertex) {
+                for other in [&edge.v1, &edge.v2].iter() {
+                    if reached.insert(*other) {
+                        queue.push_back((*other, hops + 1));
+                    }
+                }
+            }
+        }
+
+        reached
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use super::Graph;
+use crate::error::GraphError;
+
+// matrix market coordinate files, read as the weighted adjacency matrix
+// of a graph with vertices numbered from one. a symmetric file only
+// stores one triangle, so each entry is an undirected edge. in a
+// general one, entry (i, j) is an edge from i to j when the edges are
+// directed. otherwise, it may store both (i, j) and (j, i), in which
+// case the first of the two is kept. pattern files have no values at
+// all, so every edge gets a weight of one. the diagonal is skipped,
+// since loops make no difference to any distances.
+impl<E: FromStr> Graph<usize, E> {
+    pub fn from_mtx<R: BufRead>(reader: R, directed: bool) -> Result<Self, GraphError> {
+        let mut lines = reader.lines().enumerate();
+
+        let header = match lines.next() {
+            Some((_, line)) => line?,
+            None => return Err(GraphError::syntax(1, "File is empty.")),
+        };
+
+        let fields: Vec<String> = header
+            .split_whitespace()
+            .map(|field| field.to_lowercase())
+            .collect();
+
+        if fields.len() != 5 || fields[0] != "%%matrixmarket" || fields[1] != "matrix" {
+            return Err(GraphError::syntax(1, "Missing the Matrix Market header."));
+        }
+
+        if fields[2] != "coordinate" {
+            return Err(GraphError::syntax(
+                1,
+                "Only coordinate matrices are supported.",
+            ));
+        }
+
+        let pattern = match fields[3].as_str() {
+            "real" | "integer" => false,
+            "pattern" => true,
+            _ => {
+                return Err(GraphError::syntax(
+                    1,
+                    "Only real, integer and pattern matrices are supported.",
+                ))
+            }
+        };
+
+        let symmetric = match fields[4].as_str() {
+            "general" => false,
+            "symmetric" => true,
+            _ => {
+                return Err(GraphError::syntax(
+                    1,
+                    "Only general and symmetric matrices are supported.",
+                ))
+            }
+        };
+
+        let mut graph = Graph::empty();
+        let mut size: Option<(usize, usize)> = None;
+        let mut seen: HashSet<(usize, usize)> = HashSet::new();
+        let mut entries = 0;
+        let mut last = 1;
+
+        for (number, line) in lines {
+            let line = line?;
+            last = number + 1;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('%') {
+                continue;
+            }
+
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let number = number + 1;
+
+            // the first line that isn't a comment gives the dimensions
+            // and the number of entries that follow.
+            let (order, expected) = match size {
+                Some(size) => size,
+                None => {
+                    let numbers: Vec<usize> = tokens
+                        .iter()
+                        .map(|token| token.parse())
+                        .collect::<Result<_, _>>()
+                        .map_err(|_| {
+                            GraphError::syntax(number, "Size line could not be parsed.")
+                        })?;
+
+                    if numbers.len() != 3 {
+                        return Err(GraphError::syntax(
+                            number,
+                            "Size line needs rows, columns and entries.",
+                        ));
+                    }
+
+                    if numbers[0] != numbers[1] {
+                        return Err(GraphError::syntax(
+                            number,
+                            "Adjacency matrix must be square.",
+                        ));
+                    }
+
+                    for vertex in 1..=numbers[0] {
+                        graph.add_vertex(vertex);
+                    }
+
+                    size = Some((numbers[0], numbers[2]));
+                    continue;
+                }
+            };
+
+            if entries == expected {
+                return Err(GraphError::syntax(
+                    number,
+                    "Matrix has more entries than its header says.",
+                ));
+            }
+            entries += 1;
+
+            if tokens.len() != if pattern { 2 } else { 3 } {
+                return Err(GraphError::syntax(
+                    number,
+                    "Entry has the wrong number of fields.",
+                ));
+            }
+
+            let position = |token: &str| match token.parse::<usize>() {
+                Ok(i) if i >= 1 && i <= order => Ok(i - 1),
+                _ => Err(GraphError::syntax(
+                    number,
+                    "Entry is outside of the matrix.",
+                )),
+            };
+
+            let (i, j) = (position(tokens[0])?, position(tokens[1])?);
+            let weight = if pattern { "1" } else { tokens[2] };
+            let weight = weight
+                .parse()
+                .map_err(|_| GraphError::syntax(number, "Entry value could not be parsed."))?;
+
+            if i == j {
+                continue;
+            }
+
+            // the vertices were all added with the size line, so
+            // connecting them can't fail.
+            let (a, b) = (i + 1, j + 1);
+
+            if symmetric {
+                graph.connect_vertices(&a, &b, weight).unwrap();
+            } else if directed {
+                graph.connect_directed(&a, &b, weight).unwrap();
+            } else if seen.insert((a.min(b), a.max(b))) {
+                graph.connect_vertices(&a, &b, weight).unwrap();
+            }
+        }
+
+        match size {
+            None => Err(GraphError::syntax(last, "Missing the size line.")),
+            Some((_, expected)) if entries < expected => Err(GraphError::syntax(
+                last,
+                "Matrix has fewer entries than its header says.",
+            )),
+            Some(_) => Ok(graph),
+        }
+    }
+
+    pub fn parse_mtx(text: &str, directed: bool) -> Result<Self, GraphError> {
+        Self::from_mtx(text.as_bytes(), directed)
+    }
+}
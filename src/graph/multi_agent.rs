@@ -0,0 +1,322 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::mem;
+
+use super::Graph;
+use crate::error::GraphError;
+use crate::limits::SearchLimits;
+
+// everything one agent has been forbidden from doing so far.
+#[derive(Debug, Clone, Default)]
+struct Constraints {
+    vertices: HashSet<(usize, usize)>,
+    edges: HashSet<(usize, usize, usize)>,
+}
+
+// a point in the search over sets of constraints: the constraints on
+// each agent, and the best path for each one that keeps to them.
+struct Node {
+    constraints: Vec<Constraints>,
+    paths: Vec<Vec<usize>>,
+    cost: usize,
+}
+
+// something to stop an agent doing: being at a vertex at a time, or
+// going along an edge (from, to) so as to get there at a time.
+#[derive(Debug, Clone, Copy)]
+enum Forbid {
+    At(usize, usize),
+    Along(usize, usize, usize),
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // paths for a group of agents that never run into each other, each
+    // going from its start to its goal (given as pairs). time goes in
+    // steps, and at each one, every agent either waits where it is or
+    // moves along an edge, so edge values are ignored. no two agents
+    // can be at the same vertex at once, or swap places along an edge.
+    // each path has the agent's vertex at every step until it gets to
+    // its goal, where it then stays, and the total number of steps
+    // between them all is as small as it can be.
+    //
+    // this is conflict-based search: every agent plans by itself with
+    // a search through space and time, and whenever two of them clash,
+    // the search tries both ways of sorting it out, by forbidding one
+    // or the other from being there then. it can take exponential time
+    // with many agents crowded together, and never finishes at all if
+    // there's no answer, so the limits (counting each set of constraints
+    // tried as an expanded vertex) are worth setting.
+    pub fn multi_agent_paths(
+        &self,
+        agents: &[(&V, &V)],
+        limits: &SearchLimits,
+    ) -> Result<Vec<Vec<&V>>, GraphError> {
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let mut ends = Vec::with_capacity(agents.len());
+        for &(start, goal) in agents.iter() {
+            match (index.get(start), index.get(goal)) {
+                (Some(&start), Some(&goal)) => ends.push((start, goal)),
+                _ => return Err(GraphError::VertexNotFound),
+            }
+        }
+
+        let starts: HashSet<usize> = ends.iter().map(|&(start, _)| start).collect();
+        let goals: HashSet<usize> = ends.iter().map(|&(_, goal)| goal).collect();
+
+        if starts.len() < ends.len() {
+            return Err(GraphError::InvalidArgument(
+                "Agents must start at different vertices.",
+            ));
+        }
+
+        if goals.len() < ends.len() {
+            return Err(GraphError::InvalidArgument(
+                "Agents must have different goals.",
+            ));
+        }
+
+        let mut out = vec![Vec::new(); vertices.len()];
+        let mut into = vec![Vec::new(); vertices.len()];
+        for edge in self.edges.iter() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+
+            if a == b {
+                continue;
+            }
+
+            out[a].push(b);
+            into[b].push(a);
+
+            if !edge.directed {
+                out[b].push(a);
+                into[a].push(b);
+            }
+        }
+
+        let planner = Planner {
+            out,
+            hops: ends.iter().map(|&(_, goal)| hops_to(&into, goal)).collect(),
+            ends,
+        };
+
+        let root_constraints = vec![Constraints::default(); agents.len()];
+        let mut paths = Vec::with_capacity(agents.len());
+
+        for (agent, constraints) in root_constraints.iter().enumerate() {
+            paths.push(
+                planner
+                    .plan(agent, constraints)
+                    .ok_or(GraphError::Unreachable)?,
+            );
+        }
+
+        let mut nodes = vec![Node {
+            cost: cost(&paths),
+            constraints: root_constraints,
+            paths,
+        }];
+
+        let mut open = BinaryHeap::new();
+        open.push(Reverse((nodes[0].cost, 0)));
+        let mut budget = limits.start();
+
+        while let Some(Reverse((_, id))) = open.pop() {
+            let memory = nodes.len() * agents.len() * mem::size_of::<Constraints>();
+            budget.expand(memory).map_err(GraphError::Limit)?;
+
+            // one child forbids the first agent, and the other the
+            // second, from doing what they clashed over.
+            let branches = match first_conflict(&nodes[id].paths) {
+                Some(branches) => branches,
+                None => {
+                    let node = nodes.swap_remove(id);
+
+                    return Ok(node
+                        .paths
+                        .into_iter()
+                        .map(|path| path.into_iter().map(|v| vertices[v]).collect())
+                        .collect());
+                }
+            };
+
+            for &(agent, forbidden) in branches.iter() {
+                let mut constraints = nodes[id].constraints.clone();
+
+                match forbidden {
+                    Forbid::At(vertex, time) => constraints[agent].vertices.insert((vertex, time)),
+                    Forbid::Along(from, to, time) => {
+                        constraints[agent].edges.insert((from, to, time))
+                    }
+                };
+
+                let path = match planner.plan(agent, &constraints[agent]) {
+                    Some(path) => path,
+                    None => continue,
+                };
+
+                let mut paths = nodes[id].paths.clone();
+                paths[agent] = path;
+
+                nodes.push(Node {
+                    cost: cost(&paths),
+                    constraints,
+                    paths,
+                });
+                open.push(Reverse((nodes[nodes.len() - 1].cost, nodes.len() - 1)));
+            }
+        }
+
+        Err(GraphError::Infeasible(
+            "Agents can't all get to their goals without running into each other.",
+        ))
+    }
+}
+
+// the graph by number, each agent's start and goal, and how many steps
+// every vertex is from each agent's goal.
+struct Planner {
+    out: Vec<Vec<usize>>,
+    ends: Vec<(usize, usize)>,
+    hops: Vec<Vec<usize>>,
+}
+
+impl Planner {
+    // a* through space and time for one agent, with the steps to its
+    // goal as the heuristic. it's only done once nobody's barred from
+    // its goal any later, since it stays there. once every constraint is
+    // in the past, a path can't need more steps than there are vertices,
+    // so the search doesn't have to look any further ahead than that.
+    fn plan(&self, agent: usize, constraints: &Constraints) -> Option<Vec<usize>> {
+        let (start, goal) = self.ends[agent];
+        let hops = &self.hops[agent];
+
+        if hops[start] == usize::MAX {
+            return None;
+        }
+
+        let latest = constraints
+            .vertices
+            .iter()
+            .map(|&(_, time)| time)
+            .chain(constraints.edges.iter().map(|&(_, _, time)| time))
+            .max()
+            .unwrap_or(0);
+
+        let barred_until = constraints
+            .vertices
+            .iter()
+            .filter(|&&(vertex, _)| vertex == goal)
+            .map(|&(_, time)| time + 1)
+            .max()
+            .unwrap_or(0);
+
+        let horizon = latest + self.out.len() + 1;
+        let mut parent: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((hops[start], 0, start)));
+        parent.insert((start, 0), (start, 0));
+
+        while let Some(Reverse((_, time, vertex))) = queue.pop() {
+            if vertex == goal && time >= barred_until {
+                let mut path = vec![vertex];
+                let mut state = (vertex, time);
+
+                while state.1 > 0 {
+                    state = parent[&state];
+                    path.push(state.0);
+                }
+
+                path.reverse();
+                return Some(path);
+            }
+
+            if time == horizon {
+                continue;
+            }
+
+            let next = time + 1;
+            let moves = self.out[vertex].iter().cloned().chain(Some(vertex));
+
+            for to in moves {
+                if hops[to] == usize::MAX
+                    || parent.contains_key(&(to, next))
+                    || constraints.vertices.contains(&(to, next))
+                    || constraints.edges.contains(&(vertex, to, next))
+                {
+                    continue;
+                }
+
+                parent.insert((to, next), (vertex, time));
+                queue.push(Reverse((next + hops[to], next, to)));
+            }
+        }
+
+        None
+    }
+}
+
+// how many steps every vertex is from the goal, breadth first along
+// the edges backwards. usize::MAX means it can't get there.
+fn hops_to(into: &[Vec<usize>], goal: usize) -> Vec<usize> {
+    let mut hops = vec![usize::MAX; into.len()];
+    hops[goal] = 0;
+    let mut queue = VecDeque::new();
+    queue.push_back(goal);
+
+    while let Some(vertex) = queue.pop_front() {
+        for &before in into[vertex].iter() {
+            if hops[before] == usize::MAX {
+                hops[before] = hops[vertex] + 1;
+                queue.push_back(before);
+            }
+        }
+    }
+
+    hops
+}
+
+fn cost(paths: &[Vec<usize>]) -> usize {
+    paths.iter().map(|path| path.len() - 1).sum()
+}
+
+// the earliest clash between any two of the paths, where an agent that's
+// finished stays at its goal, as the two agents and what to stop each one
+// doing to get around it. two agents can clash by being at the same
+// vertex at once, or by swapping places along an edge.
+fn first_conflict(paths: &[Vec<usize>]) -> Option<[(usize, Forbid); 2]> {
+    let at = |agent: usize, time: usize| paths[agent][time.min(paths[agent].len() - 1)];
+    let end = paths.iter().map(|path| path.len()).max().unwrap_or(0);
+
+    for time in 0..end {
+        for a in 0..paths.len() {
+            for b in a + 1..paths.len() {
+                let vertex = at(a, time);
+
+                if vertex == at(b, time) {
+                    return Some([(a, Forbid::At(vertex, time)), (b, Forbid::At(vertex, time))]);
+                }
+
+                let next = at(a, time + 1);
+
+                if vertex != next && vertex == at(b, time + 1) && next == at(b, time) {
+                    return Some([
+                        (a, Forbid::Along(vertex, next, time + 1)),
+                        (b, Forbid::Along(next, vertex, time + 1)),
+                    ]);
+                }
+            }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,119 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+use super::Graph;
+use crate::error::GraphError;
+use crate::flow::FlowNetwork;
+
+// the edges that were cut, and their total weight.
+#[derive(Debug, Clone)]
+pub struct MultiwayCut<'g, V, E> {
+    pub edges: Vec<(&'g V, &'g V, &'g E)>,
+    pub weight: E,
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + Sub<Output = E> + PartialOrd + Copy + Default,
+{
+    // a set of edges whose removal leaves no path between any two of
+    // the terminals. finding the lightest such set is np-hard for three
+    // or more terminals, so this uses the isolation heuristic (dahlhaus
+    // et al.): cut each terminal away from all the others with a
+    // minimum cut, then keep every cut but the heaviest. that's never
+    // worse than 2 - 2/k times the best answer. edges are treated as
+    // two-way here, whatever their direction.
+    pub fn multiway_cut(&self, terminals: &[&V]) -> Result<MultiwayCut<'_, V, E>, GraphError> {
+        let index: HashMap<&V, usize> = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, vertex)| (vertex, i))
+            .collect();
+
+        let mut terminal_index = Vec::new();
+        for &terminal in terminals.iter() {
+            let i = *index.get(terminal).ok_or(GraphError::VertexNotFound)?;
+
+            if terminal_index.contains(&i) {
+                return Err(GraphError::InvalidArgument("Terminals must be distinct."));
+            }
+
+            terminal_index.push(i);
+        }
+
+        let mut isolating: Vec<(E, Vec<usize>)> = Vec::new();
+
+        for &terminal in terminal_index.iter() {
+            // all the other terminals are merged into one sink node,
+            // so a cut from the terminal to it separates it from all of
+            // them at once.
+            let sink = index.len();
+            let node = |i: usize| {
+                if i != terminal && terminal_index.contains(&i) {
+                    sink
+                } else {
+                    i
+                }
+            };
+
+            let mut network = FlowNetwork::new(index.len() + 1);
+
+            for edge in self.edges.iter() {
+                let (v1, v2) = (node(index[&edge.v1]), node(index[&edge.v2]));
+
+                if v1 != v2 {
+                    network.add_arc(v1, v2, edge.value);
+                    network.add_arc(v2, v1, edge.value);
+                }
+            }
+
+            let weight = network.max_flow(terminal, sink);
+            let source_side = network.min_cut(terminal);
+
+            let cut = self
+                .edges
+                .iter()
+                .enumerate()
+                .filter(|(_, edge)| {
+                    let (v1, v2) = (node(index[&edge.v1]), node(index[&edge.v2]));
+                    source_side[v1] != source_side[v2]
+                })
+                .map(|(i, _)| i)
+                .collect();
+
+            isolating.push((weight, cut));
+        }
+
+        let heaviest = (0..isolating.len()).fold(None, |best: Option<usize>, i| match best {
+            Some(j) if isolating[j].0 >= isolating[i].0 => Some(j),
+            _ => Some(i),
+        });
+
+        // the cuts can overlap, and each edge should only count once.
+        let mut chosen: HashSet<usize> = HashSet::new();
+        for (i, (_, cut)) in isolating.into_iter().enumerate() {
+            if Some(i) != heaviest {
+                chosen.extend(cut);
+            }
+        }
+
+        let mut chosen: Vec<usize> = chosen.into_iter().collect();
+        chosen.sort_unstable();
+
+        let weight = chosen
+            .iter()
+            .fold(E::default(), |total, &i| total + self.edges[i].value);
+        let edges = chosen
+            .into_iter()
+            .map(|i| {
+                let edge = &self.edges[i];
+                (&edge.v1, &edge.v2, &edge.value)
+            })
+            .collect();
+
+        Ok(MultiwayCut { edges, weight })
+    }
+}
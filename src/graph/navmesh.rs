@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use super::Graph;
+use crate::error::GraphError;
+
+pub type Point = (f64, f64);
+
+// a navigation mesh: walkable space as convex polygons, where two
+// polygons are neighbours when they share an edge (the portal between
+// them). the region graph has a vertex for each polygon, by index, and
+// the edges are the distance from one polygon's centre to the other's
+// through the middle of their portal.
+#[derive(Debug, Clone)]
+pub struct NavMesh {
+    polygons: Vec<Vec<Point>>,
+    regions: Graph<usize, f64>,
+    // the two ends of the portal from one polygon to the next, as they
+    // look facing across it: (left, right).
+    portals: HashMap<(usize, usize), (Point, Point)>,
+}
+
+impl NavMesh {
+    // the polygons can be given either way round, but have to be convex
+    // and have some area. neighbours have to share the whole of an edge
+    // exactly, corner to corner, rather than just overlapping part of
+    // one, which is how meshes built by triangulating usually come out.
+    pub fn new(polygons: Vec<Vec<Point>>) -> Result<Self, GraphError> {
+        let mut polygons = polygons;
+
+        for polygon in polygons.iter_mut() {
+            if polygon.len() < 3 {
+                return Err(GraphError::InvalidArgument(
+                    "Polygons must have at least three corners.",
+                ));
+            }
+
+            let area: f64 = corners(polygon).map(|(a, b)| a.0 * b.1 - b.0 * a.1).sum();
+
+            if area == 0.0 || !area.is_finite() {
+                return Err(GraphError::InvalidArgument("Polygons must have some area."));
+            }
+
+            // everything below assumes the corners go anticlockwise.
+            if area < 0.0 {
+                polygon.reverse();
+            }
+
+            let n = polygon.len();
+            if (0..n).any(|i| cross(polygon[i], polygon[(i + 1) % n], polygon[(i + 2) % n]) < 0.0) {
+                return Err(GraphError::Unsupported("Polygons must be convex."));
+            }
+        }
+
+        let mut regions = Graph::empty();
+        for i in 0..polygons.len() {
+            regions.add_vertex(i);
+        }
+
+        // each edge shows up going one way around one polygon and the
+        // other way around its neighbour.
+        let mut sides: HashMap<(Key, Key), usize> = HashMap::new();
+        let mut portals = HashMap::new();
+
+        for (i, polygon) in polygons.iter().enumerate() {
+            for (a, b) in corners(polygon) {
+                let other = match sides.get(&(key(b), key(a))) {
+                    Some(&other) if other != i => other,
+                    _ => {
+                        sides.insert((key(a), key(b)), i);
+                        continue;
+                    }
+                };
+
+                // leaving polygon i across its edge from a to b, its
+                // inside is behind, so a is on the right.
+                portals.insert((i, other), (b, a));
+                portals.insert((other, i), (a, b));
+
+                let middle = ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+                let length =
+                    distance(centre(polygon), middle) + distance(middle, centre(&polygons[other]));
+                regions.connect_vertices(&i, &other, length)?;
+            }
+        }
+
+        Ok(Self {
+            polygons,
+            regions,
+            portals,
+        })
+    }
+
+    pub fn regions(&self) -> &Graph<usize, f64> {
+        &self.regions
+    }
+
+    pub fn polygon(&self, region: usize) -> Option<&[Point]> {
+        self.polygons.get(region).map(|polygon| &polygon[..])
+    }
+
+    // the ends of the portal between two neighbouring polygons, as
+    // (left, right) going from the first into the second.
+    pub fn portal(&self, from: usize, to: usize) -> Option<(Point, Point)> {
+        self.portals.get(&(from, to)).cloned()
+    }
+
+    // which polygon a point is in, counting their edges as inside. on
+    // a shared edge, it's whichever came first.
+    pub fn locate(&self, point: Point) -> Option<usize> {
+        self.polygons
+            .iter()
+            .position(|polygon| corners(polygon).all(|(a, b)| cross(a, b, point) >= 0.0))
+    }
+
+    // the polygons to go through, from the one the start is in to the
+    // goal's, by the shortest route through the region graph.
+    pub fn corridor(&self, start: Point, goal: Point) -> Option<Vec<usize>> {
+        let (from, to) = (self.locate(start)?, self.locate(goal)?);
+
+        if from == to {
+            return Some(vec![from]);
+        }
+
+        let (path, _) = self.regions.dijkstra_path(&from, &to)?;
+        Some(path.into_iter().cloned().collect())
+    }
+
+    // a path from the start to the goal, including both, that stays in
+    // the corridor but cuts each corner as tightly as it can, so it only
+    // turns at the ends of portals. this is the "simple stupid funnel"
+    // algorithm: it keeps a funnel of the directions still open from
+    // the last turn, narrowing it portal by portal, and turns at one of
+    // its sides whenever the other would cross over it.
+    pub fn find_path(&self, start: Point, goal: Point) -> Option<Vec<Point>> {
+        let corridor = self.corridor(start, goal)?;
+
+        let mut portals = vec![(start, start)];
+        for pair in corridor.windows(2) {
+            portals.push(self.portals[&(pair[0], pair[1])]);
+        }
+        portals.push((goal, goal));
+
+        let mut path = vec![start];
+        let (mut apex, mut left, mut right) = (start, start, start);
+        let (mut left_index, mut right_index) = (0, 0);
+        let mut i = 1;
+
+        while i < portals.len() {
+            let (next_left, next_right) = portals[i];
+
+            // narrow the right side, unless it would cross the left.
+            if cross(apex, right, next_right) >= 0.0 {
+                if apex == right || cross(apex, left, next_right) < 0.0 {
+                    right = next_right;
+                    right_index = i;
+                } else {
+                    path.push(left);
+                    apex = left;
+                    right = apex;
+                    right_index = left_index;
+                    i = left_index + 1;
+                    continue;
+                }
+            }
+
+            // and the same for the left side.
+            if cross(apex, left, next_left) <= 0.0 {
+                if apex == left || cross(apex, right, next_left) > 0.0 {
+                    left = next_left;
+                    left_index = i;
+                } else {
+                    path.push(right);
+                    apex = right;
+                    left = apex;
+                    left_index = right_index;
+                    i = right_index + 1;
+                    continue;
+                }
+            }
+
+            i += 1;
+        }
+
+        if path.last() != Some(&goal) {
+            path.push(goal);
+        }
+
+        Some(path)
+    }
+}
+
+// corner positions as bits, so that shared edges can be matched up.
+type Key = (u64, u64);
+
+fn key(point: Point) -> Key {
+    (point.0.to_bits(), point.1.to_bits())
+}
+
+// each edge of the polygon, going around it.
+fn corners(polygon: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    (0..polygon.len()).map(move |i| (polygon[i], polygon[(i + 1) % polygon.len()]))
+}
+
+// positive when c is to the left of the line from a through b.
+fn cross(a: Point, b: Point, c: Point) -> f64 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+fn centre(polygon: &[Point]) -> Point {
+    let n = polygon.len() as f64;
+    let (x, y) = polygon
+        .iter()
+        .fold((0.0, 0.0), |(x, y), point| (x + point.0, y + point.1));
+
+    (x / n, y / n)
+}
+
+fn distance(a: Point, b: Point) -> f64 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::bitset::BitSet;
+use super::frontier::Frontier;
+use super::Graph;
+
+// dijkstra's algorithm one vertex at a time, nearest first. nothing is
+// settled until it's asked for, so stopping early (after the first few
+// vertices, say) skips the rest of the search entirely. as with
+// dijkstra_paths, the source itself isn't given.
+pub struct Nearest<'g, V, E> {
+    vertices: Vec<&'g V>,
+    index: HashMap<&'g V, usize>,
+    adjacent: Vec<Vec<(usize, &'g E)>>,
+    frontier: Frontier<E>,
+    settled: BitSet,
+    previous: Vec<Option<usize>>,
+    source: Option<usize>,
+}
+
+impl<'g, V, E> Nearest<'g, V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    fn new(graph: &'g Graph<V, E>, source: &V) -> Self {
+        let vertices: Vec<&V> = graph.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let mut adjacent: Vec<Vec<(usize, &E)>> = vec![Vec::new(); vertices.len()];
+
+        for edge in graph.edges.iter() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+
+            if a != b {
+                adjacent[a].push((b, &edge.value));
+
+                if !edge.directed {
+                    adjacent[b].push((a, &edge.value));
+                }
+            }
+        }
+
+        let mut nearest = Self {
+            settled: BitSet::new(vertices.len()),
+            previous: vec![None; vertices.len()],
+            source: index.get(source).cloned(),
+            vertices,
+            index,
+            adjacent,
+            frontier: Frontier::new(),
+        };
+
+        // there's no zero for E, so the search starts from the
+        // source's neighbours rather than the source itself.
+        if let Some(source) = nearest.source {
+            nearest.settled.insert(source);
+
+            for &(next, weight) in nearest.adjacent[source].iter() {
+                if nearest.frontier.improve(next, weight.clone()) {
+                    nearest.previous[next] = Some(source);
+                }
+            }
+        }
+
+        nearest
+    }
+
+    // the route from the source to a vertex the search has already
+    // given, or none if it hasn't got that far yet.
+    pub fn path_to(&self, vertex: &V) -> Option<Vec<&'g V>> {
+        let mut current = self
+            .index
+            .get(vertex)
+            .cloned()
+            .filter(|&i| self.settled.contains(i) && Some(i) != self.source)?;
+
+        let mut path = vec![self.vertices[current]];
+
+        while let Some(before) = self.previous[current] {
+            path.push(self.vertices[before]);
+            current = before;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<'g, V, E> Iterator for Nearest<'g, V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    type Item = (&'g V, E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (vertex, dist) = self.frontier.pop()?;
+        self.settled.insert(vertex);
+
+        for &(next, weight) in self.adjacent[vertex].iter() {
+            if !self.settled.contains(next)
+                && self.frontier.improve(next, dist.clone() + weight.clone())
+            {
+                self.previous[next] = Some(vertex);
+            }
+        }
+
+        Some((self.vertices[vertex], dist))
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    pub fn nearest<'g>(&'g self, source: &V) -> Nearest<'g, V, E> {
+        Nearest::new(self, source)
+    }
+
+    // the k vertices closest to the source, nearest first, each with
+    // its distance and the path to it. there may be fewer than k if
+    // not that many can be reached. ties are broken arbitrarily.
+    pub fn k_nearest<'g>(&'g self, source: &V, k: usize) -> Vec<(&'g V, E, Vec<&'g V>)> {
+        let mut search = self.nearest(source);
+        let mut found = Vec::new();
+
+        while found.len() < k {
+            let (vertex, dist) = match search.next() {
+                Some(next) => next,
+                None => break,
+            };
+
+            // the path is there as soon as the vertex has been given.
+            let path = search.path_to(vertex).unwrap();
+            found.push((vertex, dist, path));
+        }
+
+        found
+    }
+}
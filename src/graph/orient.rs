@@ -0,0 +1,189 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+use super::{Edge, Graph};
+use crate::error::GraphError;
+
+// how orient_edges should choose a direction for each edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    // no directed cycles, with each vertex having as few edges out as
+    // the graph's degeneracy allows.
+    Acyclic,
+    // as many edges into every vertex as out of it, which needs every
+    // degree to be even.
+    Eulerian,
+    // the number of edges in and out of every vertex differ by at most
+    // one, which any graph can manage.
+    Balanced,
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Clone,
+{
+    // the same graph with every edge made one-way, for things like flow
+    // algorithms that want a directed input. edges that were already
+    // directed are oriented again along with the rest.
+    //
+    // the acyclic orientation points each edge away from whichever end
+    // comes first in a smallest-last ordering (keep taking away the
+    // vertex with the fewest edges left), so nobody has more edges out
+    // than the degeneracy. the other two follow euler circuits: every
+    // time a circuit passes through a vertex it goes in once and out
+    // once. for balancing, the odd vertices are first paired up with
+    // pretend edges so that the circuits exist.
+    pub fn orient_edges(&self, strategy: Orientation) -> Result<Graph<V, E>, GraphError> {
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let ends: Vec<(usize, usize)> = self
+            .edges
+            .iter()
+            .map(|edge| (index[&edge.v1], index[&edge.v2]))
+            .collect();
+
+        let forwards = match strategy {
+            Orientation::Acyclic => {
+                if ends.iter().any(|&(a, b)| a == b) {
+                    return Err(GraphError::Infeasible(
+                        "Graph has a loop, so it has no acyclic orientation.",
+                    ));
+                }
+
+                acyclic(vertices.len(), &ends)
+            }
+            Orientation::Eulerian | Orientation::Balanced => {
+                let mut degree = vec![0usize; vertices.len()];
+                for &(a, b) in ends.iter() {
+                    degree[a] += 1;
+                    degree[b] += 1;
+                }
+
+                let odd: Vec<usize> = (0..vertices.len())
+                    .filter(|&v| !degree[v].is_multiple_of(2))
+                    .collect();
+
+                if strategy == Orientation::Eulerian && !odd.is_empty() {
+                    return Err(GraphError::Infeasible(
+                        "Eulerian orientation needs every degree to be even.",
+                    ));
+                }
+
+                // there's always an even number of odd vertices, and
+                // they can be paired up however, even across pieces.
+                let mut padded = ends.clone();
+                padded.extend(odd.chunks(2).map(|pair| (pair[0], pair[1])));
+
+                let mut forwards = circuits(vertices.len(), &padded);
+                forwards.truncate(ends.len());
+                forwards
+            }
+        };
+
+        let edges = self
+            .edges
+            .iter()
+            .zip(forwards)
+            .map(|(edge, forwards)| {
+                let (from, to) = if forwards {
+                    (&edge.v1, &edge.v2)
+                } else {
+                    (&edge.v2, &edge.v1)
+                };
+
+                Edge::directed(from.clone(), to.clone(), edge.value.clone())
+            })
+            .collect();
+
+        Ok(Graph::from_parts(self.vertices.clone(), edges))
+    }
+}
+
+// for each edge, whether it points from its first end to its second.
+fn acyclic(count: usize, ends: &[(usize, usize)]) -> Vec<bool> {
+    let mut adjacent = vec![Vec::new(); count];
+    for &(a, b) in ends.iter() {
+        adjacent[a].push(b);
+        adjacent[b].push(a);
+    }
+
+    let mut degree: Vec<usize> = adjacent.iter().map(|around| around.len()).collect();
+    let mut queue: BinaryHeap<Reverse<(usize, usize)>> = degree
+        .iter()
+        .enumerate()
+        .map(|(v, &d)| Reverse((d, v)))
+        .collect();
+
+    let mut position = vec![usize::MAX; count];
+    let mut taken = 0;
+
+    while let Some(Reverse((d, vertex))) = queue.pop() {
+        if position[vertex] != usize::MAX || d != degree[vertex] {
+            continue;
+        }
+
+        position[vertex] = taken;
+        taken += 1;
+
+        for &next in adjacent[vertex].iter() {
+            if position[next] == usize::MAX {
+                degree[next] -= 1;
+                queue.push(Reverse((degree[next], next)));
+            }
+        }
+    }
+
+    ends.iter()
+        .map(|&(a, b)| position[a] < position[b])
+        .collect()
+}
+
+// walks closed trails until every edge has been used, noting which way
+// each edge was walked. with every degree even, a walk can only get
+// stuck back where it started, so each one is closed.
+fn circuits(count: usize, ends: &[(usize, usize)]) -> Vec<bool> {
+    let mut adjacent: Vec<Vec<usize>> = vec![Vec::new(); count];
+    for (i, &(a, b)) in ends.iter().enumerate() {
+        adjacent[a].push(i);
+        if a != b {
+            adjacent[b].push(i);
+        }
+    }
+
+    let mut used = vec![false; ends.len()];
+    let mut forwards = vec![true; ends.len()];
+    let mut next_edge = vec![0; count];
+
+    for start in 0..count {
+        let mut vertex = start;
+
+        loop {
+            while next_edge[vertex] < adjacent[vertex].len()
+                && used[adjacent[vertex][next_edge[vertex]]]
+            {
+                next_edge[vertex] += 1;
+            }
+
+            if next_edge[vertex] == adjacent[vertex].len() {
+                // stuck, which only happens back at the start once
+                // everything around it has been used.
+                break;
+            }
+
+            let edge = adjacent[vertex][next_edge[vertex]];
+            let (a, b) = ends[edge];
+            used[edge] = true;
+            forwards[edge] = a == vertex;
+            vertex = if a == vertex { b } else { a };
+        }
+    }
+
+    forwards
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::Graph;
+use crate::error::GraphError;
+
+// integer addition that can say when it would overflow, as the
+// primitive integers already can (but through inherent methods, which
+// generic code can't get at).
+pub trait CheckedAdd: Sized {
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+    fn saturating_add(&self, other: &Self) -> Self;
+}
+
+macro_rules! impl_checked_add {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedAdd for $t {
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+
+                fn saturating_add(&self, other: &Self) -> Self {
+                    <$t>::saturating_add(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_add!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+// a distance that's either known or too big to represent. the search
+// treats an overflowed distance as longer than any real one, so it's
+// only ever kept for vertices that can't be reached any other way.
+// (the variant order matters here, since it's what PartialOrd uses.)
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+enum Checked<E> {
+    Value(E),
+    Overflow,
+}
+
+impl<E: CheckedAdd> Add for Checked<E> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        match (self, other) {
+            (Checked::Value(a), Checked::Value(b)) => match a.checked_add(&b) {
+                Some(sum) => Checked::Value(sum),
+                None => Checked::Overflow,
+            },
+            _ => Checked::Overflow,
+        }
+    }
+}
+
+// saturating addition, for when a distance of "at least the maximum"
+// is good enough. a newtype so it can go through dijkstra_by.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+struct Saturating<E>(E);
+
+impl<E: CheckedAdd> Add for Saturating<E> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Saturating(self.0.saturating_add(&other.0))
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: CheckedAdd + PartialOrd + Clone,
+{
+    // plain addition on integers wraps around in release builds, which
+    // makes a long path look short. this catches it instead, and only
+    // fails if the true distance to some vertex doesn't fit in E.
+    pub fn dijkstra_paths_overflow_checked(
+        &self,
+        source: &V,
+    ) -> Result<HashMap<&V, E>, GraphError> {
+        let distances = self.dijkstra_by(source, |edge| Checked::Value(edge.clone()), None);
+        let mut checked = HashMap::new();
+
+        for (vertex, dist) in distances {
+            match dist {
+                Checked::Value(dist) => checked.insert(vertex, dist),
+                Checked::Overflow => return Err(GraphError::Overflow),
+            };
+        }
+
+        Ok(checked)
+    }
+
+    // the same search, with any distance too big for E capped at its
+    // maximum value instead.
+    pub fn dijkstra_paths_saturating(&self, source: &V) -> HashMap<&V, E> {
+        self.dijkstra_by(source, |edge| Saturating(edge.clone()), None)
+            .into_iter()
+            .map(|(vertex, dist)| (vertex, dist.0))
+            .collect()
+    }
+}
@@ -0,0 +1,143 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::Graph;
+
+// edge values made up of several independent criteria (say, time
+// and price) which are summed separately along a path. one value
+// dominates another if it's at least as good in every criterion.
+pub trait Criteria: PartialOrd + Clone {
+    fn combine(&self, other: &Self) -> Self;
+    fn dominates(&self, other: &Self) -> bool;
+}
+
+macro_rules! impl_criteria {
+    ( $( $name:ident : $index:tt ),* ) => {
+        impl<$( $name ),*> Criteria for ( $( $name, )* )
+        where
+            $( $name: Add<Output = $name> + PartialOrd + Clone ),*
+        {
+            fn combine(&self, other: &Self) -> Self {
+                ( $( self.$index.clone() + other.$index.clone(), )* )
+            }
+
+            fn dominates(&self, other: &Self) -> bool {
+                true $( && self.$index <= other.$index )*
+            }
+        }
+    }
+}
+
+impl_criteria!(A: 0, B: 1);
+impl_criteria!(A: 0, B: 1, C: 2);
+impl_criteria!(A: 0, B: 1, C: 2, D: 3);
+
+struct Label<'v, V, E> {
+    vertex: &'v V,
+    value: E,
+    previous: Option<usize>,
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Criteria,
+{
+    pub fn pareto_shortest_paths(&self, source: &V, target: &V) -> Vec<(Vec<&V>, E)> {
+        // multi-label dijkstra. each vertex keeps every label that
+        // isn't dominated by another one, and labels are processed in
+        // lexicographic order. since no label can be dominated by one
+        // that comes after it in that order, a label is final as soon
+        // as it's taken off the pending list.
+        let mut labels: Vec<Label<V, E>> = Vec::new();
+        let mut pending: Vec<usize> = Vec::new();
+        let mut settled: HashMap<&V, Vec<usize>> = HashMap::new();
+        let mut frontier: Vec<usize> = Vec::new();
+
+        for (vertex, edge) in self.neighbors(source) {
+            if vertex == source {
+                continue;
+            }
+
+            pending.push(labels.len());
+            labels.push(Label {
+                vertex,
+                value: edge.clone(),
+                previous: None,
+            });
+        }
+
+        while !pending.is_empty() {
+            let (position, _) = pending
+                .iter()
+                .enumerate()
+                .min_by(|&(_, &a), &(_, &b)| {
+                    let (a, b) = (&labels[a].value, &labels[b].value);
+                    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+                })
+                .unwrap();
+            let current = pending.swap_remove(position);
+            let vertex = labels[current].vertex;
+
+            // anything the target's frontier already dominates can't
+            // lead to a new trade-off, so there's no use extending it.
+            let existing = settled.entry(vertex).or_default();
+            if existing
+                .iter()
+                .chain(frontier.iter())
+                .any(|&index| labels[index].value.dominates(&labels[current].value))
+            {
+                continue;
+            }
+            existing.push(current);
+
+            if vertex == target {
+                frontier.push(current);
+                continue;
+            }
+
+            for (neighbor, edge) in self.neighbors(vertex) {
+                if neighbor == source {
+                    continue;
+                }
+
+                let value = labels[current].value.combine(edge);
+                let dominated = settled.get(neighbor).is_some_and(|existing| {
+                    existing
+                        .iter()
+                        .any(|&index| labels[index].value.dominates(&value))
+                });
+
+                if !dominated {
+                    pending.push(labels.len());
+                    labels.push(Label {
+                        vertex: neighbor,
+                        value,
+                        previous: Some(current),
+                    });
+                }
+            }
+        }
+
+        let source = self.vertices.get(source);
+
+        frontier
+            .into_iter()
+            .map(|index| {
+                let mut path = vec![labels[index].vertex];
+                let mut previous = labels[index].previous;
+
+                while let Some(index) = previous {
+                    path.push(labels[index].vertex);
+                    previous = labels[index].previous;
+                }
+
+                path.push(source.unwrap());
+                path.reverse();
+                (path, labels[index].value.clone())
+            })
+            .collect()
+    }
+}
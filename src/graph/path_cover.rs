@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::matching::maximum_matching;
+use super::topological::CycleError;
+use super::Graph;
+
+const NONE: usize = usize::MAX;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // as few paths along the edges of a dag as it takes for every
+    // vertex to be on exactly one of them, like the fewest machines
+    // that can run a set of jobs one after another, with an edge for
+    // each job that can follow another. each vertex is split in two, one
+    // side for leaving it and one for arriving, with an edge from a's
+    // leaving side to b's arriving side for every edge from a to b. a
+    // matched pair there means b comes straight after a on a path, and
+    // every one of those saves a path, so a maximum matching gives the
+    // fewest paths. a two-way edge counts as a cycle, as with
+    // topological_sort.
+    pub fn minimum_path_cover(&self) -> Result<Vec<Vec<&V>>, CycleError<'_, V>> {
+        let order = self.topological_sort()?;
+        let count = order.len();
+        let index: HashMap<&V, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        // leaving sides are numbered first, then the arriving ones.
+        let mut adjacent: Vec<Vec<usize>> = vec![Vec::new(); 2 * count];
+
+        for edge in self.edges.iter() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+            adjacent[a].push(count + b);
+            adjacent[count + b].push(a);
+        }
+
+        let mate = maximum_matching(&adjacent);
+        let next: Vec<usize> = (0..count)
+            .map(|v| match mate[v] {
+                NONE => NONE,
+                arriving => arriving - count,
+            })
+            .collect();
+
+        // a path starts wherever nothing arrives.
+        Ok((0..count)
+            .filter(|&v| mate[count + v] == NONE)
+            .map(|start| {
+                let mut path = vec![order[start]];
+                let mut current = start;
+
+                while next[current] != NONE {
+                    current = next[current];
+                    path.push(order[current]);
+                }
+
+                path
+            })
+            .collect())
+    }
+}
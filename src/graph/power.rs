@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::{Edge, Graph};
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    // the k-th power of the graph, where there's an edge between two
+    // vertices whenever one can be reached from the other in at most k
+    // steps, weighted by the shortest way of doing it in that many. the
+    // distances come from k rounds of bellman-ford from every vertex,
+    // since the shortest path overall might take more steps than that.
+    // if any edge is directed, the power is made of directed edges too,
+    // otherwise there's a single two-way edge for each pair. loops are
+    // left out.
+    pub fn power(&self, k: usize) -> Graph<V, E> {
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let mut adjacent: Vec<Vec<(usize, &E)>> = vec![Vec::new(); vertices.len()];
+
+        for edge in self.edges.iter() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+
+            if a != b {
+                adjacent[a].push((b, &edge.value));
+
+                if !edge.directed {
+                    adjacent[b].push((a, &edge.value));
+                }
+            }
+        }
+
+        let directed = self.edges.iter().any(|edge| edge.directed);
+        let mut edges = Vec::new();
+
+        for source in 0..vertices.len() {
+            // there's no zero for E, so the first round comes straight
+            // from the source's own edges, and nothing ever gets back to
+            // the source. after that, only what changed in the last
+            // round can improve anything.
+            let mut dist: Vec<Option<E>> = vec![None; vertices.len()];
+            let mut changed = Vec::new();
+
+            if k > 0 {
+                for &(next, weight) in adjacent[source].iter() {
+                    if improve(&mut dist, next, weight.clone()) {
+                        changed.push(next);
+                    }
+                }
+            }
+
+            for _ in 1..k {
+                if changed.is_empty() {
+                    break;
+                }
+
+                // each round only goes one step further than the last,
+                // so it extends the distances from before it started.
+                let before = dist.clone();
+                let mut next_changed = Vec::new();
+
+                for &vertex in changed.iter() {
+                    let here = before[vertex].clone().unwrap();
+
+                    for &(next, weight) in adjacent[vertex].iter() {
+                        if next != source && improve(&mut dist, next, here.clone() + weight.clone())
+                        {
+                            next_changed.push(next);
+                        }
+                    }
+                }
+
+                next_changed.sort_unstable();
+                next_changed.dedup();
+                changed = next_changed;
+            }
+
+            for (target, dist) in dist.into_iter().enumerate() {
+                let (from, to) = (vertices[source], vertices[target]);
+
+                match dist {
+                    Some(dist) if directed => {
+                        edges.push(Edge::directed(from.clone(), to.clone(), dist))
+                    }
+                    Some(dist) if source < target => {
+                        edges.push(Edge::new(from.clone(), to.clone(), dist))
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        Graph::from_parts(self.vertices.clone(), edges)
+    }
+}
+
+fn improve<E: PartialOrd>(dist: &mut [Option<E>], vertex: usize, alt: E) -> bool {
+    let better = match &dist[vertex] {
+        Some(current) => alt < *current,
+        None => true,
+    };
+
+    if better {
+        dist[vertex] = Some(alt);
+    }
+
+    better
+}
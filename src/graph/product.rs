@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::{Edge, Graph};
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Clone,
+{
+    // every product has the pairs of vertices from both graphs as its
+    // vertices, and only differs in how they're connected.
+    fn product_vertices<U>(&self, other: &Graph<U, E>) -> HashSet<(V, U)>
+    where
+        U: Hash + Eq + Clone,
+    {
+        let mut vertices = HashSet::new();
+
+        for v in self.vertices.iter() {
+            for u in other.vertices.iter() {
+                vertices.insert((v.clone(), u.clone()));
+            }
+        }
+
+        vertices
+    }
+
+    // (v1, u) and (v2, u) are joined whenever v1 and v2 are, and
+    // likewise for (v, u1) and (v, u2), keeping the original value and
+    // which way the edge goes.
+    pub fn cartesian_product<U>(&self, other: &Graph<U, E>) -> Graph<(V, U), E>
+    where
+        U: Hash + Eq + Clone,
+    {
+        let mut edges = Vec::new();
+
+        for edge in self.edges.iter() {
+            for u in other.vertices.iter() {
+                edges.push(edge.map_ends(|v| (v.clone(), u.clone())));
+            }
+        }
+
+        for edge in other.edges.iter() {
+            for v in self.vertices.iter() {
+                edges.push(edge.map_ends(|u| (v.clone(), u.clone())));
+            }
+        }
+
+        Graph::from_parts(self.product_vertices(other), edges)
+    }
+
+    // (v1, u1) and (v2, u2) are joined only when v1 is joined to v2
+    // and u1 is joined to u2, with the two edge values added together.
+    // with a directed edge on either side, the product's edge is
+    // directed too, going from the pair of starts to the pair of ends.
+    pub fn tensor_product<U>(&self, other: &Graph<U, E>) -> Graph<(V, U), E>
+    where
+        U: Hash + Eq + Clone,
+        E: Add<Output = E>,
+    {
+        let mut edges = Vec::new();
+
+        for e in self.edges.iter() {
+            for f in other.edges.iter() {
+                let value = e.value.clone() + f.value.clone();
+                let pair = |v: &V, u: &U| (v.clone(), u.clone());
+
+                // two undirected edges can be lined up with each other
+                // in two different ways, to give two undirected edges.
+                if !e.directed && !f.directed {
+                    if e.v1 != e.v2 && f.v1 != f.v2 {
+                        let (a, b) = (pair(&e.v1, &f.v2), pair(&e.v2, &f.v1));
+                        edges.push(Edge::new(a, b, value.clone()));
+                    }
+
+                    let (a, b) = (pair(&e.v1, &f.v1), pair(&e.v2, &f.v2));
+                    edges.push(Edge::new(a, b, value));
+                    continue;
+                }
+
+                // otherwise, every way of following e goes with every way
+                // of following f.
+                for &(v1, v2) in e.ways().iter() {
+                    for &(u1, u2) in f.ways().iter() {
+                        let (a, b) = (pair(v1, u1), pair(v2, u2));
+                        edges.push(Edge::directed(a, b, value.clone()));
+                    }
+                }
+            }
+        }
+
+        Graph::from_parts(self.product_vertices(other), edges)
+    }
+
+    // (v1, u1) and (v2, u2) are joined whenever v1 is joined to v2,
+    // regardless of u1 and u2, the same way round. within a single copy
+    // of the other graph (v1 = v2), its own edges are used instead.
+    pub fn lexicographic_product<U>(&self, other: &Graph<U, E>) -> Graph<(V, U), E>
+    where
+        U: Hash + Eq + Clone,
+    {
+        let mut edges = Vec::new();
+
+        for edge in self.edges.iter() {
+            for u1 in other.vertices.iter() {
+                for u2 in other.vertices.iter() {
+                    let (a, b) = ((edge.v1.clone(), u1.clone()), (edge.v2.clone(), u2.clone()));
+                    edges.push(Edge {
+                        v1: a,
+                        v2: b,
+                        value: edge.value.clone(),
+                        directed: edge.directed,
+                    });
+                }
+            }
+        }
+
+        for edge in other.edges.iter() {
+            for v in self.vertices.iter() {
+                edges.push(edge.map_ends(|u| (v.clone(), u.clone())));
+            }
+        }
+
+        Graph::from_parts(self.product_vertices(other), edges)
+    }
+}
+
+impl<V, E> Edge<V, E>
+where
+    V: Hash + Eq,
+    E: Clone,
+{
+    // the same edge, going the same way, between different vertices.
+    fn map_ends<W, F>(&self, end: F) -> Edge<W, E>
+    where
+        W: Hash + Eq,
+        F: Fn(&V) -> W,
+    {
+        Edge {
+            v1: end(&self.v1),
+            v2: end(&self.v2),
+            value: self.value.clone(),
+            directed: self.directed,
+        }
+    }
+
+    // the ways the edge can be followed, as (from, to), which is only
+    // one for a directed edge or a loop.
+    fn ways(&self) -> Vec<(&V, &V)> {
+        if self.directed || self.v1 == self.v2 {
+            vec![(&self.v1, &self.v2)]
+        } else {
+            vec![(&self.v1, &self.v2), (&self.v2, &self.v1)]
+        }
+    }
+}
@@ -0,0 +1,368 @@
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::frontier::Queued;
+use super::{Graph, Stop};
+use crate::limits::SearchLimits;
+use crate::metrics::Counters;
+
+const NONE: usize = usize::MAX;
+
+// the priority queue behind a search, which only ever needs to push
+// entries and pop the closest one. a vertex that gets closer is just
+// pushed again, and the search skips its old entries, so there's no
+// need for a decrease-key. ties can come out in any order.
+pub trait PriorityQueue<W, T> {
+    fn push(&mut self, dist: W, item: T);
+    fn pop(&mut self) -> Option<(W, T)>;
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// the bucket and radix queues only work with whole number distances,
+// which they use as positions rather than comparing them.
+pub trait IntegerWeight {
+    fn as_u64(&self) -> u64;
+}
+
+macro_rules! impl_integer_weight {
+    ($($t:ty),*) => {
+        $(
+            impl IntegerWeight for $t {
+                fn as_u64(&self) -> u64 {
+                    *self as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_weight!(u8, u16, u32, u64, usize);
+
+// the standard library's binary heap, which is what the searches use
+// unless they're given something else. it works with any weights.
+pub struct BinaryQueue<W, T> {
+    heap: BinaryHeap<Queued<W, T>>,
+}
+
+impl<W: PartialOrd, T> BinaryQueue<W, T> {
+    pub fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+        }
+    }
+}
+
+impl<W: PartialOrd, T> Default for BinaryQueue<W, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: PartialOrd, T> PriorityQueue<W, T> for BinaryQueue<W, T> {
+    fn push(&mut self, dist: W, item: T) {
+        self.heap.push(Queued { dist, item });
+    }
+
+    fn pop(&mut self) -> Option<(W, T)> {
+        self.heap.pop().map(|queued| (queued.dist, queued.item))
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+struct PairNode<W, T> {
+    entry: Option<(W, T)>,
+    child: usize,
+    sibling: usize,
+}
+
+// a pairing heap, kept in one vector with the slots of popped entries
+// reused. pushing is constant time, and all of the work of sorting
+// things out is left until popping, which tends to suit searches that
+// push far more entries than they ever pop.
+pub struct PairingHeap<W, T> {
+    nodes: Vec<PairNode<W, T>>,
+    free: Vec<usize>,
+    root: usize,
+    len: usize,
+}
+
+impl<W: PartialOrd, T> PairingHeap<W, T> {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: NONE,
+            len: 0,
+        }
+    }
+
+    fn dist(&self, node: usize) -> &W {
+        &self.nodes[node].entry.as_ref().unwrap().0
+    }
+
+    // the closer of the two roots takes the other as its first child.
+    fn meld(&mut self, a: usize, b: usize) -> usize {
+        let (parent, child) = if self.dist(b) < self.dist(a) {
+            (b, a)
+        } else {
+            (a, b)
+        };
+
+        self.nodes[child].sibling = self.nodes[parent].child;
+        self.nodes[parent].child = child;
+        parent
+    }
+}
+
+impl<W: PartialOrd, T> Default for PairingHeap<W, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: PartialOrd, T> PriorityQueue<W, T> for PairingHeap<W, T> {
+    fn push(&mut self, dist: W, item: T) {
+        let node = PairNode {
+            entry: Some((dist, item)),
+            child: NONE,
+            sibling: NONE,
+        };
+
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.nodes[slot] = node;
+                slot
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+
+        self.root = if self.root == NONE {
+            slot
+        } else {
+            self.meld(self.root, slot)
+        };
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(W, T)> {
+        if self.root == NONE {
+            return None;
+        }
+
+        let root = self.root;
+        let entry = self.nodes[root].entry.take();
+        self.free.push(root);
+        self.len -= 1;
+
+        let mut children = Vec::new();
+        let mut child = self.nodes[root].child;
+
+        while child != NONE {
+            children.push(child);
+            child = self.nodes[child].sibling;
+            self.nodes[children[children.len() - 1]].sibling = NONE;
+        }
+
+        // the usual two passes: meld the children in pairs from the
+        // left, then meld those together from the right.
+        let mut paired = Vec::with_capacity(children.len().div_ceil(2));
+        for pair in children.chunks(2) {
+            paired.push(match pair {
+                [a, b] => self.meld(*a, *b),
+                [a] => *a,
+                _ => unreachable!(),
+            });
+        }
+
+        self.root = paired
+            .into_iter()
+            .rev()
+            .fold(NONE, |root, tree| match root {
+                NONE => tree,
+                root => self.meld(tree, root),
+            });
+
+        entry
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+// a bucket queue (as in dial's algorithm), with a list of entries for
+// every distance from the last one popped onwards. pushing and popping
+// are both constant time, but it's only worth it for small whole
+// number weights, since it holds a bucket for every distance up to
+// the furthest entry. it's monotone: nothing can be
+// pushed closer than the last entry popped, which dijkstra's algorithm
+// never does with weights that aren't negative.
+pub struct BucketQueue<W, T> {
+    buckets: VecDeque<Vec<(W, T)>>,
+    // the distance of the first bucket, which starts at zero and only
+    // moves on as entries are popped.
+    base: u64,
+    len: usize,
+}
+
+impl<W: IntegerWeight, T> BucketQueue<W, T> {
+    pub fn new() -> Self {
+        Self {
+            buckets: VecDeque::new(),
+            base: 0,
+            len: 0,
+        }
+    }
+}
+
+impl<W: IntegerWeight, T> Default for BucketQueue<W, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: IntegerWeight, T> PriorityQueue<W, T> for BucketQueue<W, T> {
+    fn push(&mut self, dist: W, item: T) {
+        let key = dist.as_u64();
+        debug_assert!(key >= self.base, "pushed closer than the last entry");
+        let position = (key - self.base) as usize;
+
+        while self.buckets.len() <= position {
+            self.buckets.push_back(Vec::new());
+        }
+
+        self.buckets[position].push((dist, item));
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(W, T)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        while self.buckets[0].is_empty() {
+            self.buckets.pop_front();
+            self.base += 1;
+        }
+
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+// a radix heap, which is monotone like the bucket queue, but only has
+// a bucket for each bit of the distances. an entry goes in the bucket
+// for the highest bit where it differs from the last one popped, so
+// every one of them only moves down a bucket at a time, and at most
+// once per bit. unlike the bucket queue, it copes with weights of any
+// size.
+pub struct RadixHeap<W, T> {
+    buckets: Vec<Vec<(u64, W, T)>>,
+    last: u64,
+    len: usize,
+}
+
+impl<W: IntegerWeight, T> RadixHeap<W, T> {
+    pub fn new() -> Self {
+        Self {
+            buckets: (0..65).map(|_| Vec::new()).collect(),
+            last: 0,
+            len: 0,
+        }
+    }
+
+    fn bucket(&self, key: u64) -> usize {
+        (64 - (key ^ self.last).leading_zeros()) as usize
+    }
+}
+
+impl<W: IntegerWeight, T> Default for RadixHeap<W, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: IntegerWeight, T> PriorityQueue<W, T> for RadixHeap<W, T> {
+    fn push(&mut self, dist: W, item: T) {
+        let key = dist.as_u64();
+        debug_assert!(key >= self.last, "pushed closer than the last entry");
+
+        let bucket = self.bucket(key);
+        self.buckets[bucket].push((key, dist, item));
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(W, T)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        // the first bucket holds entries at the last distance popped.
+        // once it's empty, the closest entry in the next bucket along
+        // becomes the new last one, and everything else in it moves
+        // down to a lower bucket around it.
+        if self.buckets[0].is_empty() {
+            let next = (1..self.buckets.len())
+                .find(|&i| !self.buckets[i].is_empty())
+                .unwrap();
+
+            let entries = std::mem::take(&mut self.buckets[next]);
+            self.last = entries.iter().map(|&(key, _, _)| key).min().unwrap();
+
+            for entry in entries {
+                let bucket = self.bucket(entry.0);
+                self.buckets[bucket].push(entry);
+            }
+        }
+
+        self.len -= 1;
+        self.buckets[0].pop().map(|(_, dist, item)| (dist, item))
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    // the same distances as dijkstra_paths, searching with the given
+    // queue rather than a binary heap. the bucket queue and radix heap
+    // can be a few times faster when the weights are small integers.
+    pub fn dijkstra_paths_with<'g, Q>(&'g self, source: &V, queue: Q) -> HashMap<&'g V, E>
+    where
+        Q: PriorityQueue<E, &'g V>,
+    {
+        match self.dijkstra_queued(
+            source,
+            |edge| edge.clone(),
+            Stop::Never,
+            false,
+            &SearchLimits::new(),
+            &mut Counters::default(),
+            &mut HashMap::new(),
+            queue,
+        ) {
+            Ok(distances) => distances,
+            Err(exceeded) => exceeded.partial,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::topological::CycleError;
+use super::Graph;
+
+const NONE: usize = usize::MAX;
+
+// answers whether one vertex of a dag can reach another without
+// searching, and without storing the whole transitive closure. the
+// vertices are split into chains, paths along the edges, taken greedily
+// in topological order. then for each vertex and each chain, it keeps
+// the earliest position on that chain it can get to, since it can then
+// get to everything after that too. a query is a single lookup, and the
+// index takes one number per vertex per chain, so it stays small when
+// the graph is narrow (lots of vertices one after another, like build
+// dependencies), but not on wide graphs with thousands of chains.
+pub struct ReachabilityIndex<'g, V> {
+    index: HashMap<&'g V, usize>,
+    chain: Vec<usize>,
+    position: Vec<usize>,
+    earliest: Vec<Vec<usize>>,
+}
+
+impl<'g, V> ReachabilityIndex<'g, V>
+where
+    V: Hash + Eq,
+{
+    // a two-way edge counts as a cycle, as with topological_sort.
+    pub fn new<E>(graph: &'g Graph<V, E>) -> Result<Self, CycleError<'g, V>> {
+        let order = graph.topological_sort()?;
+        let index: HashMap<&V, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        // each vertex carries on the chain of whichever vertex before
+        // it is still the end of its chain, where there is one.
+        let mut chain = vec![NONE; order.len()];
+        let mut position = vec![0; order.len()];
+        let mut ends: Vec<usize> = Vec::new();
+
+        for (v, &vertex) in order.iter().enumerate() {
+            let before = graph
+                .predecessors(vertex)
+                .into_iter()
+                .map(|(before, _)| index[before])
+                .find(|&before| ends[chain[before]] == before);
+
+            match before {
+                Some(before) => {
+                    chain[v] = chain[before];
+                    position[v] = position[before] + 1;
+                    ends[chain[v]] = v;
+                }
+                None => {
+                    chain[v] = ends.len();
+                    ends.push(v);
+                }
+            }
+        }
+
+        // going backwards through the order, everything a vertex leads
+        // to has already been worked out.
+        let mut earliest = vec![Vec::new(); order.len()];
+
+        for v in (0..order.len()).rev() {
+            let mut reach = vec![NONE; ends.len()];
+            reach[chain[v]] = position[v];
+
+            for (next, _) in graph.neighbors(order[v]) {
+                for (best, &theirs) in reach.iter_mut().zip(earliest[index[next]].iter()) {
+                    *best = (*best).min(theirs);
+                }
+            }
+
+            earliest[v] = reach;
+        }
+
+        Ok(Self {
+            index,
+            chain,
+            position,
+            earliest,
+        })
+    }
+
+    // every vertex reaches itself. anything not in the graph reaches,
+    // and is reached by, nothing.
+    pub fn reaches(&self, from: &V, to: &V) -> bool {
+        match (self.index.get(from), self.index.get(to)) {
+            (Some(&from), Some(&to)) => self.earliest[from][self.chain[to]] <= self.position[to],
+            _ => false,
+        }
+    }
+
+    // how many chains the vertices were split into, which is how many
+    // numbers the index keeps for each vertex.
+    pub fn chain_count(&self) -> usize {
+        self.earliest.first().map_or(0, |reach| reach.len())
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use super::Graph;
+use crate::error::GraphError;
+use crate::random::Rng;
+
+// each edge is either up, down or (while searching) not yet decided.
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Up,
+    Down,
+    Unknown,
+}
+
+impl<V> Graph<V, f64>
+where
+    V: Hash + Eq,
+{
+    // here, the value of each edge is the probability that it exists
+    // (or equivalently, that the link it represents hasn't failed).
+    // it's estimated from the given number of random samples, so there
+    // has to be at least one.
+    pub fn reliability<R: Rng + ?Sized>(
+        &self,
+        source: &V,
+        target: &V,
+        samples: usize,
+        rng: &mut R,
+    ) -> Result<f64, GraphError> {
+        if samples == 0 {
+            return Err(GraphError::InvalidArgument(
+                "Reliability needs at least one sample.",
+            ));
+        }
+
+        if source == target {
+            return Ok(1.0);
+        }
+
+        let adjacency = self.edge_adjacency();
+        let mut states = vec![State::Down; self.edges.len()];
+        let mut connected = 0;
+
+        for _ in 0..samples {
+            for (state, edge) in states.iter_mut().zip(self.edges.iter()) {
+                *state = if rng.next_f64() < edge.value {
+                    State::Up
+                } else {
+                    State::Down
+                };
+            }
+
+            if reaches(&adjacency, &states, source, target, false) {
+                connected += 1;
+            }
+        }
+
+        Ok(connected as f64 / samples as f64)
+    }
+
+    pub fn exact_reliability(&self, source: &V, target: &V) -> Result<f64, GraphError> {
+        if source == target {
+            return Ok(1.0);
+        }
+
+        // the number of cases can double with every edge, so this is
+        // only really meant for small graphs. the pruning below makes
+        // it manageable on sparse graphs a fair bit bigger than this.
+        if self.edges.len() > 64 {
+            return Err(GraphError::Unsupported(
+                "Graph has too many edges for exact reliability.",
+            ));
+        }
+
+        let adjacency = self.edge_adjacency();
+        let mut states = vec![State::Unknown; self.edges.len()];
+
+        Ok(self.factor(&adjacency, &mut states, 0, source, target))
+    }
+
+    // the factoring method: decide one edge at a time, and stop as
+    // soon as the outcome no longer depends on the undecided edges.
+    fn factor(
+        &self,
+        adjacency: &HashMap<&V, Vec<(usize, &V)>>,
+        states: &mut Vec<State>,
+        next: usize,
+        source: &V,
+        target: &V,
+    ) -> f64 {
+        if reaches(adjacency, states, source, target, false) {
+            return 1.0;
+        }
+
+        if next == states.len() || !reaches(adjacency, states, source, target, true) {
+            return 0.0;
+        }
+
+        let probability = self.edges[next].value;
+
+        states[next] = State::Up;
+        let up = self.factor(adjacency, states, next + 1, source, target);
+        states[next] = State::Down;
+        let down = self.factor(adjacency, states, next + 1, source, target);
+        states[next] = State::Unknown;
+
+        probability * up + (1.0 - probability) * down
+    }
+
+    // the edges leading out of each vertex, along with their positions.
+    fn edge_adjacency(&self) -> HashMap<&V, Vec<(usize, &V)>> {
+        let mut adjacency: HashMap<&V, Vec<(usize, &V)>> = HashMap::new();
+
+        for (index, edge) in self.edges.iter().enumerate() {
+            adjacency
+                .entry(&edge.v1)
+                .or_default()
+                .push((index, &edge.v2));
+
+            if !edge.directed {
+                adjacency
+                    .entry(&edge.v2)
+                    .or_default()
+                    .push((index, &edge.v1));
+            }
+        }
+
+        adjacency
+    }
+}
+
+// a plain depth-first search along the edges that are up, optionally
+// treating undecided edges as being up too.
+fn reaches<V: Hash + Eq>(
+    adjacency: &HashMap<&V, Vec<(usize, &V)>>,
+    states: &[State],
+    source: &V,
+    target: &V,
+    optimistic: bool,
+) -> bool {
+    let mut visited: HashSet<&V> = HashSet::new();
+    let mut stack = vec![source];
+
+    while let Some(vertex) = stack.pop() {
+        if vertex == target {
+            return true;
+        }
+
+        if !visited.insert(vertex) {
+            continue;
+        }
+
+        for &(index, neighbor) in adjacency.get(vertex).into_iter().flatten() {
+            let usable = match states[index] {
+                State::Up => true,
+                State::Down => false,
+                State::Unknown => optimistic,
+            };
+
+            if usable && !visited.contains(neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    false
+}
@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use super::Graph;
+use crate::random::Rng;
+
+// how many attempts may be made per requested swap before giving up,
+// since some graphs (like stars) barely allow any swaps at all.
+const ATTEMPTS_PER_SWAP: usize = 10;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+{
+    pub fn rewire_preserving_degrees<R: Rng + ?Sized>(
+        &mut self,
+        swaps: usize,
+        rng: &mut R,
+    ) -> usize {
+        // a double edge swap takes edges a-b and c-d and turns them into
+        // a-d and c-b (or a-c and b-d), which leaves every degree as it
+        // was. swaps that would create a loop or a parallel edge are
+        // rejected, so a simple graph stays simple. each edge keeps its
+        // own value. returns how many swaps actually happened.
+        if self.edges.len() < 2 {
+            return 0;
+        }
+
+        let mut present: HashSet<(V, V)> = HashSet::new();
+
+        for edge in self.edges.iter() {
+            present.insert((edge.v1.clone(), edge.v2.clone()));
+            present.insert((edge.v2.clone(), edge.v1.clone()));
+        }
+
+        let mut performed = 0;
+        let mut attempts = 0;
+
+        while performed < swaps && attempts < swaps * ATTEMPTS_PER_SWAP {
+            attempts += 1;
+
+            let first = rng.below(self.edges.len());
+            let second = rng.below(self.edges.len());
+            if first == second {
+                continue;
+            }
+
+            let (a, b) = (&self.edges[first].v1, &self.edges[first].v2);
+            let (mut c, mut d) = (&self.edges[second].v1, &self.edges[second].v2);
+
+            if rng.below(2) == 1 {
+                std::mem::swap(&mut c, &mut d);
+            }
+
+            let loops = a == d || c == b;
+            let pair = |x: &V, y: &V| (x.clone(), y.clone());
+            let parallel = present.contains(&pair(a, d)) || present.contains(&pair(c, b));
+
+            if loops || parallel {
+                continue;
+            }
+
+            let (a, b, c, d) = (a.clone(), b.clone(), c.clone(), d.clone());
+
+            for &(x, y) in [(&a, &b), (&c, &d)].iter() {
+                present.remove(&pair(x, y));
+                present.remove(&pair(y, x));
+            }
+
+            for &(x, y) in [(&a, &d), (&c, &b)].iter() {
+                present.insert(pair(x, y));
+                present.insert(pair(y, x));
+            }
+
+            self.edges[first].v2 = d;
+            self.edges[second].v1 = c;
+            self.edges[second].v2 = b;
+            performed += 1;
+        }
+
+        // the edges have moved, so which vertex they touch has to be
+        // worked out again.
+        let edges = std::mem::take(&mut self.edges);
+        self.incident.clear();
+
+        for edge in edges {
+            self.push_edge(edge);
+        }
+
+        performed
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use super::{Edge, Graph};
+
+// a rewriting rule: wherever the pattern turns up, it's replaced by the
+// replacement. the two share their vertex labels, so a label in both
+// is a vertex that stays (along with any edges of its that weren't in
+// the pattern), a label only in the pattern is a vertex that goes
+// (along with all of its edges), and a label only in the replacement
+// is a new vertex. the pattern's edges are all taken out, and the
+// replacement's are all put in.
+pub struct Rule<L: Hash + Eq, E> {
+    pattern: Graph<L, E>,
+    replacement: Graph<L, E>,
+}
+
+impl<L, E> Rule<L, E>
+where
+    L: Hash + Eq,
+{
+    pub fn new(pattern: Graph<L, E>, replacement: Graph<L, E>) -> Self {
+        Self {
+            pattern,
+            replacement,
+        }
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: PartialEq,
+{
+    // somewhere the pattern appears in the graph, as which vertex each
+    // of its labels stands for. different labels stand for different
+    // vertices, and every edge in the pattern needs an edge of its own
+    // going the same way, with an equal value, though the graph can
+    // have other edges as well. which match it finds, when there's more
+    // than one, is up to the order the vertices come in.
+    pub fn find_subgraph<'p, L>(&self, pattern: &'p Graph<L, E>) -> Option<HashMap<&'p L, &V>>
+    where
+        L: Hash + Eq,
+    {
+        let (image, _) = Matcher::new(self, pattern).run()?;
+        Some(image)
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: PartialEq + Clone,
+{
+    // keeps rewriting the graph with the first rule that matches, until
+    // none of them do or it's made the given number of rewrites, and
+    // gives back how many it made. rules can easily keep matching
+    // forever, hence the limit. new vertices come from calling fresh,
+    // which has to give something that isn't in the graph already.
+    pub fn apply_rules<L, F>(
+        &mut self,
+        rules: &[Rule<L, E>],
+        max_steps: usize,
+        mut fresh: F,
+    ) -> usize
+    where
+        L: Hash + Eq,
+        F: FnMut() -> V,
+    {
+        for step in 0..max_steps {
+            let found = rules.iter().find_map(|rule| {
+                let (image, edges) = Matcher::new(self, &rule.pattern).run()?;
+                let image: HashMap<&L, V> = image
+                    .into_iter()
+                    .map(|(label, vertex)| (label, vertex.clone()))
+                    .collect();
+
+                Some((rule, image, edges))
+            });
+
+            let (rule, mut image, mut edges) = match found {
+                Some(found) => found,
+                None => return step,
+            };
+
+            edges.sort_unstable();
+            self.remove_edges(&edges);
+
+            for label in rule.pattern.vertices.iter() {
+                if !rule.replacement.contains(label) {
+                    self.remove_vertex(&image[label]);
+                }
+            }
+
+            for label in rule.replacement.vertices.iter() {
+                if !image.contains_key(label) {
+                    let vertex = fresh();
+                    self.add_vertex(vertex.clone());
+                    image.insert(label, vertex);
+                }
+            }
+
+            // outside a multigraph, an edge that's already there stays
+            // as it is.
+            for edge in rule.replacement.edges.iter() {
+                let (v1, v2) = (&image[&edge.v1], &image[&edge.v2]);
+                let value = edge.value.clone();
+
+                let _ = if edge.directed {
+                    self.connect_directed(v1, v2, value)
+                } else {
+                    self.connect_vertices(v1, v2, value)
+                };
+            }
+        }
+
+        max_steps
+    }
+}
+
+// a backtracking search for the pattern, giving each pattern vertex a
+// graph vertex in turn, and each pattern edge the position of a graph
+// edge as soon as both its ends have one.
+struct Matcher<'h, 'p, V: Hash + Eq, L: Hash + Eq, E> {
+    host: &'h Graph<V, E>,
+    pattern: &'p Graph<L, E>,
+    order: Vec<&'p L>,
+    image: HashMap<&'p L, &'h V>,
+    taken: HashSet<&'h V>,
+    used: Vec<usize>,
+}
+
+impl<'h, 'p, V, L, E> Matcher<'h, 'p, V, L, E>
+where
+    V: Hash + Eq,
+    L: Hash + Eq,
+    E: PartialEq,
+{
+    // the pattern vertices are gone through breadth first, so nearly
+    // all of them have a neighbour placed already, and only the graph
+    // vertices next to that neighbour's need trying.
+    fn new(host: &'h Graph<V, E>, pattern: &'p Graph<L, E>) -> Self {
+        let mut order: Vec<&L> = Vec::new();
+        let mut seen: HashSet<&L> = HashSet::new();
+
+        for root in pattern.vertices.iter() {
+            if !seen.insert(root) {
+                continue;
+            }
+
+            let mut queue = VecDeque::new();
+            queue.push_back(root);
+
+            while let Some(label) = queue.pop_front() {
+                order.push(label);
+
+                for edge in pattern.incident_edges(label) {
+                    for other in [&edge.v1, &edge.v2].iter() {
+                        let other = pattern.vertices.get(*other).unwrap();
+
+                        if seen.insert(other) {
+                            queue.push_back(other);
+                        }
+                    }
+                }
+            }
+        }
+
+        Self {
+            host,
+            pattern,
+            order,
+            image: HashMap::new(),
+            taken: HashSet::new(),
+            used: Vec::new(),
+        }
+    }
+
+    fn run(mut self) -> Option<(HashMap<&'p L, &'h V>, Vec<usize>)> {
+        if self.order.len() > self.host.vertices.len() || !self.extend(0) {
+            return None;
+        }
+
+        Some((self.image, self.used))
+    }
+
+    // patterns are small, so recursing once per pattern vertex is fine.
+    fn extend(&mut self, depth: usize) -> bool {
+        let label = match self.order.get(depth) {
+            Some(&label) => label,
+            None => return true,
+        };
+
+        let placed = self
+            .pattern
+            .incident_edges(label)
+            .flat_map(|edge| [&edge.v1, &edge.v2])
+            .find_map(|other| self.image.get(other).cloned());
+
+        let candidates: Vec<&'h V> = match placed {
+            Some(near) => {
+                let mut seen = HashSet::new();
+                self.host
+                    .incident_edges(near)
+                    .flat_map(|edge| [&edge.v1, &edge.v2])
+                    .filter(|&vertex| seen.insert(vertex))
+                    .collect()
+            }
+            None => self.host.vertices.iter().collect(),
+        };
+
+        for candidate in candidates {
+            if self.taken.contains(candidate) {
+                continue;
+            }
+
+            let before = self.used.len();
+            self.image.insert(label, candidate);
+            self.taken.insert(candidate);
+
+            if self.place_edges(label) && self.extend(depth + 1) {
+                return true;
+            }
+
+            self.used.truncate(before);
+            self.image.remove(label);
+            self.taken.remove(candidate);
+        }
+
+        false
+    }
+
+    // finds an unused graph edge for every pattern edge between the
+    // newly placed vertex and the ones placed before it. what can stand
+    // in for a pattern edge only depends on its value and which way it
+    // goes, so taking the first one that fits can't rule out a match.
+    fn place_edges(&mut self, label: &L) -> bool {
+        let pattern = self.pattern;
+
+        for edge in pattern.incident_edges(label) {
+            let (a, b) = match (self.image.get(&edge.v1), self.image.get(&edge.v2)) {
+                (Some(&a), Some(&b)) => (a, b),
+                _ => continue,
+            };
+
+            let fits = |other: &Edge<V, E>| {
+                other.directed == edge.directed
+                    && other.value == edge.value
+                    && ((other.v1 == *a && other.v2 == *b)
+                        || (!edge.directed && other.v1 == *b && other.v2 == *a))
+            };
+
+            let position = self
+                .host
+                .incident
+                .get(a)
+                .into_iter()
+                .flatten()
+                .cloned()
+                .find(|&i| !self.used.contains(&i) && fits(&self.host.edges[i]));
+
+            match position {
+                Some(i) => self.used.push(i),
+                None => return false,
+            }
+        }
+
+        true
+    }
+}
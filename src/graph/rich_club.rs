@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::Graph;
+use crate::random::Rng;
+
+// each randomised copy gets this many swaps per edge, which is the
+// usual rule of thumb for mixing the edges thoroughly.
+const SWAPS_PER_EDGE: usize = 10;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    pub fn rich_club_coefficient(&self, k: usize) -> Option<f64> {
+        // the fraction of possible edges that actually exist among the
+        // vertices of degree greater than k. it's undefined when there
+        // are fewer than two such vertices.
+        let mut degrees: HashMap<&V, usize> = HashMap::new();
+
+        for edge in self.edges.iter() {
+            *degrees.entry(&edge.v1).or_default() += 1;
+            *degrees.entry(&edge.v2).or_default() += 1;
+        }
+
+        let rich = |vertex: &V| degrees.get(vertex).is_some_and(|&d| d > k);
+        let members = self.vertices.iter().filter(|v| rich(v)).count();
+
+        if members < 2 {
+            return None;
+        }
+
+        let links = self
+            .edges
+            .iter()
+            .filter(|edge| edge.v1 != edge.v2 && rich(&edge.v1) && rich(&edge.v2))
+            .count();
+
+        Some(2.0 * links as f64 / (members * (members - 1)) as f64)
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Clone,
+{
+    pub fn normalized_rich_club_coefficient<R: Rng + ?Sized>(
+        &self,
+        k: usize,
+        randomizations: usize,
+        rng: &mut R,
+    ) -> Option<f64> {
+        // high degree vertices are more likely to be linked to each
+        // other by chance alone, so the raw coefficient is compared to
+        // its average over random graphs with the same degrees.
+        let actual = self.rich_club_coefficient(k)?;
+        let mut total = 0.0;
+
+        for _ in 0..randomizations {
+            let mut random = self.clone();
+            random.rewire_preserving_degrees(SWAPS_PER_EDGE * self.edges.len(), rng);
+            total += random.rich_club_coefficient(k)?;
+        }
+
+        let expected = total / randomizations as f64;
+
+        if expected > 0.0 {
+            Some(actual / expected)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::Graph;
+use crate::error::GraphError;
+
+// where a token is: sitting at a vertex, or part of the way along an
+// edge, with how many more ticks until it gets to the other end.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Position<'g, V> {
+    At(&'g V),
+    Moving {
+        from: &'g V,
+        to: &'g V,
+        remaining: u64,
+    },
+}
+
+// what happened to a token during a tick, in the order it happened.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Event<'g, V> {
+    Departed {
+        token: usize,
+        from: &'g V,
+        to: &'g V,
+    },
+    Arrived {
+        token: usize,
+        at: &'g V,
+    },
+    // the next vertex on its route was full, so it's waiting.
+    Blocked {
+        token: usize,
+        at: &'g V,
+        next: &'g V,
+    },
+    // it's got to the end of its route.
+    Finished {
+        token: usize,
+        at: &'g V,
+    },
+}
+
+// these only hold references, so they can be copied whatever V is (which
+// deriving wouldn't allow).
+impl<'g, V> Clone for Position<'g, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'g, V> Copy for Position<'g, V> {}
+
+impl<'g, V> Clone for Event<'g, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'g, V> Copy for Event<'g, V> {}
+
+struct Token<'g, V> {
+    position: Position<'g, V>,
+    route: VecDeque<&'g V>,
+}
+
+// tokens moving around a graph one tick at a time, like vehicles on a
+// road network or agents on a map. each token follows its own route,
+// taking however many ticks the duration function says to cross each
+// edge (at least one). a vertex can be given a capacity, and a token
+// heading for a vertex counts towards it from the moment it leaves, so
+// a vertex is never over capacity. tokens are numbered from zero in
+// the order they were added, and take their turns in that order.
+pub struct Simulation<'g, V: Hash + Eq, E, F> {
+    graph: &'g Graph<V, E>,
+    duration: F,
+    capacity: HashMap<&'g V, usize>,
+    tokens: Vec<Token<'g, V>>,
+    tick: u64,
+}
+
+impl<'g, V, E, F> Simulation<'g, V, E, F>
+where
+    V: Hash + Eq,
+    F: Fn(&E) -> u64,
+{
+    pub fn new(graph: &'g Graph<V, E>, duration: F) -> Self {
+        Self {
+            graph,
+            duration,
+            capacity: HashMap::new(),
+            tokens: Vec::new(),
+            tick: 0,
+        }
+    }
+
+    // how many tokens can be at (or on their way to) a vertex at once.
+    // vertices are unlimited until they're given one.
+    pub fn set_capacity(&mut self, vertex: &V, capacity: usize) -> Result<(), GraphError> {
+        let vertex = self
+            .graph
+            .vertices
+            .get(vertex)
+            .ok_or(GraphError::VertexNotFound)?;
+
+        self.capacity.insert(vertex, capacity);
+        Ok(())
+    }
+
+    pub fn add_token(&mut self, at: &V) -> Result<usize, GraphError> {
+        let at = self
+            .graph
+            .vertices
+            .get(at)
+            .ok_or(GraphError::VertexNotFound)?;
+
+        if !self.has_room(at) {
+            return Err(GraphError::Infeasible("Vertex is already at capacity."));
+        }
+
+        self.tokens.push(Token {
+            position: Position::At(at),
+            route: VecDeque::new(),
+        });
+
+        Ok(self.tokens.len() - 1)
+    }
+
+    // gives a token a new route, starting with the vertex it's at (or,
+    // if it's moving, the one it's heading for), like the paths from
+    // dijkstra_path. every step has to follow an edge.
+    pub fn set_route(&mut self, token: usize, route: &[&V]) -> Result<(), GraphError> {
+        let current = match self.position(token) {
+            Some(Position::At(at)) => at,
+            Some(Position::Moving { to, .. }) => to,
+            None => return Err(GraphError::InvalidArgument("No such token.")),
+        };
+
+        if route.first() != Some(&current) {
+            return Err(GraphError::InvalidPath(
+                "Route must start where the token is.",
+            ));
+        }
+
+        let mut steps = VecDeque::new();
+
+        for pair in route.windows(2) {
+            if self.graph.value_between(pair[0], pair[1]).is_none() {
+                return Err(GraphError::EdgeNotFound);
+            }
+
+            steps.push_back(self.graph.vertices.get(pair[1]).unwrap());
+        }
+
+        self.tokens[token].route = steps;
+        Ok(())
+    }
+
+    pub fn position(&self, token: usize) -> Option<Position<'g, V>> {
+        self.tokens.get(token).map(|token| token.position)
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    // whether every token has got to the end of its route.
+    pub fn is_idle(&self) -> bool {
+        self.tokens
+            .iter()
+            .all(|token| token.route.is_empty() && matches!(token.position, Position::At(_)))
+    }
+
+    // runs a single tick: tokens on edges move along them, and then
+    // any token at a vertex with more of its route to go sets off, if
+    // there's room where it's going.
+    pub fn step(&mut self) -> Vec<Event<'g, V>> {
+        self.tick += 1;
+        let mut events = Vec::new();
+
+        for (i, token) in self.tokens.iter_mut().enumerate() {
+            if let Position::Moving {
+                to, mut remaining, ..
+            } = token.position
+            {
+                remaining -= 1;
+
+                if remaining > 0 {
+                    if let Position::Moving {
+                        remaining: left, ..
+                    } = &mut token.position
+                    {
+                        *left = remaining;
+                    }
+
+                    continue;
+                }
+
+                token.position = Position::At(to);
+                events.push(Event::Arrived { token: i, at: to });
+
+                if token.route.is_empty() {
+                    events.push(Event::Finished { token: i, at: to });
+                }
+            }
+        }
+
+        for i in 0..self.tokens.len() {
+            let (at, next) = match (self.tokens[i].position, self.tokens[i].route.front()) {
+                (Position::At(at), Some(&next)) => (at, next),
+                _ => continue,
+            };
+
+            if !self.has_room(next) {
+                events.push(Event::Blocked { token: i, at, next });
+                continue;
+            }
+
+            // with parallel edges, tokens take whichever is quickest.
+            let duration = self
+                .graph
+                .edges_between(at, next)
+                .into_iter()
+                .map(|value| (self.duration)(value))
+                .min()
+                .unwrap();
+            let token = &mut self.tokens[i];
+            token.route.pop_front();
+            token.position = Position::Moving {
+                from: at,
+                to: next,
+                remaining: duration.max(1),
+            };
+
+            events.push(Event::Departed {
+                token: i,
+                from: at,
+                to: next,
+            });
+        }
+
+        events
+    }
+
+    // keeps stepping until every token has finished, or until the tick
+    // limit, since tokens blocking each other can wait forever.
+    pub fn run(&mut self, max_ticks: u64) -> Vec<(u64, Event<'g, V>)> {
+        let mut events = Vec::new();
+
+        for _ in 0..max_ticks {
+            if self.is_idle() {
+                break;
+            }
+
+            let tick = self.tick + 1;
+            events.extend(self.step().into_iter().map(|event| (tick, event)));
+        }
+
+        events
+    }
+
+    fn has_room(&self, vertex: &V) -> bool {
+        let capacity = match self.capacity.get(vertex) {
+            Some(&capacity) => capacity,
+            None => return true,
+        };
+
+        let occupying = self
+            .tokens
+            .iter()
+            .filter(|token| match token.position {
+                Position::At(at) => at == vertex,
+                Position::Moving { to, .. } => to == vertex,
+            })
+            .count();
+
+        occupying < capacity
+    }
+}
+
+impl<'g, V, E, F> Simulation<'g, V, E, F>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+    F: Fn(&E) -> u64,
+{
+    // routes the token along a shortest path to the target.
+    pub fn send_to(&mut self, token: usize, target: &V) -> Result<(), GraphError> {
+        let current = match self.position(token) {
+            Some(Position::At(at)) => at,
+            Some(Position::Moving { to, .. }) => to,
+            None => return Err(GraphError::InvalidArgument("No such token.")),
+        };
+
+        if current == target {
+            self.tokens[token].route.clear();
+            return Ok(());
+        }
+
+        let (path, _) = self
+            .graph
+            .dijkstra_path(current, target)
+            .ok_or(GraphError::Unreachable)?;
+
+        self.set_route(token, &path)
+    }
+}
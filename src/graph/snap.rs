@@ -0,0 +1,90 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::BufRead;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::Graph;
+use crate::error::GraphError;
+use crate::gzip;
+
+// the edge lists that snap and konect publish their networks as. each
+// line is a pair of integer vertex ids, optionally followed by a weight
+// (anything after that, like konect's timestamps, is ignored). lines
+// starting with # (snap) or % (konect) are headers and comments. edges
+// without a weight get a weight of one. when the edges are directed,
+// they go from the first id to the second. otherwise, datasets that
+// list both a-b and b-a only keep the first. either way, an edge that's
+// listed more than once is only kept the first time, and loops are
+// skipped since they make no difference to distances.
+impl<E: FromStr> Graph<u64, E> {
+    pub fn from_snap<R: BufRead>(reader: R, directed: bool) -> Result<Self, GraphError> {
+        let mut graph = Graph::empty();
+        let mut seen: HashSet<(u64, u64)> = HashSet::new();
+
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') || line.starts_with('%') {
+                continue;
+            }
+
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+
+            if tokens.len() < 2 {
+                return Err(GraphError::syntax(number + 1, "Edge needs two vertex ids."));
+            }
+
+            let id = |token: &str| {
+                token
+                    .parse::<u64>()
+                    .map_err(|_| GraphError::syntax(number + 1, "Vertex id could not be parsed."))
+            };
+
+            let (a, b) = (id(tokens[0])?, id(tokens[1])?);
+            let weight =
+                tokens.get(2).cloned().unwrap_or("1").parse().map_err(|_| {
+                    GraphError::syntax(number + 1, "Edge weight could not be parsed.")
+                })?;
+
+            for &vertex in [a, b].iter() {
+                if !graph.vertices.contains(&vertex) {
+                    graph.add_vertex(vertex);
+                }
+            }
+
+            let pair = if directed {
+                (a, b)
+            } else {
+                (a.min(b), a.max(b))
+            };
+
+            if a == b || !seen.insert(pair) {
+                continue;
+            }
+
+            // both ends were just added, so connecting them can't fail.
+            if directed {
+                graph.connect_directed(&a, &b, weight).unwrap();
+            } else {
+                graph.connect_vertices(&a, &b, weight).unwrap();
+            }
+        }
+
+        Ok(graph)
+    }
+
+    // reads a dataset straight from its file, decompressing it first if
+    // it's gzipped (which most of them are).
+    pub fn load_snap<P: AsRef<Path>>(path: P, directed: bool) -> Result<Self, GraphError> {
+        let bytes = fs::read(path)?;
+
+        if gzip::is_gzip(&bytes) {
+            let bytes = gzip::gunzip(&bytes)?;
+            Self::from_snap(&bytes[..], directed)
+        } else {
+            Self::from_snap(&bytes[..], directed)
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use super::frontier::Frontier;
+use super::Graph;
+use crate::error::GraphError;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Into<f64> + Clone,
+{
+    // a subgraph where no shortest distance is more than stretch times
+    // what it was, using as few edges as the greedy algorithm (althöfer
+    // et al.) manages. edges are tried from lightest to heaviest, and
+    // one is only kept if the spanner so far can't already get between
+    // its ends within stretch times its weight. a stretch of 2k - 1
+    // leaves o(n^(1 + 1/k)) edges, so even 3 usually thins a dense
+    // graph out a lot.
+    pub fn spanner(&self, stretch: f64) -> Result<Graph<V, E>, GraphError> {
+        if stretch.is_nan() || stretch < 1.0 {
+            return Err(GraphError::InvalidArgument(
+                "Spanner stretch must be at least 1.",
+            ));
+        }
+
+        let index: HashMap<&V, usize> = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, vertex)| (vertex, i))
+            .collect();
+
+        let weights: Vec<f64> = self
+            .edges
+            .iter()
+            .map(|edge| edge.value.clone().into())
+            .collect();
+
+        let mut order: Vec<usize> = (0..self.edges.len()).collect();
+        order.sort_by(|&a, &b| {
+            weights[a]
+                .partial_cmp(&weights[b])
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let mut adjacent: Vec<Vec<(usize, f64)>> = vec![Vec::new(); index.len()];
+        let mut kept = vec![false; self.edges.len()];
+
+        for i in order {
+            let edge = &self.edges[i];
+            let (from, to) = (index[&edge.v1], index[&edge.v2]);
+
+            // a loop never gets anywhere.
+            if from == to {
+                continue;
+            }
+
+            if within(&adjacent, from, to, stretch * weights[i]) {
+                continue;
+            }
+
+            kept[i] = true;
+            adjacent[from].push((to, weights[i]));
+
+            if !edge.directed {
+                adjacent[to].push((from, weights[i]));
+            }
+        }
+
+        let edges = self
+            .edges
+            .iter()
+            .zip(kept)
+            .filter(|(_, kept)| *kept)
+            .map(|(edge, _)| edge.clone())
+            .collect();
+
+        Ok(Graph::from_parts(self.vertices.clone(), edges))
+    }
+}
+
+// whether there's a path from one vertex to another no longer than the
+// limit, searching no further than it has to.
+fn within(adjacent: &[Vec<(usize, f64)>], from: usize, to: usize, limit: f64) -> bool {
+    let mut frontier = Frontier::new();
+    let mut settled = HashSet::new();
+    frontier.improve(from, 0.0);
+
+    while let Some((vertex, dist)) = frontier.pop() {
+        if dist > limit {
+            return false;
+        }
+
+        if vertex == to {
+            return true;
+        }
+
+        settled.insert(vertex);
+
+        for &(next, weight) in adjacent[vertex].iter() {
+            if !settled.contains(&next) {
+                frontier.improve(next, dist + weight);
+            }
+        }
+    }
+
+    false
+}
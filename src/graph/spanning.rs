@@ -0,0 +1,55 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::disjoint::DisjointSets;
+use super::Graph;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    // the lightest set of edges that still connects everything that was
+    // connected, along with its total weight (none if there aren't any
+    // edges in it). this is kruskal's algorithm: edges are taken from
+    // lightest to heaviest, skipping any whose ends are already joined.
+    // a disconnected graph gives a forest, edges are treated as two-way
+    // whatever their direction, and loops are never part of it.
+    pub fn minimum_spanning_tree(&self) -> (Graph<V, E>, Option<E>) {
+        let index: HashMap<&V, usize> = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, vertex)| (vertex, i))
+            .collect();
+
+        let mut order: Vec<usize> = (0..self.edges.len()).collect();
+        order.sort_by(|&a, &b| {
+            self.edges[a]
+                .value
+                .partial_cmp(&self.edges[b].value)
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let mut pieces = DisjointSets::new(index.len());
+        let mut edges = Vec::new();
+        let mut weight: Option<E> = None;
+
+        for i in order {
+            let edge = &self.edges[i];
+
+            if pieces.union(index[&edge.v1], index[&edge.v2]) {
+                weight = match weight {
+                    Some(total) => Some(total + edge.value.clone()),
+                    None => Some(edge.value.clone()),
+                };
+
+                edges.push(edge.clone());
+            }
+        }
+
+        (Graph::from_parts(self.vertices.clone(), edges), weight)
+    }
+}
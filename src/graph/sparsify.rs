@@ -0,0 +1,219 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use super::{Edge, Graph};
+use crate::error::GraphError;
+use crate::random::Rng;
+
+// how many random projections to make per factor of e in the number of
+// vertices, and how much to oversample each edge by. these are the
+// constants the laplacians.jl package settled on, which in practice
+// work far better than the ones the proofs need.
+const PROJECTIONS: f64 = 4.0;
+const OVERSAMPLING: f64 = 4.0;
+
+// when conjugate gradients counts as having converged, relative to the
+// size of the right hand side.
+const TOLERANCE: f64 = 1e-8;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Into<f64> + Clone,
+{
+    // a reweighted subgraph whose laplacian is close to this one's, so
+    // every cut (and the spectrum) comes out within about a factor of
+    // 1 ± epsilon. this is spielman and srivastava's sampling by
+    // effective resistance: an edge that's the only good way between
+    // its ends gets kept, while one of many parallel routes can go.
+    // each edge is kept with a probability that grows with its weight
+    // times its resistance, then scaled up by one over it so that the
+    // laplacian is right on average. the resistances are estimated with
+    // random projections, each needing a laplacian solve.
+    //
+    // sparsifying only pays off on fairly dense graphs, since about
+    // n log n / epsilon^2 edges are kept. edges are treated as
+    // two-way, loops are dropped, and every weight has to be positive.
+    pub fn sparsify<R: Rng + ?Sized>(
+        &self,
+        epsilon: f64,
+        rng: &mut R,
+    ) -> Result<Graph<V, f64>, GraphError> {
+        if epsilon.is_nan() || epsilon <= 0.0 || epsilon > 1.0 {
+            return Err(GraphError::InvalidArgument(
+                "Sparsifying needs an epsilon between 0 and 1.",
+            ));
+        }
+
+        let mut vertices: Vec<&V> = self.vertices.iter().collect();
+        vertices.sort_by_key(|&vertex| {
+            let mut hasher = DefaultHasher::new();
+            vertex.hash(&mut hasher);
+            hasher.finish()
+        });
+
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let mut edges: Vec<(usize, usize, f64, &Edge<V, E>)> = Vec::new();
+
+        for edge in self.edges.iter() {
+            let (a, b) = (index[&edge.v1], index[&edge.v2]);
+            let weight: f64 = edge.value.clone().into();
+
+            if weight.is_nan() || weight <= 0.0 {
+                return Err(GraphError::Unsupported(
+                    "Sparsifying needs positive edge weights.",
+                ));
+            }
+
+            if a != b {
+                edges.push((a, b, weight, edge));
+            }
+        }
+
+        let laplacian = Laplacian::new(vertices.len(), &edges);
+        let log_n = (vertices.len().max(2) as f64).ln();
+        let projections = (PROJECTIONS * log_n).ceil() as usize;
+
+        // the resistance between a and b is the squared distance between
+        // their columns of w^(1/2) b l^+, which random projections (in
+        // the johnson-lindenstrauss sense) shrink to a few rows.
+        let mut resistance = vec![0.0; edges.len()];
+        let scale = 1.0 / (projections as f64).sqrt();
+
+        for _ in 0..projections {
+            let mut rhs = vec![0.0; vertices.len()];
+
+            for &(a, b, weight, _) in edges.iter() {
+                let sign = if rng.next_u64() & 1 == 0 {
+                    scale
+                } else {
+                    -scale
+                };
+                rhs[a] += sign * weight.sqrt();
+                rhs[b] -= sign * weight.sqrt();
+            }
+
+            let potentials = laplacian.solve(&rhs);
+
+            for (i, &(a, b, _, _)) in edges.iter().enumerate() {
+                let difference = potentials[a] - potentials[b];
+                resistance[i] += difference * difference;
+            }
+        }
+
+        let oversampling = OVERSAMPLING * log_n / (epsilon * epsilon);
+        let mut kept = Vec::new();
+
+        for (i, &(_, _, weight, edge)) in edges.iter().enumerate() {
+            let probability = (weight * resistance[i] * oversampling).min(1.0);
+
+            if rng.next_f64() < probability {
+                kept.push(Edge::new(
+                    edge.v1.clone(),
+                    edge.v2.clone(),
+                    weight / probability,
+                ));
+            }
+        }
+
+        Ok(Graph::from_parts(self.vertices.clone(), kept))
+    }
+}
+
+// a graph laplacian over numbered vertices, stored as each vertex's
+// weighted degree and its weighted neighbours.
+struct Laplacian {
+    degree: Vec<f64>,
+    adjacent: Vec<Vec<(usize, f64)>>,
+}
+
+impl Laplacian {
+    fn new<T>(count: usize, edges: &[(usize, usize, f64, T)]) -> Self {
+        let mut degree = vec![0.0; count];
+        let mut adjacent = vec![Vec::new(); count];
+
+        for &(a, b, weight, _) in edges.iter() {
+            degree[a] += weight;
+            degree[b] += weight;
+            adjacent[a].push((b, weight));
+            adjacent[b].push((a, weight));
+        }
+
+        Self { degree, adjacent }
+    }
+
+    fn apply(&self, x: &[f64]) -> Vec<f64> {
+        (0..x.len())
+            .map(|v| {
+                let around: f64 = self.adjacent[v].iter().map(|&(u, w)| w * x[u]).sum();
+                self.degree[v] * x[v] - around
+            })
+            .collect()
+    }
+
+    // conjugate gradients, preconditioned by the degrees. the laplacian
+    // is singular, but the right hand side always sums to zero over
+    // each connected piece, so the iterates stay where it's invertible.
+    fn solve(&self, rhs: &[f64]) -> Vec<f64> {
+        let count = rhs.len();
+        let precondition = |r: &[f64]| -> Vec<f64> {
+            (0..count)
+                .map(|v| {
+                    if self.degree[v] > 0.0 {
+                        r[v] / self.degree[v]
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        };
+        let dot = |a: &[f64], b: &[f64]| -> f64 { a.iter().zip(b).map(|(x, y)| x * y).sum() };
+
+        let mut x = vec![0.0; count];
+        let mut residual = rhs.to_vec();
+        let mut z = precondition(&residual);
+        let mut direction = z.clone();
+        let mut rz = dot(&residual, &z);
+        let limit = TOLERANCE * dot(rhs, rhs).sqrt();
+
+        // in exact arithmetic it would be done within n steps, but
+        // rounding can slow it down a little.
+        for _ in 0..2 * count {
+            if dot(&residual, &residual).sqrt() <= limit {
+                break;
+            }
+
+            let applied = self.apply(&direction);
+            let curvature = dot(&direction, &applied);
+
+            if curvature <= 0.0 {
+                break;
+            }
+
+            let step = rz / curvature;
+            for (x, d) in x.iter_mut().zip(direction.iter()) {
+                *x += step * d;
+            }
+            for (r, a) in residual.iter_mut().zip(applied.iter()) {
+                *r -= step * a;
+            }
+
+            z = precondition(&residual);
+            let next_rz = dot(&residual, &z);
+
+            for (d, z) in direction.iter_mut().zip(z.iter()) {
+                *d = z + next_rz / rz * *d;
+            }
+
+            rz = next_rz;
+        }
+
+        x
+    }
+}
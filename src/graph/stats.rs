@@ -0,0 +1,107 @@
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::Graph;
+
+// a summary of the graph's shape. parallel edges are the ones that go
+// the same way between the same two vertices as an edge added earlier,
+// so a graph without any could be built with set_multigraph(false).
+// the degrees count loops twice, as both of their ends touch the
+// vertex, and the mean is none for a graph without any vertices.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats {
+    pub vertices: usize,
+    pub edges: usize,
+    pub directed_edges: usize,
+    pub loops: usize,
+    pub parallel_edges: usize,
+    pub isolated_vertices: usize,
+    pub min_degree: usize,
+    pub max_degree: usize,
+    pub mean_degree: Option<f64>,
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    // how many edge ends touch the vertex, whichever way the edges go.
+    pub fn degree(&self, vertex: &V) -> usize {
+        self.incident_edges(vertex)
+            .map(|edge| if edge.v1 == edge.v2 { 2 } else { 1 })
+            .sum()
+    }
+
+    // how many edges can be followed out of the vertex, which counts
+    // undirected edges as well as directed ones that start there.
+    pub fn out_degree(&self, vertex: &V) -> usize {
+        self.neighbors(vertex).len()
+    }
+
+    // and how many can be followed into it.
+    pub fn in_degree(&self, vertex: &V) -> usize {
+        self.predecessors(vertex).len()
+    }
+
+    pub fn stats(&self) -> GraphStats {
+        let degrees: Vec<usize> = self.vertices.iter().map(|v| self.degree(v)).collect();
+
+        let parallel_edges = self
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|&(i, edge)| {
+                self.incident[&edge.v1]
+                    .iter()
+                    .take_while(|&&j| j < i)
+                    .map(|&j| &self.edges[j])
+                    .any(|other| {
+                        other.leads(&edge.v1, &edge.v2)
+                            || (!edge.directed && other.leads(&edge.v2, &edge.v1))
+                    })
+            })
+            .count();
+
+        GraphStats {
+            vertices: self.vertices.len(),
+            edges: self.edges.len(),
+            directed_edges: self.edges.iter().filter(|edge| edge.directed).count(),
+            loops: self.edges.iter().filter(|edge| edge.v1 == edge.v2).count(),
+            parallel_edges,
+            isolated_vertices: degrees.iter().filter(|&&degree| degree == 0).count(),
+            min_degree: degrees.iter().cloned().min().unwrap_or(0),
+            max_degree: degrees.iter().cloned().max().unwrap_or(0),
+            mean_degree: if degrees.is_empty() {
+                None
+            } else {
+                Some(degrees.iter().sum::<usize>() as f64 / degrees.len() as f64)
+            },
+        }
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + Clone,
+{
+    // every edge's value added up, or none without any edges, since
+    // there's no zero for E to give.
+    pub fn total_weight(&self) -> Option<E> {
+        self.edges
+            .iter()
+            .map(|edge| edge.value.clone())
+            .fold(None, |total, value| match total {
+                Some(total) => Some(total + value),
+                None => Some(value),
+            })
+    }
+}
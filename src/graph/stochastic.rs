@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::pareto::Criteria;
+use super::Graph;
+use crate::error::GraphError;
+
+// an edge weight that isn't known exactly, described by the mean and
+// variance of its distribution. edge weights are assumed to be
+// independent, so both moments simply add up along a path.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Uncertain {
+    pub mean: f64,
+    pub variance: f64,
+}
+
+impl Uncertain {
+    pub fn new(mean: f64, variance: f64) -> Self {
+        Self { mean, variance }
+    }
+
+    // with no samples there's nothing to estimate the mean from.
+    pub fn from_samples(samples: &[f64]) -> Result<Self, GraphError> {
+        if samples.is_empty() {
+            return Err(GraphError::InvalidArgument(
+                "An uncertain weight needs at least one sample.",
+            ));
+        }
+
+        let count = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / count;
+
+        // use the unbiased estimator, unless there's only one sample
+        // to go on (in which case we have no idea about the spread).
+        let variance = if samples.len() > 1 {
+            let squares: f64 = samples.iter().map(|s| (s - mean).powi(2)).sum();
+            squares / (count - 1.0)
+        } else {
+            0.0
+        };
+
+        Ok(Self { mean, variance })
+    }
+
+    // the probability of the weight being at most the given value,
+    // treating the distribution as normal.
+    pub fn probability_within(&self, limit: f64) -> f64 {
+        if self.variance <= 0.0 {
+            return if self.mean <= limit { 1.0 } else { 0.0 };
+        }
+
+        let z = (limit - self.mean) / (2.0 * self.variance).sqrt();
+        0.5 * (1.0 + erf(z))
+    }
+}
+
+impl Add for Uncertain {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            mean: self.mean + other.mean,
+            variance: self.variance + other.variance,
+        }
+    }
+}
+
+// a lower mean and a lower variance are both preferable, at least as
+// long as the deadline is later than the mean travel time.
+impl Criteria for Uncertain {
+    fn combine(&self, other: &Self) -> Self {
+        *self + *other
+    }
+
+    fn dominates(&self, other: &Self) -> bool {
+        self.mean <= other.mean && self.variance <= other.variance
+    }
+}
+
+// abramowitz and stegun's approximation 7.1.26, which is accurate to
+// within about 1.5e-7. that's plenty for comparing probabilities.
+fn erf(x: f64) -> f64 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let polynomial = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+
+    sign * (1.0 - polynomial * (-x * x).exp())
+}
+
+impl<V> Graph<V, Uncertain>
+where
+    V: Hash + Eq,
+{
+    pub fn expected_shortest_paths(&self, source: &V) -> HashMap<&V, f64> {
+        // by linearity of expectation, the expected length of a path
+        // is the sum of the expected lengths of its edges, so this is
+        // just dijkstra over the means.
+        self.dijkstra_by(source, |edge| edge.mean, None)
+    }
+
+    pub fn most_reliable_path(
+        &self,
+        source: &V,
+        target: &V,
+        deadline: f64,
+    ) -> Option<(Vec<&V>, f64)> {
+        // the chance of arriving on time isn't additive over edges,
+        // so dijkstra can't optimise it directly. however, the best
+        // path always has a mean and variance that no other path
+        // beats in both respects (when the deadline isn't hopeless),
+        // so it's enough to check each path on the pareto frontier.
+        self.pareto_shortest_paths(source, target)
+            .into_iter()
+            .map(|(path, total)| (path, total.probability_within(deadline)))
+            .fold(None, |best, (path, probability)| match best {
+                Some((_, best_probability)) if best_probability >= probability => best,
+                _ => Some((path, probability)),
+            })
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::Graph;
+
+const NONE: usize = usize::MAX;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // the vertices whose removal splits up the strongly connected
+    // component they're in, so that there are more components than
+    // before. these are the weak spots of a one-way street network,
+    // where closing one junction strands some of it from the rest.
+    pub fn strong_articulation_points(&self) -> Vec<&V> {
+        self.strong_cuts().0
+    }
+
+    // the same for edges, besides loops, which never matter. removing a
+    // two-way edge closes it both ways.
+    pub fn strong_bridges(&self) -> Vec<(&V, &V, &E)> {
+        self.strong_cuts()
+            .1
+            .into_iter()
+            .map(|i| {
+                let edge = &self.edges[i];
+                (&edge.v1, &edge.v2, &edge.value)
+            })
+            .collect()
+    }
+
+    // italiano, laura and santaroni's approach. inside a strongly
+    // connected component, everything can reach some root r and be
+    // reached from it, so taking a vertex away breaks that exactly when
+    // it stands between r and something else: when it dominates some
+    // vertex, searching from r either forwards or backwards. the root
+    // itself gets checked the same way from a second root. every edge
+    // is split in two by a vertex of its own, so that an edge is a
+    // strong bridge exactly when its middle vertex is a strong
+    // articulation point. the dominators come from cooper, harvey and
+    // kennedy's iterative algorithm, which isn't linear time like the
+    // lengauer-tarjan one the paper uses, but is usually just as quick.
+    fn strong_cuts(&self) -> (Vec<&V>, Vec<usize>) {
+        let components = self.strongly_connected_components();
+        let mut place: HashMap<&V, (usize, usize)> = HashMap::new();
+
+        for (c, component) in components.iter().enumerate() {
+            for (i, &vertex) in component.iter().enumerate() {
+                place.insert(vertex, (c, i));
+            }
+        }
+
+        let mut inside: Vec<Vec<usize>> = vec![Vec::new(); components.len()];
+
+        for (i, edge) in self.edges.iter().enumerate() {
+            let ((a, _), (b, _)) = (place[&edge.v1], place[&edge.v2]);
+
+            if a == b && edge.v1 != edge.v2 {
+                inside[a].push(i);
+            }
+        }
+
+        let mut points = Vec::new();
+        let mut bridges = Vec::new();
+
+        for (component, edges) in components.iter().zip(inside) {
+            let count = component.len();
+
+            if count < 2 {
+                continue;
+            }
+
+            let nodes = count + edges.len();
+            let mut out: Vec<Vec<usize>> = vec![Vec::new(); nodes];
+            let mut into: Vec<Vec<usize>> = vec![Vec::new(); nodes];
+
+            for (j, &i) in edges.iter().enumerate() {
+                let edge = &self.edges[i];
+                let (a, b, middle) = (place[&edge.v1].1, place[&edge.v2].1, count + j);
+                let ways = if edge.directed { 1 } else { 2 };
+
+                for &(from, to) in [(a, b), (b, a)][..ways].iter() {
+                    out[from].push(middle);
+                    into[middle].push(from);
+                    out[middle].push(to);
+                    into[to].push(middle);
+                }
+            }
+
+            let forwards = dominating(&out, &into, 0, count);
+            let backwards = dominating(&into, &out, 0, count);
+            let cut = |node: usize| forwards[node] || backwards[node];
+
+            if dominating(&out, &into, 1, count)[0] || dominating(&into, &out, 1, count)[0] {
+                points.push(component[0]);
+            }
+
+            points.extend((1..count).filter(|&v| cut(v)).map(|v| component[v]));
+            bridges.extend(
+                (0..edges.len())
+                    .filter(|&j| cut(count + j))
+                    .map(|j| edges[j]),
+            );
+        }
+
+        (points, bridges)
+    }
+}
+
+// for every node besides the root, whether it dominates one of the first
+// `count` nodes other than itself, meaning every path from the root to
+// that node goes through it. everything has to be reachable from the
+// root.
+fn dominating(out: &[Vec<usize>], into: &[Vec<usize>], root: usize, count: usize) -> Vec<bool> {
+    let nodes = out.len();
+
+    // a depth-first postorder, without recursion so long paths are fine.
+    let mut postorder = Vec::with_capacity(nodes);
+    let mut number = vec![NONE; nodes];
+    let mut seen = vec![false; nodes];
+    let mut calls = vec![(root, 0)];
+    seen[root] = true;
+
+    while let Some(&mut (node, ref mut next)) = calls.last_mut() {
+        if let Some(&to) = out[node].get(*next) {
+            *next += 1;
+
+            if !seen[to] {
+                seen[to] = true;
+                calls.push((to, 0));
+            }
+
+            continue;
+        }
+
+        number[node] = postorder.len();
+        postorder.push(node);
+        calls.pop();
+    }
+
+    // each node's immediate dominator, found by repeatedly meeting up
+    // the dominators of everything leading into it, going through the
+    // nodes in reverse postorder until nothing changes.
+    let mut idom = vec![NONE; nodes];
+    idom[root] = root;
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+
+        for &node in postorder.iter().rev().skip(1) {
+            let mut best = NONE;
+
+            for &before in into[node].iter() {
+                if idom[before] == NONE {
+                    continue;
+                }
+
+                best = if best == NONE {
+                    before
+                } else {
+                    meet(&idom, &number, best, before)
+                };
+            }
+
+            if idom[node] != best {
+                idom[node] = best;
+                changed = true;
+            }
+        }
+    }
+
+    // dominators come before what they dominate in reverse postorder,
+    // so going the other way sees every node before its dominator.
+    let mut dominates = vec![false; nodes];
+
+    for &node in postorder[..nodes - 1].iter() {
+        if node < count || dominates[node] {
+            dominates[idom[node]] = true;
+        }
+    }
+
+    dominates[root] = false;
+    dominates
+}
+
+// the closest common dominator of two nodes.
+fn meet(idom: &[usize], number: &[usize], mut a: usize, mut b: usize) -> usize {
+    while a != b {
+        while number[a] < number[b] {
+            a = idom[a];
+        }
+
+        while number[b] < number[a] {
+            b = idom[b];
+        }
+    }
+
+    a
+}
@@ -0,0 +1,130 @@
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::hash::Hash;
+
+use super::Graph;
+
+// what topological_sort gives back when there's no ordering: the
+// vertices around one of the cycles, in the order the edges go, so the
+// last one leads back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<'g, V> {
+    pub cycle: Vec<&'g V>,
+}
+
+impl<'g, V: Debug> fmt::Display for CycleError<'g, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Graph has a cycle: ")?;
+
+        for vertex in self.cycle.iter() {
+            write!(f, "{:?} -> ", vertex)?;
+        }
+
+        match self.cycle.first() {
+            Some(first) => write!(f, "{:?}", first),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'g, V: Debug> Error for CycleError<'g, V> {}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // the vertices in an order where every edge goes from an earlier
+    // one to a later one, like a build order where everything comes
+    // after what it depends on (with edges pointing from dependency to
+    // dependent). this is kahn's algorithm: anything with nothing left
+    // leading into it can go next. a two-way edge can be followed both
+    // ways, so it's a cycle by itself.
+    pub fn topological_sort(&self) -> Result<Vec<&V>, CycleError<'_, V>> {
+        let mut waiting: HashMap<&V, usize> = self
+            .vertices
+            .iter()
+            .map(|vertex| (vertex, self.predecessors(vertex).len()))
+            .collect();
+
+        let mut ready: VecDeque<&V> = self
+            .vertices
+            .iter()
+            .filter(|&vertex| waiting[vertex] == 0)
+            .collect();
+
+        let mut order = Vec::with_capacity(self.vertices.len());
+
+        while let Some(vertex) = ready.pop_front() {
+            order.push(vertex);
+
+            for (next, _) in self.neighbors(vertex) {
+                let count = waiting.get_mut(next).unwrap();
+                *count -= 1;
+
+                if *count == 0 {
+                    ready.push_back(self.vertices.get(next).unwrap());
+                }
+            }
+        }
+
+        if order.len() == self.vertices.len() {
+            return Ok(order);
+        }
+
+        // everything left over still has something left over leading
+        // into it, so walking backwards from any of them has to come
+        // round to a vertex it's already been to.
+        let left = |vertex: &V| waiting[vertex] > 0;
+        let mut walked: Vec<&V> = Vec::new();
+        let mut position: HashMap<&V, usize> = HashMap::new();
+        let mut current = self.vertices.iter().find(|&vertex| left(vertex)).unwrap();
+
+        while !position.contains_key(current) {
+            position.insert(current, walked.len());
+            walked.push(current);
+
+            current = self
+                .predecessors(current)
+                .into_iter()
+                .map(|(before, _)| before)
+                .find(|&before| left(before))
+                .unwrap();
+        }
+
+        let mut cycle = walked.split_off(position[current]);
+        cycle.reverse();
+
+        Err(CycleError { cycle })
+    }
+
+    // the vertices in layers, where every edge goes from an earlier
+    // layer to a later one, and each vertex is in the earliest layer it
+    // can be: straight after the latest thing leading into it. so the
+    // first layer is everything with nothing before it, each layer can
+    // run in parallel once the ones before it are done, and there are
+    // as few layers as there can be (one more than the longest path).
+    pub fn topological_layers(&self) -> Result<Vec<Vec<&V>>, CycleError<'_, V>> {
+        let order = self.topological_sort()?;
+        let mut layer: HashMap<&V, usize> = HashMap::new();
+        let mut layers: Vec<Vec<&V>> = Vec::new();
+
+        for vertex in order {
+            let depth = self
+                .predecessors(vertex)
+                .into_iter()
+                .map(|(before, _)| layer[before] + 1)
+                .max()
+                .unwrap_or(0);
+
+            if depth == layers.len() {
+                layers.push(Vec::new());
+            }
+
+            layer.insert(vertex, depth);
+            layers[depth].push(vertex);
+        }
+
+        Ok(layers)
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::Graph;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // splits the edges into as few trails (walks that never reuse an
+    // edge) as possible, each given as the vertices it passes through.
+    // a connected piece of the graph with 2k odd degree vertices needs
+    // k trails, or just one closed trail if there aren't any. the odd
+    // vertices are paired up with pretend edges so that an euler
+    // circuit exists, and cutting that circuit at the pretend edges
+    // gives the trails. vertices without any edges aren't on a trail.
+    pub fn decompose_into_trails(&self) -> Vec<Vec<&V>> {
+        let vertices: Vec<&V> = self.vertices.iter().collect();
+        let index: HashMap<&V, usize> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, &vertex)| (vertex, i))
+            .collect();
+
+        let mut ends: Vec<(usize, usize)> = self
+            .edges
+            .iter()
+            .map(|edge| (index[&edge.v1], index[&edge.v2]))
+            .collect();
+        let real = ends.len();
+
+        // a loop shows up twice, since it adds two to the degree.
+        let mut adjacent: Vec<Vec<(usize, usize)>> = vec![Vec::new(); vertices.len()];
+        for (i, &(a, b)) in ends.iter().enumerate() {
+            adjacent[a].push((b, i));
+            adjacent[b].push((a, i));
+        }
+
+        let mut component = vec![usize::MAX; vertices.len()];
+        for start in 0..vertices.len() {
+            if component[start] != usize::MAX {
+                continue;
+            }
+
+            component[start] = start;
+            let mut stack = vec![start];
+
+            while let Some(vertex) = stack.pop() {
+                for &(next, _) in adjacent[vertex].iter() {
+                    if component[next] == usize::MAX {
+                        component[next] = start;
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+
+        // any two odd vertices in the same piece can be paired, since
+        // the pretend edge between them gets cut back out anyway.
+        let mut unpaired: HashMap<usize, usize> = HashMap::new();
+        for vertex in 0..vertices.len() {
+            if adjacent[vertex].len().is_multiple_of(2) {
+                continue;
+            }
+
+            match unpaired.remove(&component[vertex]) {
+                Some(other) => {
+                    let pretend = ends.len();
+                    ends.push((other, vertex));
+                    adjacent[other].push((vertex, pretend));
+                    adjacent[vertex].push((other, pretend));
+                }
+                None => {
+                    unpaired.insert(component[vertex], vertex);
+                }
+            }
+        }
+
+        let mut used = vec![false; ends.len()];
+        let mut next_edge = vec![0; vertices.len()];
+        let mut trails = Vec::new();
+
+        for start in 0..vertices.len() {
+            if next_edge[start] == adjacent[start].len() {
+                continue;
+            }
+
+            // hierholzer's algorithm. each vertex comes off the stack
+            // along with the edge that led to it, which joins it to
+            // the next vertex of the circuit.
+            let mut circuit: Vec<(usize, Option<usize>)> = Vec::new();
+            let mut stack: Vec<(usize, Option<usize>)> = vec![(start, None)];
+
+            while let Some(&(vertex, _)) = stack.last() {
+                while next_edge[vertex] < adjacent[vertex].len()
+                    && used[adjacent[vertex][next_edge[vertex]].1]
+                {
+                    next_edge[vertex] += 1;
+                }
+
+                match adjacent[vertex].get(next_edge[vertex]) {
+                    Some(&(next, edge)) => {
+                        used[edge] = true;
+                        stack.push((next, Some(edge)));
+                    }
+                    None => circuit.push(stack.pop().unwrap()),
+                }
+            }
+
+            // the circuit ends where it started, so it can be read
+            // from just after any pretend edge to cut it into trails.
+            let length = circuit.len() - 1;
+            let first = (0..length)
+                .find(|&i| circuit[i].1.unwrap() >= real)
+                .map(|i| i + 1)
+                .unwrap_or(0);
+
+            let mut trail = vec![vertices[circuit[first].0]];
+
+            for step in 0..length {
+                let i = (first + step) % length;
+                let (edge, next) = (circuit[i].1.unwrap(), circuit[i + 1].0);
+
+                if edge >= real {
+                    trails.push(trail);
+                    trail = vec![vertices[next]];
+                } else {
+                    trail.push(vertices[next]);
+                }
+            }
+
+            if trail.len() > 1 {
+                trails.push(trail);
+            }
+        }
+
+        trails
+    }
+}
@@ -0,0 +1,87 @@
+use std::hash::Hash;
+
+use super::{Edge, Graph};
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+{
+    // builds a graph with the same shape as this one, but with every
+    // edge value replaced by the result of the given function.
+    pub fn map_weights<W, F>(&self, f: F) -> Graph<V, W>
+    where
+        F: Fn(&E) -> W,
+    {
+        Graph::from_parts(
+            self.vertices.clone(),
+            self.edges
+                .iter()
+                .map(|edge| Edge {
+                    v1: edge.v1.clone(),
+                    v2: edge.v2.clone(),
+                    value: f(&edge.value),
+                    directed: edge.directed,
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Into<f64> + Clone,
+{
+    pub fn normalize_weights(&self) -> Graph<V, f64> {
+        self.rescale_weights(0.0, 1.0)
+    }
+
+    pub fn rescale_weights(&self, min: f64, max: f64) -> Graph<V, f64> {
+        let weights = self.edges.iter().map(|edge| edge.value.clone().into());
+        let (low, high) = weights.fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), w| {
+            (low.min(w), high.max(w))
+        });
+
+        // if every edge has the same weight, there's no range to
+        // stretch out, so they all end up at the bottom of the scale.
+        let spread = high - low;
+
+        self.map_weights(|value| {
+            let weight: f64 = value.clone().into();
+
+            if spread > 0.0 {
+                min + (weight - low) / spread * (max - min)
+            } else {
+                min
+            }
+        })
+    }
+
+    // turns similarities into distances (or the other way around) by
+    // taking reciprocals. edges of weight zero become infinitely long.
+    pub fn invert_weights(&self) -> Graph<V, f64> {
+        self.map_weights(|value| 1.0 / value.clone().into())
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq + Clone,
+    E: Clone,
+{
+    // the graph made up of the chosen vertices and every edge that
+    // has both of its endpoints among them.
+    pub fn induced_subgraph<F>(&self, keep: F) -> Graph<V, E>
+    where
+        F: Fn(&V) -> bool,
+    {
+        Graph::from_parts(
+            self.vertices.iter().filter(|v| keep(v)).cloned().collect(),
+            self.edges
+                .iter()
+                .filter(|edge| keep(&edge.v1) && keep(&edge.v2))
+                .cloned()
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+use super::Graph;
+
+// a breadth-first or depth-first walk over everything reachable from
+// the start, one vertex at a time. nothing is looked at until it's
+// asked for, so stopping early (once a flood fill hits a wall, say)
+// skips the rest. each vertex comes with the vertex and edge value it
+// was discovered from, which is none for the start.
+pub struct Traversal<'g, V: Hash + Eq, E> {
+    graph: &'g Graph<V, E>,
+    pending: VecDeque<(&'g V, Option<(&'g V, &'g E)>)>,
+    seen: HashSet<&'g V>,
+    depth_first: bool,
+}
+
+impl<'g, V, E> Traversal<'g, V, E>
+where
+    V: Hash + Eq,
+{
+    fn new(graph: &'g Graph<V, E>, start: &V, depth_first: bool) -> Self {
+        let mut traversal = Self {
+            graph,
+            pending: VecDeque::new(),
+            seen: HashSet::new(),
+            depth_first,
+        };
+
+        if let Some(start) = graph.vertices.get(start) {
+            if !depth_first {
+                traversal.seen.insert(start);
+            }
+
+            traversal.pending.push_back((start, None));
+        }
+
+        traversal
+    }
+}
+
+impl<'g, V, E> Iterator for Traversal<'g, V, E>
+where
+    V: Hash + Eq,
+{
+    type Item = (&'g V, Option<(&'g V, &'g E)>);
+
+    // breadth first, a vertex counts as seen once it's queued, so it's
+    // only ever queued once. depth first, it might be stacked several
+    // times, and it's the last way it was stacked that discovers it,
+    // which is what makes the edges a proper depth-first tree.
+    fn next(&mut self) -> Option<Self::Item> {
+        let (vertex, via) = if self.depth_first {
+            loop {
+                let (vertex, via) = self.pending.pop_back()?;
+
+                if self.seen.insert(vertex) {
+                    break (vertex, via);
+                }
+            }
+        } else {
+            self.pending.pop_front()?
+        };
+
+        let neighbors = self.graph.neighbors(vertex);
+
+        if self.depth_first {
+            // stacked backwards, so that they come off in order.
+            for &(next, value) in neighbors.iter().rev() {
+                if !self.seen.contains(next) {
+                    self.pending.push_back((next, Some((vertex, value))));
+                }
+            }
+        } else {
+            for &(next, value) in neighbors.iter() {
+                if self.seen.insert(next) {
+                    self.pending.push_back((next, Some((vertex, value))));
+                }
+            }
+        }
+
+        Some((vertex, via))
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // every vertex reachable from the start (the start included), in
+    // order of how many edges away it is. directed edges are only
+    // followed forwards. nothing happens if the start isn't here.
+    pub fn bfs(&self, start: &V) -> impl Iterator<Item = &V> + '_ {
+        self.bfs_with_edges(start).map(|(vertex, _)| vertex)
+    }
+
+    // the same vertices, in the order a depth-first search first gets
+    // to them.
+    pub fn dfs(&self, start: &V) -> impl Iterator<Item = &V> + '_ {
+        self.dfs_with_edges(start).map(|(vertex, _)| vertex)
+    }
+
+    // the same traversals, with the edge each vertex was discovered by.
+    pub fn bfs_with_edges(&self, start: &V) -> Traversal<'_, V, E> {
+        Traversal::new(self, start, false)
+    }
+
+    pub fn dfs_with_edges(&self, start: &V) -> Traversal<'_, V, E> {
+        Traversal::new(self, start, true)
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::queue::{BinaryQueue, PriorityQueue};
+use super::{Graph, Stop};
+use crate::limits::SearchLimits;
+use crate::metrics::Counters;
+
+// everything dijkstra_paths finds out, not just the distances: each
+// vertex also remembers where its shortest path came from, which is
+// enough to trace the whole path back to the source.
+#[derive(Debug, Clone)]
+pub struct ShortestPathTree<'g, V, E> {
+    distances: HashMap<&'g V, E>,
+    previous: HashMap<&'g V, &'g V>,
+}
+
+impl<'g, V: Hash + Eq, E> ShortestPathTree<'g, V, E> {
+    pub fn distance(&self, vertex: &V) -> Option<&E> {
+        self.distances.get(vertex)
+    }
+
+    pub fn distances(&self) -> &HashMap<&'g V, E> {
+        &self.distances
+    }
+
+    pub fn into_distances(self) -> HashMap<&'g V, E> {
+        self.distances
+    }
+
+    // the vertex just before this one on its shortest path.
+    pub fn predecessor(&self, vertex: &V) -> Option<&'g V> {
+        self.previous.get(vertex).cloned()
+    }
+
+    // the path from the source, including both ends. like the
+    // distances, there isn't one to the source itself or to anywhere
+    // that couldn't be reached.
+    pub fn path_to(&self, vertex: &V) -> Option<Vec<&'g V>> {
+        let (&last, _) = self.distances.get_key_value(vertex)?;
+        let mut path = vec![last];
+        let mut current = last;
+
+        while let Some(&before) = self.previous.get(current) {
+            path.push(before);
+            current = before;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    pub fn dijkstra_paths_with_predecessors<'g>(
+        &'g self,
+        source: &V,
+    ) -> ShortestPathTree<'g, V, E> {
+        let mut previous = HashMap::new();
+
+        let distances = match self.dijkstra_within(
+            source,
+            |edge| edge.clone(),
+            Stop::Never,
+            false,
+            &SearchLimits::new(),
+            &mut Counters::default(),
+            &mut previous,
+        ) {
+            Ok(distances) => distances,
+            Err(exceeded) => exceeded.partial,
+        };
+
+        ShortestPathTree {
+            distances,
+            previous,
+        }
+    }
+
+    // the shortest path from the source to the target and its cost,
+    // which stops searching as soon as the target is settled instead of
+    // working out the whole tree. there's no path from a vertex to
+    // itself, or to anywhere it can't reach.
+    pub fn dijkstra_path<'g>(&'g self, source: &V, target: &V) -> Option<(Vec<&'g V>, E)> {
+        self.dijkstra_path_with(source, target, BinaryQueue::new())
+    }
+
+    // the same path, found by searching with the given queue.
+    pub fn dijkstra_path_with<'g, Q>(
+        &'g self,
+        source: &V,
+        target: &V,
+        queue: Q,
+    ) -> Option<(Vec<&'g V>, E)>
+    where
+        Q: PriorityQueue<E, &'g V>,
+    {
+        let mut previous = HashMap::new();
+
+        let distances = match self.dijkstra_queued(
+            source,
+            |edge| edge.clone(),
+            Stop::At(target),
+            false,
+            &SearchLimits::new(),
+            &mut Counters::default(),
+            &mut previous,
+            queue,
+        ) {
+            Ok(distances) => distances,
+            Err(exceeded) => exceeded.partial,
+        };
+
+        let cost = distances.get(target)?.clone();
+        let tree = ShortestPathTree {
+            distances,
+            previous,
+        };
+
+        Some((tree.path_to(target)?, cost))
+    }
+}
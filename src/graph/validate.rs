@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::hash::Hash;
+
+use super::Graph;
+
+// a single problem found by validate. edges are identified by their
+// position in the order they were connected, along with their ends.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Issue<'g, V> {
+    MissingVertex {
+        edge: usize,
+        vertex: &'g V,
+    },
+    Loop {
+        edge: usize,
+        vertex: &'g V,
+    },
+    ParallelEdge {
+        edge: usize,
+        first: usize,
+        v1: &'g V,
+        v2: &'g V,
+    },
+    InvalidWeight {
+        edge: usize,
+        v1: &'g V,
+        v2: &'g V,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidationReport<'g, V> {
+    pub issues: Vec<Issue<'g, V>>,
+}
+
+impl<'g, V> ValidationReport<'g, V> {
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    // loops and parallel edges are perfectly legal, so anything that's
+    // happy with them can check for the problems that are always bad.
+    pub fn is_consistent(&self) -> bool {
+        self.issues.iter().all(|issue| match issue {
+            Issue::MissingVertex { .. } | Issue::InvalidWeight { .. } => false,
+            Issue::Loop { .. } | Issue::ParallelEdge { .. } => true,
+        })
+    }
+}
+
+// one line per issue, or a note saying there weren't any.
+impl<'g, V: Debug> fmt::Display for ValidationReport<'g, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.issues.is_empty() {
+            return writeln!(f, "No issues found.");
+        }
+
+        for issue in self.issues.iter() {
+            match issue {
+                Issue::MissingVertex { edge, vertex } => writeln!(
+                    f,
+                    "Edge {} uses {:?}, which isn't in the graph.",
+                    edge, vertex
+                )?,
+                Issue::Loop { edge, vertex } => {
+                    writeln!(f, "Edge {} is a loop at {:?}.", edge, vertex)?
+                }
+                Issue::ParallelEdge {
+                    edge,
+                    first,
+                    v1,
+                    v2,
+                } => writeln!(
+                    f,
+                    "Edge {} joins {:?} and {:?}, as edge {} already does.",
+                    edge, v1, v2, first
+                )?,
+                Issue::InvalidWeight { edge, v1, v2 } => writeln!(
+                    f,
+                    "Edge {} between {:?} and {:?} has an invalid weight.",
+                    edge, v1, v2
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // checks the graph over, with each edge value also being checked
+    // by the given predicate (like |w| *w >= 0.0 for searches that
+    // can't cope with negative weights).
+    pub fn validate<F>(&self, valid_weight: F) -> ValidationReport<'_, V>
+    where
+        F: Fn(&E) -> bool,
+    {
+        let mut issues = Vec::new();
+        let mut seen: HashMap<(&V, &V), usize> = HashMap::new();
+
+        for (index, edge) in self.edges.iter().enumerate() {
+            for &vertex in [&edge.v1, &edge.v2].iter() {
+                if !self.vertices.contains(vertex) {
+                    issues.push(Issue::MissingVertex {
+                        edge: index,
+                        vertex,
+                    });
+                }
+            }
+
+            if edge.v1 == edge.v2 {
+                issues.push(Issue::Loop {
+                    edge: index,
+                    vertex: &edge.v1,
+                });
+            }
+
+            let (v1, v2) = (&edge.v1, &edge.v2);
+
+            // edges are parallel when they can be followed the same way,
+            // so a->b and b->a are fine if they're both directed.
+            let ways = if edge.directed { 1 } else { 2 };
+            let arcs = &[(v1, v2), (v2, v1)][..ways];
+
+            if let Some(&first) = arcs.iter().find_map(|arc| seen.get(arc)) {
+                issues.push(Issue::ParallelEdge {
+                    edge: index,
+                    first,
+                    v1,
+                    v2,
+                });
+            }
+
+            for &arc in arcs.iter() {
+                seen.entry(arc).or_insert(index);
+            }
+
+            if !valid_weight(&edge.value) {
+                issues.push(Issue::InvalidWeight {
+                    edge: index,
+                    v1,
+                    v2,
+                });
+            }
+        }
+
+        ValidationReport { issues }
+    }
+}
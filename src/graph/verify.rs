@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::Graph;
+use crate::error::GraphError;
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone,
+{
+    // the total cost of following the given vertices in order, taking
+    // the cheapest edge wherever there's a choice.
+    pub fn path_cost(&self, path: &[&V]) -> Result<E, GraphError> {
+        Ok(self.describe_path(path)?.total())
+    }
+
+    // checks that the path really goes from the source to the target
+    // and that nothing is shorter. the shortest distance comes from
+    // bellman-ford rather than dijkstra's algorithm, so that a bug in
+    // one doesn't hide behind the same bug in the other.
+    pub fn verify_shortest(&self, source: &V, target: &V, path: &[&V]) -> Result<(), GraphError> {
+        if path.first() != Some(&source) {
+            return Err(GraphError::InvalidPath("Path doesn't start at the source."));
+        }
+
+        if path.last() != Some(&target) {
+            return Err(GraphError::InvalidPath("Path doesn't end at the target."));
+        }
+
+        let cost = self.path_cost(path)?;
+
+        match self.bellman_ford(source).get(target) {
+            Some(shortest) if cost == *shortest => Ok(()),
+            Some(_) => Err(GraphError::InvalidPath(
+                "Path is longer than the shortest path.",
+            )),
+            None => Err(GraphError::Unreachable),
+        }
+    }
+
+    fn bellman_ford(&self, source: &V) -> HashMap<&V, E> {
+        let mut distances: HashMap<&V, E> = HashMap::new();
+
+        // with no zero for E, edges out of the source start paths of
+        // their own rather than extending a path of length zero. a
+        // shortest path has fewer edges than there are vertices, so
+        // that many rounds is enough (and stops negative weights from
+        // keeping it going forever).
+        for _ in 0..self.vertices.len() {
+            let mut changed = false;
+
+            for edge in self.edges.iter() {
+                // a directed edge can only be relaxed the one way.
+                let ways = if edge.directed { 1 } else { 2 };
+
+                for &(from, to) in [(&edge.v1, &edge.v2), (&edge.v2, &edge.v1)][..ways].iter() {
+                    if to == source {
+                        continue;
+                    }
+
+                    let candidate = if from == source {
+                        edge.value.clone()
+                    } else {
+                        match distances.get(from) {
+                            Some(dist) => dist.clone() + edge.value.clone(),
+                            None => continue,
+                        }
+                    };
+
+                    let better = match distances.get(to) {
+                        Some(current) => candidate < *current,
+                        None => true,
+                    };
+
+                    if better {
+                        distances.insert(to, candidate);
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        distances
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use super::Graph;
+
+// what a visitor wants to happen next. pruning a vertex as it's
+// discovered means none of its edges get looked at, and pruning an
+// edge means it isn't followed. breaking stops the whole traversal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    Prune,
+    Break,
+}
+
+// callbacks for traverse, all of which do nothing by default, so a
+// visitor only needs the ones it's interested in. every vertex that's
+// discovered is finished later, once everything reachable through it
+// has been, unless the traversal breaks first.
+pub trait Visitor<'g, V, E> {
+    fn discover_vertex(&mut self, _vertex: &'g V) -> Control {
+        Control::Continue
+    }
+
+    // every edge out of a discovered vertex (that wasn't pruned), even
+    // if what's at the other end has already been discovered.
+    fn examine_edge(&mut self, _from: &'g V, _to: &'g V, _value: &'g E) -> Control {
+        Control::Continue
+    }
+
+    // pruning here makes no difference, since there's nothing left to
+    // skip by then.
+    fn finish_vertex(&mut self, _vertex: &'g V) -> Control {
+        Control::Continue
+    }
+}
+
+// the vertex whose edges are being gone through, and how far.
+type Frame<'g, V, E> = (&'g V, Vec<(&'g V, &'g E)>, usize);
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+{
+    // a depth-first traversal from the start that tells the visitor
+    // about everything as it happens, following directed edges forwards
+    // like dfs does. it gives back break if the visitor stopped it, and
+    // continue otherwise (including when the start isn't here).
+    pub fn traverse<'g, T>(&'g self, start: &V, visitor: &mut T) -> Control
+    where
+        T: Visitor<'g, V, E>,
+    {
+        let start = match self.vertices.get(start) {
+            Some(start) => start,
+            None => return Control::Continue,
+        };
+
+        let mut seen = HashSet::new();
+        let mut stack: Vec<Frame<V, E>> = Vec::new();
+
+        if self.discover(start, visitor, &mut seen, &mut stack) == Control::Break {
+            return Control::Break;
+        }
+
+        while let Some((vertex, neighbors, next)) = stack.last_mut() {
+            let vertex = *vertex;
+
+            if *next == neighbors.len() {
+                stack.pop();
+
+                if visitor.finish_vertex(vertex) == Control::Break {
+                    return Control::Break;
+                }
+
+                continue;
+            }
+
+            let (to, value) = neighbors[*next];
+            *next += 1;
+
+            match visitor.examine_edge(vertex, to, value) {
+                Control::Break => return Control::Break,
+                Control::Prune => continue,
+                Control::Continue => (),
+            }
+
+            if !seen.contains(to)
+                && self.discover(to, visitor, &mut seen, &mut stack) == Control::Break
+            {
+                return Control::Break;
+            }
+        }
+
+        Control::Continue
+    }
+
+    // a vertex that's pruned as it's discovered is finished straight
+    // away, and the rest go on the stack to have their edges examined.
+    fn discover<'g, T>(
+        &'g self,
+        vertex: &'g V,
+        visitor: &mut T,
+        seen: &mut HashSet<&'g V>,
+        stack: &mut Vec<Frame<'g, V, E>>,
+    ) -> Control
+    where
+        T: Visitor<'g, V, E>,
+    {
+        seen.insert(vertex);
+
+        match visitor.discover_vertex(vertex) {
+            Control::Break => Control::Break,
+            Control::Prune => visitor.finish_vertex(vertex),
+            Control::Continue => {
+                stack.push((vertex, self.neighbors(vertex), 0));
+                Control::Continue
+            }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Add;
+
+use super::frontier::Queued;
+use super::Graph;
+
+// edge weights with a value that adding makes no difference to, which
+// is all dijkstra's algorithm needs to give the source a distance too.
+pub trait Zero {
+    fn zero() -> Self;
+}
+
+macro_rules! impl_zero {
+    ($($t:ty),*) => {
+        $(
+            impl Zero for $t {
+                fn zero() -> Self {
+                    0 as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_zero!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl<V, E> Graph<V, E>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + PartialOrd + Clone + Zero,
+{
+    // the same distances as dijkstra_paths, except that the source is
+    // included at a distance of zero. knowing the zero also means the
+    // search can just start with the source in the queue, rather than
+    // handling its neighbours before the main loop.
+    pub fn dijkstra_paths_complete(&self, source: &V) -> HashMap<&V, E> {
+        let mut distances: HashMap<&V, E> = HashMap::new();
+        let mut settled: HashSet<&V> = HashSet::new();
+        let mut queue = BinaryHeap::new();
+
+        if let Some(source) = self.vertices.get(source) {
+            distances.insert(source, E::zero());
+            queue.push(Queued {
+                dist: E::zero(),
+                item: source,
+            });
+        }
+
+        while let Some(Queued { dist, item: vertex }) = queue.pop() {
+            if !settled.insert(vertex) {
+                continue;
+            }
+
+            for (next, value) in self.neighbors(vertex) {
+                if settled.contains(next) {
+                    continue;
+                }
+
+                let through = dist.clone() + value.clone();
+                let better = match distances.get(next) {
+                    Some(current) => through < *current,
+                    None => true,
+                };
+
+                if better {
+                    let next = self.vertices.get(next).unwrap();
+                    distances.insert(next, through.clone());
+                    queue.push(Queued {
+                        dist: through,
+                        item: next,
+                    });
+                }
+            }
+        }
+
+        distances
+    }
+}
@@ -0,0 +1,375 @@
+// just enough of gzip (rfc 1952) and deflate (rfc 1951) to read the
+// compressed datasets that graphs tend to be published as, without
+// pulling in a dependency. it works on the whole file at once, which
+// is fine for anything that's going to be loaded into memory anyway.
+
+use crate::error::GraphError;
+
+pub fn is_gzip(bytes: &[u8]) -> bool {
+    bytes.len() >= 2 && bytes[0] == 0x1f && bytes[1] == 0x8b
+}
+
+pub fn gunzip(bytes: &[u8]) -> Result<Vec<u8>, GraphError> {
+    members(bytes).map_err(GraphError::Compression)
+}
+
+fn members(bytes: &[u8]) -> Result<Vec<u8>, &'static str> {
+    let mut output = Vec::new();
+    let mut position = 0;
+
+    // a file can be several gzip members one after another, which
+    // decompress to everything joined together. some tools pad the file
+    // out with zeros after the last one, which gzip itself ignores.
+    while position < bytes.len() {
+        if position > 0 && bytes[position..].iter().all(|&byte| byte == 0) {
+            break;
+        }
+
+        if !is_gzip(&bytes[position..]) {
+            return Err("Not a gzip file.");
+        }
+
+        let start = output.len();
+        position = member(bytes, position, &mut output)?;
+
+        let trailer = bytes
+            .get(position..position + 8)
+            .ok_or("Gzip file is truncated.")?;
+        let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+        let size = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+
+        if crc32(&output[start..]) != crc || (output.len() - start) as u32 != size {
+            return Err("Gzip file is corrupt.");
+        }
+
+        position += 8;
+    }
+
+    Ok(output)
+}
+
+// skips over a member's header, inflates its contents, and gives the
+// position of its trailer.
+fn member(bytes: &[u8], start: usize, output: &mut Vec<u8>) -> Result<usize, &'static str> {
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+
+    let header = bytes
+        .get(start..start + 10)
+        .ok_or("Gzip file is truncated.")?;
+
+    if header[2] != 8 {
+        return Err("Gzip file uses an unknown compression method.");
+    }
+
+    let flags = header[3];
+    let mut position = start + 10;
+
+    if flags & FEXTRA != 0 {
+        let length = bytes
+            .get(position..position + 2)
+            .ok_or("Gzip file is truncated.")?;
+        position += 2 + u16::from_le_bytes([length[0], length[1]]) as usize;
+    }
+
+    for &flag in [FNAME, FCOMMENT].iter() {
+        if flags & flag != 0 {
+            let end = bytes[position.min(bytes.len())..]
+                .iter()
+                .position(|&byte| byte == 0)
+                .ok_or("Gzip file is truncated.")?;
+            position += end + 1;
+        }
+    }
+
+    if flags & FHCRC != 0 {
+        position += 2;
+    }
+
+    let mut reader = BitReader {
+        bytes,
+        position,
+        bit: 0,
+    };
+
+    inflate(&mut reader, output)?;
+
+    // the trailer starts on the next whole byte.
+    Ok(reader.position + if reader.bit > 0 { 1 } else { 0 })
+}
+
+struct BitReader<'b> {
+    bytes: &'b [u8],
+    position: usize,
+    bit: u32,
+}
+
+impl<'b> BitReader<'b> {
+    // deflate packs its bits starting from the least significant one.
+    fn bits(&mut self, count: u32) -> Result<u32, &'static str> {
+        let mut value = 0;
+
+        for i in 0..count {
+            let byte = *self
+                .bytes
+                .get(self.position)
+                .ok_or("Gzip file is truncated.")?;
+            value |= (((byte >> self.bit) & 1) as u32) << i;
+
+            self.bit += 1;
+            if self.bit == 8 {
+                self.bit = 0;
+                self.position += 1;
+            }
+        }
+
+        Ok(value)
+    }
+
+    fn align(&mut self) {
+        if self.bit > 0 {
+            self.bit = 0;
+            self.position += 1;
+        }
+    }
+}
+
+// a canonical huffman code, stored as the number of codes of each
+// length and the symbols in code order. decoding reads one bit at a
+// time and checks whether the code so far falls among those of the
+// current length (this is how zlib's puff does it).
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0; 16];
+        for &length in lengths.iter() {
+            counts[length as usize] += 1;
+        }
+        counts[0] = 0;
+
+        let mut offsets = [0; 16];
+        for length in 1..15 {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, &length) in lengths.iter().enumerate() {
+            if length != 0 {
+                symbols[offsets[length as usize] as usize] = symbol as u16;
+                offsets[length as usize] += 1;
+            }
+        }
+
+        Self { counts, symbols }
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, &'static str> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+
+        for length in 1..16 {
+            code |= reader.bits(1)? as i32;
+            let count = self.counts[length] as i32;
+
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        Err("Gzip file has an invalid huffman code.")
+    }
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+// the order that the code length code lengths come in.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+fn inflate(reader: &mut BitReader, output: &mut Vec<u8>) -> Result<(), &'static str> {
+    // back references can't reach further back than this member.
+    let start = output.len();
+
+    loop {
+        let last = reader.bits(1)? == 1;
+
+        match reader.bits(2)? {
+            0 => stored(reader, output)?,
+            1 => {
+                let (literals, distances) = fixed_codes();
+                codes(reader, output, start, &literals, &distances)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(reader)?;
+                codes(reader, output, start, &literals, &distances)?;
+            }
+            _ => return Err("Gzip file has an invalid block type."),
+        }
+
+        if last {
+            return Ok(());
+        }
+    }
+}
+
+fn stored(reader: &mut BitReader, output: &mut Vec<u8>) -> Result<(), &'static str> {
+    reader.align();
+
+    let position = reader.position;
+    let header = reader
+        .bytes
+        .get(position..position + 4)
+        .ok_or("Gzip file is truncated.")?;
+    let length = u16::from_le_bytes([header[0], header[1]]);
+    let complement = u16::from_le_bytes([header[2], header[3]]);
+
+    if length != !complement {
+        return Err("Gzip file has a corrupt stored block.");
+    }
+
+    let data = reader
+        .bytes
+        .get(position + 4..position + 4 + length as usize)
+        .ok_or("Gzip file is truncated.")?;
+    output.extend_from_slice(data);
+    reader.position = position + 4 + length as usize;
+
+    Ok(())
+}
+
+fn fixed_codes() -> (Huffman, Huffman) {
+    let mut lengths = [0u8; 288];
+
+    for (symbol, length) in lengths.iter_mut().enumerate() {
+        *length = match symbol {
+            0..=143 => 8,
+            144..=255 => 9,
+            256..=279 => 7,
+            _ => 8,
+        };
+    }
+
+    (Huffman::new(&lengths), Huffman::new(&[5; 30]))
+}
+
+fn dynamic_codes(reader: &mut BitReader) -> Result<(Huffman, Huffman), &'static str> {
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_count = reader.bits(4)? as usize + 4;
+
+    let mut code_lengths = [0u8; 19];
+    for &symbol in CODE_LENGTH_ORDER.iter().take(code_count) {
+        code_lengths[symbol] = reader.bits(3)? as u8;
+    }
+    let code_lengths = Huffman::new(&code_lengths);
+
+    // both sets of lengths are sent as one run, compressed with their
+    // own little code that has symbols for repeating lengths.
+    let mut lengths: Vec<u8> = Vec::with_capacity(literal_count + distance_count);
+
+    while lengths.len() < literal_count + distance_count {
+        let (length, repeat) = match code_lengths.decode(reader)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => {
+                let previous = *lengths.last().ok_or("Gzip file has an invalid code.")?;
+                (previous, 3 + reader.bits(2)?)
+            }
+            17 => (0, 3 + reader.bits(3)?),
+            _ => (0, 11 + reader.bits(7)?),
+        };
+
+        for _ in 0..repeat {
+            lengths.push(length);
+        }
+    }
+
+    if lengths.len() > literal_count + distance_count {
+        return Err("Gzip file has an invalid code.");
+    }
+
+    let (literals, distances) = lengths.split_at(literal_count);
+    Ok((Huffman::new(literals), Huffman::new(distances)))
+}
+
+fn codes(
+    reader: &mut BitReader,
+    output: &mut Vec<u8>,
+    start: usize,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> Result<(), &'static str> {
+    loop {
+        let symbol = literals.decode(reader)? as usize;
+
+        if symbol < 256 {
+            output.push(symbol as u8);
+            continue;
+        }
+
+        if symbol == 256 {
+            return Ok(());
+        }
+
+        let symbol = symbol - 257;
+        if symbol >= LENGTH_BASE.len() {
+            return Err("Gzip file has an invalid length.");
+        }
+        let length = LENGTH_BASE[symbol] as usize + reader.bits(LENGTH_EXTRA[symbol])? as usize;
+
+        let symbol = distances.decode(reader)? as usize;
+        if symbol >= DISTANCE_BASE.len() {
+            return Err("Gzip file has an invalid distance.");
+        }
+        let distance =
+            DISTANCE_BASE[symbol] as usize + reader.bits(DISTANCE_EXTRA[symbol])? as usize;
+
+        if distance > output.len() - start {
+            return Err("Gzip file refers back too far.");
+        }
+
+        // the copy can overlap what it's writing, so it has to go one
+        // byte at a time.
+        for _ in 0..length {
+            output.push(output[output.len() - distance]);
+        }
+    }
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+
+    for &byte in bytes.iter() {
+        crc ^= byte as u32;
+
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+
+    !crc
+}
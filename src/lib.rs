@@ -0,0 +1,18 @@
+pub mod cache;
+pub mod cancel;
+pub mod error;
+pub mod fixtures;
+pub mod flow;
+pub mod generate;
+pub mod graph;
+pub mod gzip;
+pub mod limits;
+pub mod metrics;
+pub mod progress;
+pub mod random;
+pub mod vrp;
+
+pub use error::GraphError;
+pub use graph::data::GraphData;
+pub use graph::tree::ShortestPathTree;
+pub use graph::Graph;
@@ -0,0 +1,113 @@
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+// caps on how much work a single search may do, for when the worst
+// case matters more than always getting an answer. every limit is off
+// unless it's set. memory is only a rough estimate, based on the size
+// of the entries the search keeps for each vertex it has seen.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SearchLimits {
+    max_expanded: Option<usize>,
+    max_memory: Option<usize>,
+    time_budget: Option<Duration>,
+}
+
+impl SearchLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_expanded(mut self, vertices: usize) -> Self {
+        self.max_expanded = Some(vertices);
+        self
+    }
+
+    pub fn max_memory(mut self, bytes: usize) -> Self {
+        self.max_memory = Some(bytes);
+        self
+    }
+
+    pub fn time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    pub fn start(&self) -> Budget {
+        Budget {
+            limits: *self,
+            started: Instant::now(),
+            expanded: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    Expanded,
+    Memory,
+    Time,
+}
+
+// what a search gives back when it runs out of budget: which limit it
+// hit, and the best answer it had by then.
+#[derive(Debug, Clone)]
+pub struct LimitExceeded<T> {
+    pub limit: Limit,
+    pub partial: T,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Limit::Expanded => write!(f, "expanded vertex"),
+            Limit::Memory => write!(f, "memory"),
+            Limit::Time => write!(f, "time"),
+        }
+    }
+}
+
+impl<T> fmt::Display for LimitExceeded<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Search exceeded its {} limit.", self.limit)
+    }
+}
+
+impl<T: fmt::Debug> Error for LimitExceeded<T> {}
+
+// the running tally for one search.
+pub struct Budget {
+    limits: SearchLimits,
+    started: Instant,
+    expanded: usize,
+}
+
+impl Budget {
+    // called each time the search expands a vertex, with roughly how
+    // many bytes it's holding on to.
+    pub fn expand(&mut self, memory: usize) -> Result<(), Limit> {
+        self.expanded += 1;
+
+        if self
+            .limits
+            .max_expanded
+            .is_some_and(|max| self.expanded > max)
+        {
+            return Err(Limit::Expanded);
+        }
+
+        if self.limits.max_memory.is_some_and(|max| memory > max) {
+            return Err(Limit::Memory);
+        }
+
+        if self
+            .limits
+            .time_budget
+            .is_some_and(|budget| self.started.elapsed() > budget)
+        {
+            return Err(Limit::Time);
+        }
+
+        Ok(())
+    }
+}
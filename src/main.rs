@@ -1,7 +1,4 @@
-#[macro_use]
-mod graph;
-
-use graph::Graph;
+use dijkstra::{graph, Graph};
 
 fn main() {
     let graph: Graph<&str, u32> = graph!(
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+// how much work one run of an algorithm did. searches count vertices
+// settled, edges relaxed and priority queue operations (pops and
+// decreases). flow algorithms count nodes reached by their level
+// searches, arcs examined, and their queue's pushes and pops.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counters {
+    pub settled: u64,
+    pub relaxed: u64,
+    pub queue_operations: u64,
+    pub elapsed: Duration,
+}
+
+// receives the counters at the end of each instrumented run, under the
+// name of the algorithm (like "dijkstra_paths"), which is the place to
+// forward them into whatever tracing or metrics system is in use. as
+// with progress, any closure will do, and () ignores them.
+pub trait Recorder {
+    fn record(&mut self, span: &'static str, counters: &Counters);
+}
+
+impl Recorder for () {
+    fn record(&mut self, _span: &'static str, _counters: &Counters) {}
+}
+
+impl<F: FnMut(&'static str, &Counters)> Recorder for F {
+    fn record(&mut self, span: &'static str, counters: &Counters) {
+        self(span, counters)
+    }
+}
@@ -0,0 +1,67 @@
+use std::io::{self, BufRead, Read};
+
+// told how far along a long computation is, as a count of finished
+// steps out of a total, so that something like a progress bar can be
+// drawn. what counts as a step depends on the computation (a search
+// from one source, or a byte of input). any closure taking the two
+// counts will do, and () can be passed in to ignore it all.
+pub trait Progress {
+    fn on_progress(&mut self, done: usize, total: usize);
+}
+
+impl Progress for () {
+    fn on_progress(&mut self, _done: usize, _total: usize) {}
+}
+
+impl<F: FnMut(usize, usize)> Progress for F {
+    fn on_progress(&mut self, done: usize, total: usize) {
+        self(done, total)
+    }
+}
+
+// wraps the input to any of the importers, reporting how many of its
+// bytes have been read so far. the total has to be given up front,
+// which for a file is just its length.
+pub struct ProgressReader<R, P> {
+    inner: R,
+    progress: P,
+    done: usize,
+    total: usize,
+}
+
+impl<R, P: Progress> ProgressReader<R, P> {
+    pub fn new(inner: R, total: usize, progress: P) -> Self {
+        Self {
+            inner,
+            progress,
+            done: 0,
+            total,
+        }
+    }
+
+    fn advance(&mut self, amount: usize) {
+        if amount > 0 {
+            self.done += amount;
+            self.progress.on_progress(self.done, self.total);
+        }
+    }
+}
+
+impl<R: Read, P: Progress> Read for ProgressReader<R, P> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let amount = self.inner.read(buf)?;
+        self.advance(amount);
+        Ok(amount)
+    }
+}
+
+impl<R: BufRead, P: Progress> BufRead for ProgressReader<R, P> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.inner.consume(amount);
+        self.advance(amount);
+    }
+}
@@ -0,0 +1,74 @@
+// a minimal source of randomness for the randomised algorithms, so
+// that the crate doesn't need any dependencies. anything that can
+// produce uniformly distributed u64s can implement this.
+//
+// every randomised algorithm takes its rng as an argument and never
+// makes one of its own, and none of them let hash map ordering decide
+// how the random numbers get used: where the order of the vertices
+// matters, they're sorted by a fixed hash first. so for the same graph
+// (built in the same order) and an rng in the same state, the results
+// are the same every time the same build runs. that hash depends on
+// the vertex type's Hash impl, which can vary with pointer width and
+// endianness, and the standard library is free to change its hasher,
+// so on another platform or toolchain the results can differ. they
+// take `&mut R` where R can be unsized, which means a `&mut dyn Rng`
+// works too.
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+
+    // a float uniformly distributed in [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // an integer uniformly distributed in [0, bound).
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_f64() * bound as f64) as usize
+    }
+
+    // a fisher-yates shuffle, so every order is equally likely.
+    fn shuffle<T>(&mut self, items: &mut [T])
+    where
+        Self: Sized,
+    {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+impl<R: Rng + ?Sized> Rng for &mut R {
+    fn next_u64(&mut self) -> u64 {
+        (**self).next_u64()
+    }
+}
+
+// sebastiano vigna's splitmix64. it's fast, passes the usual
+// statistical tests and gives the same sequence for the same seed.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    // a new generator seeded from this one, for handing separate (but
+    // still reproducible) streams to separate experiments.
+    pub fn split(&mut self) -> Self {
+        Self::new(self.next_u64())
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+use crate::error::GraphError;
+use crate::graph::Graph;
+
+// a single vehicle's trip, which starts and ends at the depot. the
+// cost includes the legs to and from the depot.
+#[derive(Debug)]
+pub struct Route<'g, V, E, D> {
+    pub customers: Vec<&'g V>,
+    pub load: D,
+    pub cost: E,
+}
+
+pub fn clarke_wright<'g, V, E, D>(
+    graph: &'g Graph<V, E>,
+    depot: &'g V,
+    customers: &[(&'g V, D)],
+    capacity: D,
+) -> Result<Vec<Route<'g, V, E, D>>, GraphError>
+where
+    V: Hash + Eq,
+    E: Add<Output = E> + Sub<Output = E> + Ord + Clone,
+    D: Add<Output = D> + Ord + Clone,
+{
+    // the savings heuristic needs the shortest distance between every
+    // pair of stops, since vehicles are free to pass through vertices
+    // that aren't customers along the way.
+    let stops: Vec<&V> = customers
+        .iter()
+        .map(|(c, _)| *c)
+        .chain(Some(depot))
+        .collect();
+    let distances = graph.distance_matrix(&stops, &stops);
+
+    let distance = |from: &V, to: &V| distances.get(from, to).cloned();
+
+    // routes get joined up end to end, and turned around to do it, so
+    // with directed edges every stop has to be reachable from every
+    // other one, both ways, before any of the distances can be relied on.
+    for (customer, demand) in customers.iter() {
+        let stranded = stops
+            .iter()
+            .any(|&other| other != *customer && distance(other, customer).is_none());
+
+        if stranded || distance(customer, depot).is_none() {
+            return Err(GraphError::Unreachable);
+        }
+
+        if *demand > capacity {
+            return Err(GraphError::Infeasible(
+                "Customer demand exceeds vehicle capacity.",
+            ));
+        }
+    }
+
+    // every customer starts off with a dedicated trip from the depot.
+    let mut routes: Vec<Option<(Vec<&V>, D)>> = customers
+        .iter()
+        .map(|(customer, demand)| Some((vec![*customer], demand.clone())))
+        .collect();
+    let mut route_of: HashMap<&V, usize> = customers
+        .iter()
+        .enumerate()
+        .map(|(index, (customer, _))| (*customer, index))
+        .collect();
+
+    // serving i and j on the same trip, rather than on separate ones,
+    // saves d(depot, i) + d(depot, j) - d(i, j). it's never negative
+    // because shortest distances obey the triangle inequality.
+    let mut savings: Vec<(E, &V, &V)> = Vec::new();
+
+    for (index, &(i, _)) in customers.iter().enumerate() {
+        for &(j, _) in customers[index + 1..].iter() {
+            let via_depot = distance(depot, i).unwrap() + distance(depot, j).unwrap();
+            let direct = distance(i, j).unwrap();
+
+            // without a zero for E, we can't check for a saving of
+            // zero directly, but this amounts to the same thing.
+            if via_depot > direct {
+                savings.push((via_depot - direct, i, j));
+            }
+        }
+    }
+
+    savings.sort_by(|a, b| b.0.cmp(&a.0));
+
+    for (_, i, j) in savings {
+        let (route_i, route_j) = (route_of[i], route_of[j]);
+        if route_i == route_j {
+            continue;
+        }
+
+        let (stops_i, load_i) = routes[route_i].as_ref().unwrap();
+        let (stops_j, load_j) = routes[route_j].as_ref().unwrap();
+
+        // customers in the middle of a route can't be linked to
+        // anything else without breaking the route apart.
+        let i_at_end = stops_i.first() == Some(&i) || stops_i.last() == Some(&i);
+        let j_at_end = stops_j.first() == Some(&j) || stops_j.last() == Some(&j);
+        let load = load_i.clone() + load_j.clone();
+
+        if !i_at_end || !j_at_end || load > capacity {
+            continue;
+        }
+
+        let (mut stops_i, _) = routes[route_i].take().unwrap();
+        let (mut stops_j, _) = routes[route_j].take().unwrap();
+
+        // line the routes up so that i and j end up next to each other.
+        if stops_i.last() != Some(&i) {
+            stops_i.reverse();
+        }
+
+        if stops_j.first() != Some(&j) {
+            stops_j.reverse();
+        }
+
+        for &customer in stops_j.iter() {
+            route_of.insert(customer, route_i);
+        }
+
+        stops_i.extend(stops_j);
+        routes[route_i] = Some((stops_i, load));
+    }
+
+    Ok(routes
+        .into_iter()
+        .flatten()
+        .map(|(customers, load)| {
+            let first = customers[0];
+            let last = customers[customers.len() - 1];
+            let mut cost = distance(depot, first).unwrap();
+
+            for pair in customers.windows(2) {
+                cost = cost + distance(pair[0], pair[1]).unwrap();
+            }
+
+            Route {
+                customers,
+                load,
+                cost: cost + distance(last, depot).unwrap(),
+            }
+        })
+        .collect())
+}
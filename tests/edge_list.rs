@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+
+use dijkstra::Graph;
+
+fn sample(directed: bool) -> Graph<String, u32> {
+    let mut graph = Graph::empty();
+
+    for name in ["New York", "Boston", "Washington D.C.", "Salem"].iter() {
+        graph.add_vertex(name.to_string());
+    }
+
+    let edges = [
+        ("New York", "Boston", 215),
+        ("Boston", "Washington D.C.", 440),
+    ];
+
+    for &(a, b, weight) in edges.iter() {
+        let (a, b) = (a.to_string(), b.to_string());
+
+        if directed {
+            graph.connect_directed(&a, &b, weight).unwrap();
+        } else {
+            graph.connect_vertices(&a, &b, weight).unwrap();
+        }
+    }
+
+    graph
+}
+
+fn vertices(graph: &Graph<String, u32>) -> HashSet<String> {
+    graph.vertices().cloned().collect()
+}
+
+fn edges(graph: &Graph<String, u32>) -> Vec<(String, String, u32)> {
+    let mut edges: Vec<_> = graph
+        .edges()
+        .map(|(a, b, weight)| (a.clone(), b.clone(), *weight))
+        .collect();
+    edges.sort();
+    edges
+}
+
+#[test]
+fn round_trip_keeps_names_with_spaces() {
+    for &delimiter in [',', ';', '|'].iter() {
+        for &directed in [false, true].iter() {
+            let graph = sample(directed);
+            let text = graph.to_edge_list(delimiter).unwrap();
+            let read: Graph<String, u32> =
+                Graph::parse_edge_list(&text, delimiter, directed).unwrap();
+
+            assert_eq!(vertices(&read), vertices(&graph), "{:?}", text);
+            assert_eq!(edges(&read), edges(&graph), "{:?}", text);
+            assert_eq!(read.neighbors(&"Salem".to_string()).len(), 0);
+        }
+    }
+}
+
+#[test]
+fn isolated_vertex_with_a_space_reads_back_as_one_vertex() {
+    let mut graph: Graph<String, u32> = Graph::empty();
+    graph.add_vertex("New York".to_string());
+
+    let text = graph.to_edge_list(',').unwrap();
+    let read: Graph<String, u32> = Graph::parse_edge_list(&text, ',', false).unwrap();
+
+    assert_eq!(vertices(&read), vertices(&graph));
+    assert_eq!(read.edges().count(), 0);
+}
+
+#[test]
+fn whitespace_delimited_round_trip() {
+    let mut graph: Graph<String, u32> = Graph::empty();
+
+    for name in ["a", "b", "c", "lonely"].iter() {
+        graph.add_vertex(name.to_string());
+    }
+
+    graph
+        .connect_vertices(&"a".to_string(), &"b".to_string(), 3)
+        .unwrap();
+    graph
+        .connect_vertices(&"b".to_string(), &"c".to_string(), 4)
+        .unwrap();
+
+    for &delimiter in [' ', '\t'].iter() {
+        let text = graph.to_edge_list(delimiter).unwrap();
+        let read: Graph<String, u32> = Graph::parse_edge_list(&text, delimiter, false).unwrap();
+
+        assert_eq!(vertices(&read), vertices(&graph));
+        assert_eq!(edges(&read), edges(&graph));
+    }
+
+    // spaces can't be told apart from the delimiter, so they're refused.
+    assert!(sample(false).to_edge_list(' ').is_err());
+}
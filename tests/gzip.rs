@@ -0,0 +1,86 @@
+use dijkstra::gzip::{gunzip, is_gzip};
+use dijkstra::{Graph, GraphError};
+
+// the fixtures were made with python's gzip module, which uses zlib.
+// hello.gz is a single fixed huffman block, stored.gz was written
+// without compression, and edges.txt.gz has dynamic huffman blocks
+// and keeps the original file name in its header.
+const HELLO: &[u8] = include_bytes!("data/hello.gz");
+const STORED: &[u8] = include_bytes!("data/stored.gz");
+const EDGES: &[u8] = include_bytes!("data/edges.txt.gz");
+
+// the same edges that went into edges.txt.gz.
+fn edges() -> Vec<u8> {
+    (0..2000)
+        .map(|i| format!("{} {}\n", i, (i * 7 + 3) % 1000))
+        .collect::<String>()
+        .into_bytes()
+}
+
+fn compression_error(bytes: &[u8]) -> bool {
+    matches!(gunzip(bytes), Err(GraphError::Compression(_)))
+}
+
+#[test]
+fn fixtures() {
+    for fixture in [HELLO, STORED, EDGES].iter() {
+        assert!(is_gzip(fixture));
+    }
+
+    assert_eq!(gunzip(HELLO).unwrap(), b"hello, graph\n");
+    assert_eq!(gunzip(STORED).unwrap(), b"hello, graph\n".repeat(3));
+    assert_eq!(gunzip(EDGES).unwrap(), edges());
+}
+
+#[test]
+fn members_are_joined() {
+    let bytes = [HELLO, EDGES, STORED].concat();
+    let expected = [
+        &b"hello, graph\n"[..],
+        &edges(),
+        &b"hello, graph\n".repeat(3),
+    ]
+    .concat();
+
+    assert_eq!(gunzip(&bytes).unwrap(), expected);
+}
+
+#[test]
+fn trailing_zeros_are_ignored() {
+    let bytes = [HELLO, &[0; 512]].concat();
+    assert_eq!(gunzip(&bytes).unwrap(), b"hello, graph\n");
+
+    // anything else after the last member is still an error.
+    let bytes = [HELLO, &[0, 0, 1]].concat();
+    assert!(compression_error(&bytes));
+}
+
+#[test]
+fn truncated_files_are_rejected() {
+    for fixture in [HELLO, STORED, EDGES].iter() {
+        for &cut in [1, 4, 8, fixture.len() / 2, fixture.len() - 10].iter() {
+            assert!(compression_error(&fixture[..fixture.len() - cut]));
+        }
+    }
+}
+
+#[test]
+fn bad_crc_is_rejected() {
+    for fixture in [HELLO, STORED, EDGES].iter() {
+        let mut bytes = fixture.to_vec();
+        let crc = bytes.len() - 8;
+        bytes[crc] ^= 1;
+
+        assert!(compression_error(&bytes));
+    }
+}
+
+#[test]
+fn snap_loads_gzipped_files() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/edges.txt.gz");
+    let graph: Graph<u64, u32> = Graph::load_snap(path, true).unwrap();
+
+    assert_eq!(graph.vertices().count(), 2000);
+    assert_eq!(graph.edges().count(), 2000);
+    assert_eq!(graph.neighbors(&10), vec![(&73, &1)]);
+}
@@ -0,0 +1,87 @@
+use std::hash::Hash;
+
+use dijkstra::Graph;
+
+// where each vertex can go, in order, so direction shows up.
+fn arcs<V: Hash + Eq + Ord + Clone>(graph: &Graph<V, u32>) -> Vec<(V, V, u32)> {
+    let mut arcs = Vec::new();
+
+    for vertex in graph.vertices() {
+        for (next, &weight) in graph.neighbors(vertex) {
+            arcs.push((vertex.clone(), next.clone(), weight));
+        }
+    }
+
+    arcs.sort();
+    arcs
+}
+
+fn names(arcs: &[(&str, &str, u32)]) -> Vec<(String, String, u32)> {
+    arcs.iter()
+        .map(|&(a, b, weight)| (a.to_string(), b.to_string(), weight))
+        .collect()
+}
+
+#[test]
+fn adj_keeps_direction() {
+    let text = "A B 6 C 5\nB C 2\nD\n";
+
+    let directed: Graph<String, u32> = Graph::parse_adj(text, true).unwrap();
+    assert_eq!(
+        arcs(&directed),
+        names(&[("A", "B", 6), ("A", "C", 5), ("B", "C", 2)])
+    );
+    assert_eq!(directed.vertices().count(), 4);
+
+    let undirected: Graph<String, u32> = Graph::parse_adj(text, false).unwrap();
+    assert_eq!(arcs(&undirected).len(), 6);
+
+    let written = directed.to_adj().unwrap();
+    let read: Graph<String, u32> = Graph::parse_adj(&written, true).unwrap();
+    assert_eq!(arcs(&read), arcs(&directed));
+}
+
+#[test]
+fn general_mtx_keeps_direction() {
+    let text = "%%MatrixMarket matrix coordinate integer general\n\
+                3 3 3\n\
+                1 2 4\n\
+                2 1 7\n\
+                3 1 1\n";
+
+    let directed: Graph<usize, u32> = Graph::parse_mtx(text, true).unwrap();
+    assert_eq!(arcs(&directed), vec![(1, 2, 4), (2, 1, 7), (3, 1, 1)]);
+
+    let undirected: Graph<usize, u32> = Graph::parse_mtx(text, false).unwrap();
+    assert_eq!(
+        arcs(&undirected),
+        vec![(1, 2, 4), (1, 3, 1), (2, 1, 4), (3, 1, 1)]
+    );
+}
+
+#[test]
+fn symmetric_mtx_is_two_way() {
+    let text = "%%MatrixMarket matrix coordinate pattern symmetric\n\
+                % a comment\n\
+                3 3 2\n\
+                2 1\n\
+                3 3\n";
+
+    let graph: Graph<usize, u32> = Graph::parse_mtx(text, true).unwrap();
+    assert_eq!(arcs(&graph), vec![(1, 2, 1), (2, 1, 1)]);
+    assert_eq!(graph.vertices().count(), 3);
+}
+
+#[test]
+fn snap_keeps_direction() {
+    let text = "# Directed graph\n# FromNodeId\tToNodeId\n0\t1\n1\t0\n1\t2\n1\t2\n2\t2\n";
+
+    let directed: Graph<u64, u32> = Graph::from_snap(text.as_bytes(), true).unwrap();
+    assert_eq!(arcs(&directed), vec![(0, 1, 1), (1, 0, 1), (1, 2, 1)]);
+
+    let undirected: Graph<u64, u32> = Graph::from_snap(text.as_bytes(), false).unwrap();
+    assert_eq!(
+        arcs(&undirected),
+        vec![(0, 1, 1), (1, 0, 1), (1, 2, 1), (2, 1, 1)]
+    );
+}